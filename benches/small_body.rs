@@ -0,0 +1,107 @@
+//! Measures the allocations and the time needed to create a small body and
+//! read it, either by sending it through `BodyHttp` or with a sync reader.
+//!
+//! Bodies created with `Body::copy_from_slice` are stored inline if they are
+//! not longer than `Body::INLINE_LEN`, `heap` shows the same body stored in
+//! `Bytes`.
+//!
+//! Run with `cargo bench --bench small_body`.
+
+use fire_http_representation::Body;
+
+use std::hint::black_box;
+use std::pin::pin;
+use std::io::Read;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+
+use bytes::Bytes;
+
+use hyper::body::Body as HttpBody;
+
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		unsafe { System.alloc(layout) }
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		unsafe { System.dealloc(ptr, layout) }
+	}
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ITERATIONS: usize = 1_000_000;
+const PAYLOAD: &str = "{\"ok\":true}";
+
+/// Polls all frames like hyper does when it sends the body.
+fn send(body: Body) -> usize {
+	let mut body = pin!(body.into_http_body());
+	let mut cx = Context::from_waker(Waker::noop());
+	let mut len = 0;
+	loop {
+		match body.as_mut().poll_frame(&mut cx) {
+			Poll::Ready(Some(Ok(frame))) => {
+				len += frame.into_data().unwrap().len();
+			},
+			Poll::Ready(None) => return len,
+			_ => unreachable!()
+		}
+	}
+}
+
+/// Reads the body like a server which writes it into its own buffer.
+fn read(body: Body) -> usize {
+	let mut buf = [0u8; 64];
+	let mut reader = body.into_sync_reader();
+	let mut len = 0;
+	loop {
+		match reader.read(&mut buf).unwrap() {
+			0 => return len,
+			read => len += black_box(&buf[..read]).len()
+		}
+	}
+}
+
+fn inline() -> Body {
+	Body::copy_from_slice(black_box(PAYLOAD))
+}
+
+fn heap() -> Body {
+	Body::from(Bytes::copy_from_slice(black_box(PAYLOAD).as_bytes()))
+}
+
+fn bench(name: &str, f: impl Fn() -> usize) {
+	let allocs = ALLOCATIONS.load(Ordering::Relaxed);
+	let start = Instant::now();
+	for _ in 0..ITERATIONS {
+		black_box(f());
+	}
+	let elapsed = start.elapsed();
+	let allocs = ALLOCATIONS.load(Ordering::Relaxed) - allocs;
+
+	println!(
+		"{name:<20} {:>6.1} ns/body {:>5.2} allocations/body",
+		elapsed.as_nanos() as f64 / ITERATIONS as f64,
+		allocs as f64 / ITERATIONS as f64
+	);
+}
+
+fn main() {
+	bench("send static", || send(Body::from(black_box(PAYLOAD))));
+	bench("send inline", || send(inline()));
+	bench("send heap", || send(heap()));
+	bench("send empty", || send(Body::new()));
+	bench("read static", || read(Body::from(black_box(PAYLOAD))));
+	bench("read inline", || read(inline()));
+	bench("read heap", || read(heap()));
+}
@@ -1,4 +1,5 @@
 use std::{fs, env};
+use std::collections::HashSet;
 use std::path::Path;
 use std::fmt::Write;
 
@@ -10,6 +11,7 @@ fn main() {
 }
 
 // (const, canHaveCharsetUtf8, [types..], [extensions..])
+// if an extension is used multiple times the first mime is used
 const MIMES: &[(&str, bool, &[&str], &[&str])] = &[
 	// text
 	("TEXT", true, &["text/plain"], &["txt"]),
@@ -17,6 +19,8 @@ const MIMES: &[(&str, bool, &[&str], &[&str])] = &[
 	("JS", true, &["application/javascript"], &["js"]),
 	("CSS", true, &["text/css"], &["css"]),
 	("JSON", true, &["application/json"], &["json"]),
+	("JSON_PATCH", true, &["application/json-patch+json"], &["json"]),
+	("JSON_MERGE_PATCH", true, &["application/merge-patch+json"], &["json"]),
 	("CSV", true, &["text/csv"], &["csv"]),
 	("DOC", false, &["application/msword"], &["doc"]),
 	("DOCX", false,
@@ -28,7 +32,9 @@ const MIMES: &[(&str, bool, &[&str], &[&str])] = &[
 	("RTF", false, &["application/rtf"], &["rtf"]),
 	("SH", false, &["application/x-sh"], &["sh"]),
 	("VSD", false, &["application/vnd.visio"], &["vsd"]),
-	("XML", true, &["text/xml"], &["xml"]),
+	("XML", true, &["text/xml", "application/xml"], &["xml"]),
+	("RSS", true, &["application/rss+xml"], &["rss"]),
+	("ATOM", true, &["application/atom+xml"], &["atom"]),
 
 	// imgs
 	("JPG", false, &["image/jpeg"], &["jpg"]),
@@ -76,6 +82,7 @@ fn generate_mime(out_dir: &str) {
 	let mut value_enum = format!(
 		"\
 		#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n\
+		#[allow(non_camel_case_types)]\n\
 		enum MimeValue {{\n"
 	);
 
@@ -101,6 +108,8 @@ fn generate_mime(out_dir: &str) {
 		\t\tmatch self {{\n"
 	);
 
+	let mut seen_extensions = HashSet::new();
+
 	for (id, utf8, types, extensions) in MIMES {
 		write!(value_enum,
 			"\t{id},\n"
@@ -115,6 +124,10 @@ fn generate_mime(out_dir: &str) {
 		).unwrap();
 
 		for ext in *extensions {
+			if !seen_extensions.insert(*ext) {
+				continue
+			}
+
 			write!(from_extension_fn,
 				"\t\t\t\"{ext}\" => Some(Self::{id}),\n"
 			).unwrap();
@@ -0,0 +1,32 @@
+#![no_main]
+
+use fire_http_representation::header::{
+	HttpDate, Priority, TraceParent, TraceState, SetCookie, Query,
+	ContentEncoding
+};
+use fire_http_representation::header::cookie::parse_cookie_pairs;
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+	if let Ok(date) = s.parse::<HttpDate>() {
+		assert_eq!(date.to_string().parse::<HttpDate>().unwrap(), date);
+	}
+
+	if let Ok(prio) = s.parse::<Priority>() {
+		assert_eq!(prio.to_string().parse::<Priority>().unwrap(), prio);
+	}
+
+	if let Ok(tp) = s.parse::<TraceParent>() {
+		let _ = tp.to_string();
+	}
+
+	if let Ok(ts) = s.parse::<TraceState>() {
+		assert_eq!(ts.to_string().parse::<TraceState>().unwrap(), ts);
+	}
+
+	let _ = s.parse::<SetCookie>();
+	let _ = parse_cookie_pairs(s).count();
+	let _ = Query::parse(s).to_query_string();
+	let _ = ContentEncoding::parse_list(s);
+});
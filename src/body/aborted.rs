@@ -0,0 +1,134 @@
+use std::sync::{Arc, Mutex};
+use std::future::poll_fn;
+use std::task::{Poll, Waker};
+
+
+#[derive(Debug, Default)]
+struct State {
+	aborted: bool,
+	wakers: Vec<Waker>
+}
+
+/// Allows to detect if a body could not be sent completely, for example
+/// because the client disconnected, see `Body::aborted_handle`.
+#[derive(Debug, Clone, Default)]
+pub struct AbortedHandle {
+	state: Arc<Mutex<State>>
+}
+
+impl AbortedHandle {
+	pub(super) fn new() -> Self {
+		Self::default()
+	}
+
+	pub(super) fn set_aborted(&self) {
+		let mut state = self.state.lock().unwrap();
+		state.aborted = true;
+		for waker in state.wakers.drain(..) {
+			waker.wake();
+		}
+	}
+
+	/// Returns true if the body was dropped before it was completely read or
+	/// if reading failed.
+	pub fn is_aborted(&self) -> bool {
+		self.state.lock().unwrap().aborted
+	}
+
+	/// Resolves once the body was aborted.
+	///
+	/// If the body is read completely this never resolves, use it together
+	/// with other futures, for example in `tokio::select!`.
+	pub async fn aborted(&self) {
+		poll_fn(|cx| {
+			let mut state = self.state.lock().unwrap();
+			if state.aborted {
+				return Poll::Ready(())
+			}
+
+			if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+				state.wakers.push(cx.waker().clone());
+			}
+
+			Poll::Pending
+		}).await
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use crate::Body;
+
+	use std::io;
+	use std::time::Duration;
+
+	use bytes::Bytes;
+
+	use tokio::time::timeout;
+
+	const SHORT: Duration = Duration::from_millis(20);
+
+	#[tokio::test]
+	async fn test_read_completely() {
+
+		let (body, handle) = Body::from("hello").aborted_handle();
+		assert_eq!(body.into_bytes().await.unwrap(), "hello");
+		assert!(!handle.is_aborted());
+		assert!(timeout(SHORT, handle.aborted()).await.is_err());
+
+	}
+
+	#[tokio::test]
+	async fn test_dropped() {
+
+		// never read
+		let (body, handle) = Body::from("hello").aborted_handle();
+		assert!(!handle.is_aborted());
+		drop(body);
+		assert!(handle.is_aborted());
+		timeout(SHORT, handle.aborted()).await.unwrap();
+
+		// partially read in another task
+		let (reader, mut writer) = tokio::io::duplex(16);
+		let (body, handle) = Body::from_async_reader(reader).aborted_handle();
+
+		let waiter = tokio::spawn({
+			let handle = handle.clone();
+			async move { handle.aborted().await }
+		});
+
+		let task = tokio::spawn(async move {
+			let mut stream = Box::pin(body.into_async_bytes_streamer());
+			tokio_stream::StreamExt::next(&mut stream).await
+				.unwrap()
+				.unwrap();
+			// the body gets dropped before it was read completely
+		});
+
+		tokio::io::AsyncWriteExt::write_all(&mut writer, b"abc").await
+			.unwrap();
+		task.await.unwrap();
+
+		timeout(SHORT * 10, waiter).await.unwrap().unwrap();
+		assert!(handle.is_aborted());
+
+	}
+
+	#[tokio::test]
+	async fn test_error() {
+
+		let chunks: Vec<io::Result<Bytes>> = vec![
+			Ok(Bytes::from("a")),
+			Err(io::Error::other("failed"))
+		];
+		let (body, handle) = Body::from_async_bytes_streamer(
+			tokio_stream::iter(chunks)
+		).aborted_handle();
+
+		assert!(body.into_bytes().await.is_err());
+		assert!(handle.is_aborted());
+
+	}
+
+}
@@ -1,7 +1,9 @@
 use super::{
 	size_limit_reached, timed_out, Constraints, BoxedSyncRead, PinnedAsyncRead,
-	PinnedAsyncBytesStream, IncomingAsAsyncBytesStream
+	PinnedAsyncBytesStream, WriterStream, MoreBytes
 };
+#[cfg(feature = "hyper_body")]
+use super::IncomingAsAsyncBytesStream;
 
 use std::{io, mem};
 use std::pin::Pin;
@@ -29,7 +31,12 @@ impl BodyAsyncBytesStreamer {
 	pub(super) fn new(inner: super::Inner, constraints: Constraints) -> Self {
 		let inner = match inner {
 			super::Inner::Empty => Inner::Empty,
+			// an empty chunk should never be returned
+			super::Inner::Bytes(b) if b.is_empty() => Inner::Empty,
 			super::Inner::Bytes(b) => Inner::Bytes(b),
+			super::Inner::Small(s) => Inner::Bytes(s.to_bytes()),
+			super::Inner::MoreBytes(m) => Inner::MoreBytes(m),
+			#[cfg(feature = "hyper_body")]
 			super::Inner::Incoming(i) => Inner::Incoming(
 				IncomingAsAsyncBytesStream::new(i)
 			),
@@ -40,13 +47,49 @@ impl BodyAsyncBytesStreamer {
 			super::Inner::AsyncReader(r) => Inner::AsyncReader(
 				ReaderStream::new(r)
 			),
-			super::Inner::AsyncBytesStreamer(s) => Inner::AsyncBytesStreamer(s)
+			super::Inner::AsyncBytesStreamer(s) => Inner::AsyncBytesStreamer(s),
+			super::Inner::Writer(w) => Inner::Writer(w)
 		};
 
 		Self {
 			inner: ConstrainedAsyncBytesStreamer::new(inner, constraints)
 		}
 	}
+
+	/// Returns true if the producer requested the last returned chunk to be
+	/// flushed immediately.
+	/// 
+	/// This is only the case for bodies created directly with
+	/// `Body::from_writer`, adapters like `Body::through` do not keep the
+	/// hint.
+	pub fn last_chunk_flushed(&self) -> bool {
+		match &self.inner.inner {
+			Inner::Writer(w) => w.last_flush(),
+			_ => false
+		}
+	}
+
+	/// Returns the number of bytes which are left if it is known.
+	#[cfg(feature = "hyper_body")]
+	pub(super) fn remaining_len(&self) -> Option<u64> {
+		match &self.inner.inner {
+			Inner::Empty => Some(0),
+			Inner::Bytes(b) => Some(b.len() as u64),
+			Inner::MoreBytes(m) => Some(m.len() as u64),
+			_ => None
+		}
+	}
+
+	/// Returns the number of bytes read so far.
+	pub fn bytes_read(&self) -> u64 {
+		self.inner.bytes_read()
+	}
+
+	/// Returns how many bytes can still be read before the size limit is
+	/// reached, or `None` if there is no limit.
+	pub fn size_limit_remaining(&self) -> Option<usize> {
+		self.inner.size_limit_remaining()
+	}
 }
 
 impl Stream for BodyAsyncBytesStreamer {
@@ -66,13 +109,16 @@ const DEFAULT_CAP: usize = 4096;
 enum Inner {
 	Empty,
 	Bytes(Bytes),
+	MoreBytes(MoreBytes),
+	#[cfg(feature = "hyper_body")]
 	Incoming(IncomingAsAsyncBytesStream),
 	SyncReader {
 		reader: BoxedSyncRead,
 		buf: BytesMut
 	},
 	AsyncReader(ReaderStream<PinnedAsyncRead>),
-	AsyncBytesStreamer(PinnedAsyncBytesStream)
+	AsyncBytesStreamer(PinnedAsyncBytesStream),
+	Writer(WriterStream)
 }
 
 impl Stream for Inner {
@@ -91,6 +137,14 @@ impl Stream for Inner {
 				*me = Self::Empty;
 				Poll::Ready(Some(Ok(bytes)))
 			},
+			Self::MoreBytes(m) => match m.pop_front() {
+				Some(bytes) => Poll::Ready(Some(Ok(bytes))),
+				None => {
+					*me = Self::Empty;
+					Poll::Ready(None)
+				}
+			},
+			#[cfg(feature = "hyper_body")]
 			Self::Incoming(i) => Pin::new(i).poll_next(cx),
 			Self::SyncReader { reader, buf } => {
 				if buf.len() == 0 {
@@ -104,10 +158,16 @@ impl Stream for Inner {
 					Err(e) => return Poll::Ready(Some(Err(e)))
 				};
 
+				if read == 0 {
+					*me = Self::Empty;
+					return Poll::Ready(None)
+				}
+
 				Poll::Ready(Some(Ok(buf.split_to(read).into())))
 			},
 			Self::AsyncReader(s) => Pin::new(s).poll_next(cx),
-			Self::AsyncBytesStreamer(s) => Pin::new(s).poll_next(cx)
+			Self::AsyncBytesStreamer(s) => Pin::new(s).poll_next(cx),
+			Self::Writer(w) => Pin::new(w).poll_next(cx)
 		}
 	}
 }
@@ -119,7 +179,8 @@ pin_project! {
 		inner: S,
 		#[pin]
 		timeout: Option<Sleep>,
-		size_limit: Option<usize>
+		size_limit: Option<usize>,
+		read: u64
 	}
 }
 
@@ -128,9 +189,18 @@ impl<S> ConstrainedAsyncBytesStreamer<S> {
 		Self {
 			inner: streamer,
 			timeout: constraints.timeout.map(tokio::time::sleep),
-			size_limit: constraints.size
+			size_limit: constraints.size,
+			read: 0
 		}
 	}
+
+	pub fn bytes_read(&self) -> u64 {
+		self.read
+	}
+
+	pub fn size_limit_remaining(&self) -> Option<usize> {
+		self.size_limit
+	}
 }
 
 impl<S> Stream for ConstrainedAsyncBytesStreamer<S>
@@ -150,6 +220,8 @@ where S: Stream<Item=io::Result<Bytes>> {
 				None => return Poll::Ready(None)
 			};
 
+			*me.read += bytes.len() as u64;
+
 			// validate size_limit
 			if let Some(size_limit) = &mut me.size_limit {
 				match size_limit.checked_sub(bytes.len()) {
@@ -190,4 +262,25 @@ pub(super) async fn async_bytes_streamer_into_bytes(
 	}
 
 	Ok(v.into())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::Body;
+
+	use std::io;
+
+	use tokio_stream::StreamExt;
+
+	#[tokio::test]
+	async fn test_sync_reader_ends() {
+
+		let body = Body::from_sync_reader(io::Cursor::new(b"hello".to_vec()));
+		let mut stream = Box::pin(body.into_async_bytes_streamer());
+
+		assert_eq!(stream.next().await.unwrap().unwrap(), "hello");
+		assert!(stream.next().await.is_none());
+
+	}
+
+}
@@ -1,7 +1,9 @@
 use super::{
 	size_limit_reached, timed_out, BoxedSyncRead, PinnedAsyncRead,
-	PinnedAsyncBytesStream, Constraints, IncomingAsAsyncBytesStream
+	PinnedAsyncBytesStream, Constraints, MoreBytes, SmallBytes
 };
+#[cfg(feature = "hyper_body")]
+use super::IncomingAsAsyncBytesStream;
 
 use std::io;
 use std::pin::Pin;
@@ -27,6 +29,9 @@ impl BodyAsyncReader {
 		let inner = match inner {
 			super::Inner::Empty => Inner::Bytes(Bytes::new()),
 			super::Inner::Bytes(b) => Inner::Bytes(b),
+			super::Inner::Small(s) => Inner::Small(s),
+			super::Inner::MoreBytes(m) => Inner::MoreBytes(m),
+			#[cfg(feature = "hyper_body")]
 			super::Inner::Incoming(i) => Inner::Incoming(
 				StreamReader::new(IncomingAsAsyncBytesStream::new(i))
 			),
@@ -34,6 +39,9 @@ impl BodyAsyncReader {
 			super::Inner::AsyncReader(r) => Inner::AsyncReader(r),
 			super::Inner::AsyncBytesStreamer(s) => {
 				Inner::AsyncBytesStreamer(StreamReader::new(s))
+			},
+			super::Inner::Writer(w) => {
+				Inner::AsyncBytesStreamer(StreamReader::new(Box::pin(w)))
 			}
 		};
 
@@ -41,6 +49,17 @@ impl BodyAsyncReader {
 			reader: ConstrainedAsyncReader::new(inner, constraints)
 		}
 	}
+
+	/// Returns the number of bytes read so far.
+	pub fn bytes_read(&self) -> u64 {
+		self.reader.bytes_read()
+	}
+
+	/// Returns how many bytes can still be read before the size limit is
+	/// reached, or `None` if there is no limit.
+	pub fn size_limit_remaining(&self) -> Option<usize> {
+		self.reader.size_limit_remaining()
+	}
 }
 
 impl AsyncRead for BodyAsyncReader {
@@ -56,6 +75,9 @@ impl AsyncRead for BodyAsyncReader {
 
 enum Inner {
 	Bytes(Bytes),
+	Small(SmallBytes),
+	MoreBytes(MoreBytes),
+	#[cfg(feature = "hyper_body")]
 	Incoming(StreamReader<IncomingAsAsyncBytesStream, Bytes>),
 	SyncReader(BoxedSyncRead),
 	AsyncReader(PinnedAsyncRead),
@@ -80,6 +102,23 @@ impl AsyncRead for Inner {
 				buf.put_slice(&b.split_to(read));
 				Poll::Ready(Ok(()))
 			},
+			Self::Small(s) => {
+				let read = buf.remaining().min(s.len());
+				buf.put_slice(&s[..read]);
+				s.advance(read);
+				Poll::Ready(Ok(()))
+			},
+			Self::MoreBytes(m) => {
+				let Some(b) = m.front_mut() else {
+					return Poll::Ready(Ok(()))
+				};
+
+				let read = buf.remaining().min(b.len());
+				buf.put_slice(&b.split_to(read));
+				m.advanced(read);
+				Poll::Ready(Ok(()))
+			},
+			#[cfg(feature = "hyper_body")]
 			Self::Incoming(i) => Pin::new(i).poll_read(cx, buf),
 			Self::SyncReader(r) => {
 				// todo implement this without blocking the current thread
@@ -104,7 +143,8 @@ pin_project! {
 		inner: R,
 		#[pin]
 		timeout: Option<Sleep>,
-		size_limit: Option<usize>
+		size_limit: Option<usize>,
+		read: u64
 	}
 }
 
@@ -113,9 +153,18 @@ impl<R> ConstrainedAsyncReader<R> {
 		Self {
 			inner: reader,
 			timeout: constraints.timeout.map(tokio::time::sleep),
-			size_limit: constraints.size
+			size_limit: constraints.size,
+			read: 0
 		}
 	}
+
+	pub fn bytes_read(&self) -> u64 {
+		self.read
+	}
+
+	pub fn size_limit_remaining(&self) -> Option<usize> {
+		self.size_limit
+	}
 }
 
 impl<R: AsyncRead> AsyncRead for ConstrainedAsyncReader<R> {
@@ -133,9 +182,11 @@ impl<R: AsyncRead> AsyncRead for ConstrainedAsyncReader<R> {
 				return Poll::Ready(Err(e))
 			}
 
+			let read = buf.filled().len() - prev_filled;
+			*me.read += read as u64;
+
 			// validate size_limit
 			if let Some(size_limit) = &mut me.size_limit {
-				let read = buf.filled().len() - prev_filled;
 				match size_limit.checked_sub(read) {
 					Some(ns) => *size_limit = ns,
 					None => return Poll::Ready(Err(size_limit_reached(
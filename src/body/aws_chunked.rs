@@ -0,0 +1,267 @@
+//! The `aws-chunked` content encoding used by S3 for streaming uploads.
+//!
+//! Each chunk is sent as `<hex len>[;chunk-signature=<sig>]\r\n<data>\r\n`,
+//! followed by an empty chunk, optional trailers and an empty line.
+
+use super::Body;
+
+use std::io;
+use std::pin::pin;
+
+use bytes::{Bytes, BytesMut, Buf};
+
+use tokio_stream::StreamExt;
+
+
+/// The trailer containing the base64 encoded crc32 checksum of the data.
+pub const CRC32_TRAILER: &str = "x-amz-checksum-crc32";
+
+// limits the size of a chunk header or trailer line
+const MAX_LINE_LEN: usize = 4 * 1024;
+
+impl Body {
+	/// Encodes the body with the `aws-chunked` content encoding
+	/// (`STREAMING-UNSIGNED-PAYLOAD-TRAILER`).
+	///
+	/// Chunks are at most `chunk_size` bytes long. If `crc32` is true the
+	/// checksum of the data is sent in the `x-amz-checksum-crc32` trailer,
+	/// the request then needs the header `x-amz-trailer` with that name.
+	///
+	/// ## Panics
+	/// If `chunk_size` is zero.
+	pub fn encode_aws_chunked(self, chunk_size: usize, crc32: bool) -> Self {
+		assert!(chunk_size > 0, "chunk_size needs to be bigger than zero");
+
+		Self::from_writer(move |mut writer| async move {
+			let mut stream = pin!(self.into_async_bytes_streamer());
+			let mut crc = Crc32::new();
+
+			while let Some(mut bytes) = stream.next().await.transpose()? {
+				while !bytes.is_empty() {
+					let chunk = bytes.split_to(chunk_size.min(bytes.len()));
+					crc.update(&chunk);
+
+					writer.write(format!("{:x}\r\n", chunk.len())).await?;
+					writer.write(chunk).await?;
+					writer.write("\r\n").await?;
+					writer.flush().await?;
+				}
+			}
+
+			writer.write("0\r\n").await?;
+			if crc32 {
+				let checksum = base64_encode(&crc.finish().to_be_bytes());
+				writer.write(format!("{CRC32_TRAILER}:{checksum}\r\n")).await?;
+			}
+			writer.write("\r\n").await?;
+
+			Ok(())
+		})
+	}
+
+	/// Decodes a body with the `aws-chunked` content encoding.
+	///
+	/// If the `x-amz-checksum-crc32` trailer is present the checksum is
+	/// verified once the body was read completely.
+	///
+	/// ## Note
+	/// Chunk signatures and other checksums are not verified.
+	///
+	/// ## Errors
+	/// Reading returns an `InvalidData` error if the encoding is not valid or
+	/// the checksum does not match.
+	pub fn decode_aws_chunked(self) -> Self {
+		Self::from_writer(move |mut writer| async move {
+			let mut decoder = Decoder {
+				stream: pin!(self.into_async_bytes_streamer()),
+				buf: BytesMut::new()
+			};
+			let mut crc = Crc32::new();
+
+			loop {
+				let line = decoder.line().await?;
+				let len = line.split(';').next().unwrap().trim();
+				let len = usize::from_str_radix(len, 16)
+					.map_err(|_| invalid_data("invalid chunk size"))?;
+				if len == 0 {
+					break
+				}
+
+				let mut rest = len;
+				while rest > 0 {
+					let data = decoder.data(rest).await?;
+					rest -= data.len();
+					crc.update(&data);
+					writer.write(data).await?;
+				}
+
+				if !decoder.line().await?.is_empty() {
+					return Err(invalid_data("missing chunk end"))
+				}
+				writer.flush().await?;
+			}
+
+			loop {
+				let line = decoder.line().await?;
+				if line.is_empty() {
+					break
+				}
+
+				let (name, value) = line.split_once(':')
+					.ok_or_else(|| invalid_data("invalid trailer"))?;
+				if name.trim().eq_ignore_ascii_case(CRC32_TRAILER) {
+					let expected = base64_encode(&crc.finish().to_be_bytes());
+					if value.trim() != expected {
+						return Err(invalid_data("crc32 checksum mismatch"))
+					}
+				}
+			}
+
+			Ok(())
+		})
+	}
+}
+
+struct Decoder<S> {
+	stream: S,
+	buf: BytesMut
+}
+
+impl<S> Decoder<S>
+where S: tokio_stream::Stream<Item=io::Result<Bytes>> + Unpin {
+	async fn fill(&mut self) -> io::Result<()> {
+		match self.stream.next().await.transpose()? {
+			Some(bytes) => {
+				self.buf.extend_from_slice(&bytes);
+				Ok(())
+			},
+			None => Err(io::Error::new(
+				io::ErrorKind::UnexpectedEof,
+				"aws-chunked body ended early"
+			))
+		}
+	}
+
+	/// Reads a line without `\r\n`.
+	async fn line(&mut self) -> io::Result<String> {
+		loop {
+			if let Some(pos) = self.buf.windows(2).position(|w| w == b"\r\n") {
+				let line = self.buf.split_to(pos);
+				self.buf.advance(2);
+
+				return String::from_utf8(line.to_vec())
+					.map_err(|_| invalid_data("line is not valid utf8"))
+			}
+
+			if self.buf.len() > MAX_LINE_LEN {
+				return Err(invalid_data("line too long"))
+			}
+
+			self.fill().await?;
+		}
+	}
+
+	/// Returns at most `max` bytes of data.
+	async fn data(&mut self, max: usize) -> io::Result<Bytes> {
+		if self.buf.is_empty() {
+			self.fill().await?;
+		}
+
+		let len = max.min(self.buf.len());
+		Ok(self.buf.split_to(len).freeze())
+	}
+}
+
+fn invalid_data(msg: &'static str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// CRC-32 (IEEE) as used by `x-amz-checksum-crc32`.
+struct Crc32(u32);
+
+impl Crc32 {
+	fn new() -> Self {
+		Self(!0)
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		for b in data {
+			self.0 ^= *b as u32;
+			for _ in 0..8 {
+				let mask = (self.0 & 1).wrapping_neg();
+				self.0 = (self.0 >> 1) ^ (0xedb88320 & mask);
+			}
+		}
+	}
+
+	fn finish(&self) -> u32 {
+		!self.0
+	}
+}
+
+fn base64_encode(data: &[u8]) -> String {
+	const CHARS: &[u8; 64] =
+		b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+	let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+	for chunk in data.chunks(3) {
+		let n = chunk.iter()
+			.enumerate()
+			.fold(0u32, |n, (i, b)| n | ((*b as u32) << (16 - i * 8)));
+
+		for i in 0..4 {
+			if i <= chunk.len() {
+				out.push(CHARS[(n >> (18 - i * 6)) as usize & 63] as char);
+			} else {
+				out.push('=');
+			}
+		}
+	}
+
+	out
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_crc32() {
+		let mut crc = Crc32::new();
+		crc.update(b"123456789");
+		assert_eq!(crc.finish(), 0xcbf43926);
+		assert_eq!(base64_encode(&0xcbf43926u32.to_be_bytes()), "y/Q5Jg==");
+		assert_eq!(base64_encode(b"ab"), "YWI=");
+	}
+
+	#[tokio::test]
+	async fn test_roundtrip() {
+
+		let body = Body::from("hello world").encode_aws_chunked(4, true);
+		let encoded = body.into_bytes().await.unwrap();
+		assert_eq!(
+			encoded,
+			"4\r\nhell\r\n4\r\no wo\r\n3\r\nrld\r\n0\r\n\
+			x-amz-checksum-crc32:DUoRhQ==\r\n\r\n"
+		);
+
+		let decoded = Body::from(encoded).decode_aws_chunked()
+			.into_bytes().await.unwrap();
+		assert_eq!(decoded, "hello world");
+
+		let signed = "5;chunk-signature=abc\r\nhello\r\n\
+			0;chunk-signature=def\r\n\r\n";
+		let decoded = Body::from(signed).decode_aws_chunked()
+			.into_bytes().await.unwrap();
+		assert_eq!(decoded, "hello");
+
+		let corrupt = "5\r\nhallo\r\n0\r\n\
+			x-amz-checksum-crc32:NhCmhg==\r\n\r\n";
+		let e = Body::from(corrupt).decode_aws_chunked()
+			.into_bytes().await.unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+	}
+
+}
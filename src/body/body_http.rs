@@ -4,7 +4,7 @@ use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use hyper::body::{Body, Incoming, Frame};
+use hyper::body::{Body, Incoming, Frame, SizeHint};
 
 use futures_core::Stream;
 
@@ -25,6 +25,14 @@ impl BodyHttp {
 			inner: BodyAsyncBytesStreamer::new(inner, constraints)
 		}
 	}
+
+	/// Returns true if the producer requested the data of the last returned
+	/// frame to be flushed immediately.
+	/// 
+	/// See `BodyAsyncBytesStreamer::last_chunk_flushed`.
+	pub fn last_chunk_flushed(&self) -> bool {
+		self.inner.last_chunk_flushed()
+	}
 }
 
 impl Body for BodyHttp {
@@ -43,6 +51,18 @@ impl Body for BodyHttp {
 			Poll::Pending => Poll::Pending
 		}
 	}
+
+	/// Empty bodies end immediately, so hyper does not need to poll them.
+	fn is_end_stream(&self) -> bool {
+		self.inner.remaining_len() == Some(0)
+	}
+
+	fn size_hint(&self) -> SizeHint {
+		match self.inner.remaining_len() {
+			Some(len) => SizeHint::with_exact(len),
+			None => SizeHint::default()
+		}
+	}
 }
 
 
@@ -69,8 +89,8 @@ impl Stream for IncomingAsAsyncBytesStream {
 			let r = match Pin::new(&mut me.inner).poll_frame(cx) {
 				Poll::Ready(Some(Ok(frame))) => {
 					match frame.into_data() {
-						Some(d) => Poll::Ready(Some(Ok(d))),
-						None => continue
+						Ok(d) => Poll::Ready(Some(Ok(d))),
+						Err(_) => continue
 					}
 				},
 				Poll::Ready(Some(Err(e))) => {
@@ -86,4 +106,47 @@ impl Stream for IncomingAsAsyncBytesStream {
 			break r
 		}
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use hyper::body::Body as _;
+
+	use crate::Body;
+
+	#[test]
+	fn test_end_stream() {
+
+		let body = Body::from(bytes::Bytes::new()).into_http_body();
+		assert!(body.is_end_stream());
+		assert_eq!(body.size_hint().exact(), Some(0));
+
+		let body = Body::from("hey").into_http_body();
+		assert!(!body.is_end_stream());
+		assert_eq!(body.size_hint().exact(), Some(3));
+
+	}
+
+	#[tokio::test]
+	async fn test_last_chunk_flushed() {
+
+		let body = Body::from_writer(|mut writer| async move {
+			writer.write("<head></head>").await?;
+			writer.flush().await?;
+			writer.write(vec![b'a'; 5000]).await
+		});
+		let mut body = Box::pin(body.into_http_body());
+
+		let frame = std::future::poll_fn(|cx| body.as_mut().poll_frame(cx))
+			.await.unwrap().unwrap();
+		assert_eq!(frame.into_data().unwrap(), "<head></head>");
+		assert!(body.last_chunk_flushed());
+
+		let frame = std::future::poll_fn(|cx| body.as_mut().poll_frame(cx))
+			.await.unwrap().unwrap();
+		assert_eq!(frame.into_data().unwrap().len(), 5000);
+		assert!(!body.last_chunk_flushed());
+
+	}
+
+}
@@ -0,0 +1,323 @@
+use std::io;
+use std::pin::Pin;
+use std::future::{Future, poll_fn};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use futures_core::Stream;
+
+use bytes::{Bytes, BytesMut};
+
+use super::SyncWrapper;
+
+
+const DEFAULT_CAP: usize = 4096;
+
+type PinnedWriterFuture = Pin<Box<
+	dyn Future<Output=io::Result<()>> + Send + Sync
+>>;
+
+#[derive(Debug, Default)]
+struct Shared {
+	buf: BytesMut,
+	// a chunk which waits to be returned from the stream
+	// and if it should be flushed
+	pending: Option<(Bytes, bool)>,
+	// set when the stream was dropped, nobody takes chunks anymore
+	closed: bool,
+	// woken when a chunk is pending
+	stream_waker: Option<Waker>,
+	// woken when the pending chunk was taken or the stream was dropped
+	writer_waker: Option<Waker>
+}
+
+/// Allows to write a body in chunks, see `Body::from_writer`.
+///
+/// Written data is buffered until either `flush` is called or the buffer
+/// gets to large.
+#[derive(Debug)]
+pub struct ChunkWriter {
+	shared: Arc<Mutex<Shared>>
+}
+
+impl ChunkWriter {
+	/// Writes some bytes to the buffer, if the buffer gets to large a chunk
+	/// is sent without requesting it to be flushed.
+	///
+	/// ## Errors
+	/// Returns a `BrokenPipe` error if the body was dropped.
+	pub async fn write(&mut self, buf: impl AsRef<[u8]>) -> io::Result<()> {
+		let full = {
+			let mut shared = self.shared.lock().unwrap();
+			if shared.closed {
+				return Err(closed())
+			}
+
+			shared.buf.extend_from_slice(buf.as_ref());
+			shared.buf.len() >= DEFAULT_CAP
+		};
+
+		if full {
+			self.send(false).await?;
+		}
+
+		Ok(())
+	}
+
+	/// Sends the buffered bytes as a chunk which should be flushed
+	/// immediately.
+	///
+	/// Does nothing if the buffer is empty.
+	///
+	/// ## Errors
+	/// Returns a `BrokenPipe` error if the body was dropped.
+	pub async fn flush(&mut self) -> io::Result<()> {
+		self.send(true).await
+	}
+
+	async fn send(&mut self, flush: bool) -> io::Result<()> {
+		// a previous send might have been cancelled before its chunk was
+		// taken
+		self.taken().await?;
+
+		{
+			let mut shared = self.shared.lock().unwrap();
+			if shared.buf.is_empty() {
+				return Ok(())
+			}
+
+			let bytes = shared.buf.split().freeze();
+			shared.pending = Some((bytes, flush));
+			if let Some(waker) = shared.stream_waker.take() {
+				waker.wake();
+			}
+		}
+
+		self.taken().await
+	}
+
+	/// Waits until no chunk is pending.
+	async fn taken(&self) -> io::Result<()> {
+		// if the writer runs inside the future of the stream, the pending
+		// chunk is returned immediately after we return Pending, else (for
+		// example in a spawned task) we get woken when it was taken
+		poll_fn(|cx| {
+			let mut shared = self.shared.lock().unwrap();
+			if shared.closed {
+				return Poll::Ready(Err(closed()))
+			}
+
+			match shared.pending {
+				Some(_) => {
+					shared.writer_waker = Some(cx.waker().clone());
+					Poll::Pending
+				},
+				None => Poll::Ready(Ok(()))
+			}
+		}).await
+	}
+}
+
+fn closed() -> io::Error {
+	io::Error::new(io::ErrorKind::BrokenPipe, "body was dropped")
+}
+
+pub(super) struct WriterStream {
+	shared: Arc<Mutex<Shared>>,
+	future: Option<PinnedWriterFuture>,
+	last_flush: bool
+}
+
+impl WriterStream {
+	pub fn new<F, Fut>(f: F) -> Self
+	where
+		F: FnOnce(ChunkWriter) -> Fut,
+		Fut: Future<Output=io::Result<()>> + Send + 'static
+	{
+		let shared = Arc::new(Mutex::new(Shared::default()));
+		let writer = ChunkWriter { shared: shared.clone() };
+
+		Self {
+			shared,
+			future: Some(Box::pin(SyncWrapper::new(f(writer)))),
+			last_flush: false
+		}
+	}
+
+	/// Returns true if the last returned chunk should be flushed immediately.
+	pub fn last_flush(&self) -> bool {
+		self.last_flush
+	}
+
+	fn take_pending(&mut self, cx: &Context) -> Option<Bytes> {
+		let mut shared = self.shared.lock().unwrap();
+		let Some((bytes, flush)) = shared.pending.take() else {
+			// checked under the same lock the writer uses to set pending
+			shared.stream_waker = Some(cx.waker().clone());
+			return None
+		};
+
+		if let Some(waker) = shared.writer_waker.take() {
+			waker.wake();
+		}
+
+		self.last_flush = flush;
+		Some(bytes)
+	}
+}
+
+impl Drop for WriterStream {
+	fn drop(&mut self) {
+		let mut shared = self.shared.lock().unwrap();
+		shared.closed = true;
+		if let Some(waker) = shared.writer_waker.take() {
+			waker.wake();
+		}
+	}
+}
+
+impl Stream for WriterStream {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let me = self.get_mut();
+
+		if let Some(bytes) = me.take_pending(cx) {
+			return Poll::Ready(Some(Ok(bytes)))
+		}
+
+		if let Some(fut) = &mut me.future {
+			match fut.as_mut().poll(cx) {
+				Poll::Ready(r) => {
+					me.future = None;
+					if let Err(e) = r {
+						return Poll::Ready(Some(Err(e)))
+					}
+				},
+				Poll::Pending => {
+					return match me.take_pending(cx) {
+						Some(bytes) => Poll::Ready(Some(Ok(bytes))),
+						None => Poll::Pending
+					}
+				}
+			}
+		}
+
+		// the writer is finished, return what is left in the buffer
+		let rest = me.shared.lock().unwrap().buf.split().freeze();
+		if rest.is_empty() {
+			return Poll::Ready(None)
+		}
+
+		me.last_flush = true;
+		Poll::Ready(Some(Ok(rest)))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use crate::Body;
+
+	use std::io;
+	use std::time::Duration;
+
+	use tokio::sync::oneshot;
+	use tokio::time::timeout;
+
+	#[tokio::test]
+	async fn test_spawned_writer() {
+
+		let body = Body::from_writer(|mut writer| async move {
+			tokio::spawn(async move {
+				writer.write("hello").await?;
+				writer.flush().await?;
+				tokio::time::sleep(Duration::from_millis(10)).await;
+				writer.write(" world").await?;
+				writer.write(vec![b'!'; 5000]).await?;
+				writer.flush().await
+			}).await.unwrap()
+		});
+
+		let body = tokio::time::timeout(
+			Duration::from_secs(5),
+			body.into_bytes()
+		).await.expect("writer hangs").unwrap();
+		assert_eq!(body.len(), 11 + 5000);
+		assert!(body.starts_with(b"hello world!"));
+
+	}
+
+	#[tokio::test]
+	async fn test_writer_error() {
+
+		let body = Body::from_writer(|mut writer| async move {
+			writer.write("a").await?;
+			writer.flush().await?;
+			Err(std::io::Error::other("failed"))
+		});
+
+		assert!(body.into_bytes().await.is_err());
+
+	}
+
+	#[tokio::test]
+	async fn test_dropped_body() {
+
+		let (tx, rx) = oneshot::channel();
+		let body = Body::from_writer(|mut writer| async move {
+			// the body gets dropped while we wait for this task
+			tokio::spawn(async move {
+				let r = async {
+					writer.write("hello").await?;
+					writer.flush().await?;
+					writer.write("world").await?;
+					writer.flush().await
+				}.await;
+				tx.send(r).unwrap();
+			}).await.unwrap();
+
+			Ok(())
+		});
+
+		let mut stream = Box::pin(body.into_async_bytes_streamer());
+		let first = tokio_stream::StreamExt::next(&mut stream).await;
+		assert_eq!(first.unwrap().unwrap(), "hello");
+		drop(stream);
+
+		let r = timeout(Duration::from_secs(5), rx).await
+			.expect("writer hangs")
+			.unwrap();
+		assert_eq!(r.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+
+	}
+
+	#[tokio::test]
+	async fn test_cancelled_flush() {
+
+		let body = Body::from_writer(|mut writer| async move {
+			tokio::spawn(async move {
+				writer.write("a").await?;
+				// the flush is cancelled before the chunk was taken
+				tokio::select! {
+					biased;
+					_ = writer.flush() => panic!("chunk taken"),
+					_ = std::future::ready(()) => {}
+				}
+
+				writer.write("b").await?;
+				writer.flush().await
+			}).await.unwrap()
+		});
+
+		let body = timeout(Duration::from_secs(5), body.into_bytes()).await
+			.expect("writer hangs")
+			.unwrap();
+		assert_eq!(body, "ab");
+
+	}
+
+}
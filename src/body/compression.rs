@@ -0,0 +1,160 @@
+use super::Body;
+use crate::header::ContentEncoding;
+
+use std::io;
+
+#[cfg(any(
+	feature = "gzip", feature = "deflate", feature = "brotli",
+	feature = "zstd"
+))]
+use tokio::io::BufReader;
+
+#[cfg(any(
+	feature = "gzip", feature = "deflate", feature = "brotli",
+	feature = "zstd"
+))]
+use async_compression::tokio::bufread;
+
+
+// wraps the body in an async-compression encoder or decoder
+#[cfg(any(
+	feature = "gzip", feature = "deflate", feature = "brotli",
+	feature = "zstd"
+))]
+macro_rules! coder {
+	($body:expr, $coder:ident) => (
+		Ok(Self::from_async_reader(bufread::$coder::new(
+			BufReader::new($body.into_async_reader())
+		)))
+	)
+}
+
+impl Body {
+	/// Returns a new Body which compresses this body with the given encoding
+	/// while being read.
+	/// 
+	/// The constraints of this body apply to the uncompressed data, the
+	/// returned body has no constraints set.
+	/// 
+	/// ## Supported encodings
+	/// - `identity`
+	/// - `gzip` with the feature `gzip`
+	/// - `deflate` (the zlib format) with the feature `deflate`
+	/// - `br` with the feature `brotli`
+	/// - `zstd` with the feature `zstd`
+	/// 
+	/// ## Errors
+	/// If the encoding is not supported.
+	#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+	pub fn compress(self, encoding: &ContentEncoding) -> io::Result<Self> {
+		match encoding {
+			ContentEncoding::Identity => Ok(self),
+			#[cfg(feature = "gzip")]
+			ContentEncoding::Gzip => coder!(self, GzipEncoder),
+			#[cfg(feature = "deflate")]
+			ContentEncoding::Deflate => coder!(self, ZlibEncoder),
+			#[cfg(feature = "brotli")]
+			ContentEncoding::Br => coder!(self, BrotliEncoder),
+			#[cfg(feature = "zstd")]
+			ContentEncoding::Zstd => coder!(self, ZstdEncoder),
+			e => Err(unsupported(e))
+		}
+	}
+
+	/// Returns a new Body which decompresses this body with the given
+	/// encoding while being read.
+	/// 
+	/// The constraints of this body apply to the compressed data, to limit
+	/// the decompressed size set a size limit on the returned body.
+	/// 
+	/// ## Supported encodings
+	/// See `compress`.
+	/// 
+	/// ## Errors
+	/// If the encoding is not supported.
+	#[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
+	pub fn decompress(self, encoding: &ContentEncoding) -> io::Result<Self> {
+		match encoding {
+			ContentEncoding::Identity => Ok(self),
+			#[cfg(feature = "gzip")]
+			ContentEncoding::Gzip => coder!(self, GzipDecoder),
+			#[cfg(feature = "deflate")]
+			ContentEncoding::Deflate => coder!(self, ZlibDecoder),
+			#[cfg(feature = "brotli")]
+			ContentEncoding::Br => coder!(self, BrotliDecoder),
+			#[cfg(feature = "zstd")]
+			ContentEncoding::Zstd => coder!(self, ZstdDecoder),
+			e => Err(unsupported(e))
+		}
+	}
+}
+
+fn unsupported(encoding: &ContentEncoding) -> io::Error {
+	io::Error::new(
+		io::ErrorKind::Unsupported,
+		format!("content encoding {encoding} not supported")
+	)
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[allow(dead_code)]
+	async fn roundtrip(encoding: ContentEncoding) {
+		let data = "hello world ".repeat(100);
+
+		let compressed = Body::from(data.clone()).compress(&encoding).unwrap()
+			.into_bytes().await.unwrap();
+		assert!(compressed.len() < data.len(), "{encoding}");
+
+		let decompressed = Body::from(compressed).decompress(&encoding)
+			.unwrap()
+			.into_string().await.unwrap();
+		assert_eq!(decompressed, data, "{encoding}");
+
+		// invalid data
+		let r = Body::from("not compressed").decompress(&encoding).unwrap()
+			.into_bytes().await;
+		assert!(r.is_err(), "{encoding}");
+	}
+
+	#[cfg(feature = "gzip")]
+	#[tokio::test]
+	async fn test_gzip() {
+		roundtrip(ContentEncoding::Gzip).await;
+	}
+
+	#[cfg(feature = "deflate")]
+	#[tokio::test]
+	async fn test_deflate() {
+		roundtrip(ContentEncoding::Deflate).await;
+	}
+
+	#[cfg(feature = "brotli")]
+	#[tokio::test]
+	async fn test_brotli() {
+		roundtrip(ContentEncoding::Br).await;
+	}
+
+	#[cfg(feature = "zstd")]
+	#[tokio::test]
+	async fn test_zstd() {
+		roundtrip(ContentEncoding::Zstd).await;
+	}
+
+	#[tokio::test]
+	async fn test_unsupported() {
+
+		let body = Body::from("a").compress(&ContentEncoding::Identity)
+			.unwrap();
+		assert_eq!(body.into_string().await.unwrap(), "a");
+
+		let other = ContentEncoding::Other("lz4".into());
+		let e = Body::from("a").compress(&other).unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::Unsupported);
+
+	}
+
+}
@@ -0,0 +1,171 @@
+use std::{io, fmt};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use futures_core::Stream;
+
+use pin_project_lite::pin_project;
+
+use bytes::Bytes;
+
+
+#[derive(Debug, Default)]
+struct State {
+	paused: bool,
+	aborted: bool,
+	waker: Option<Waker>
+}
+
+/// Allows to pause, resume or abort a body from another task, see
+/// `Body::with_control`.
+#[derive(Debug, Clone)]
+pub struct BodyControl {
+	state: Arc<Mutex<State>>
+}
+
+impl BodyControl {
+	/// Pauses the body, reading from it returns `Pending` until `resume` is
+	/// called.
+	pub fn pause(&self) {
+		self.state.lock().unwrap().paused = true;
+	}
+
+	/// Resumes a paused body.
+	pub fn resume(&self) {
+		let mut state = self.state.lock().unwrap();
+		state.paused = false;
+		if let Some(waker) = state.waker.take() {
+			waker.wake();
+		}
+	}
+
+	/// Aborts the body, reading from it returns a `BodyAborted` error.
+	pub fn abort(&self) {
+		let mut state = self.state.lock().unwrap();
+		state.aborted = true;
+		if let Some(waker) = state.waker.take() {
+			waker.wake();
+		}
+	}
+
+	/// Returns true if the body is paused.
+	pub fn is_paused(&self) -> bool {
+		self.state.lock().unwrap().paused
+	}
+
+	/// Returns true if the body was aborted.
+	pub fn is_aborted(&self) -> bool {
+		self.state.lock().unwrap().aborted
+	}
+}
+
+/// The inner error of the `io::Error` returned by a body which was aborted
+/// with `BodyControl::abort`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyAborted;
+
+impl fmt::Display for BodyAborted {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("body aborted")
+	}
+}
+
+impl std::error::Error for BodyAborted {}
+
+pin_project! {
+	pub(super) struct Controlled<S> {
+		#[pin]
+		inner: S,
+		state: Arc<Mutex<State>>
+	}
+}
+
+impl<S> Controlled<S> {
+	pub fn new(inner: S) -> (Self, BodyControl) {
+		let state = Arc::new(Mutex::new(State::default()));
+		let control = BodyControl { state: state.clone() };
+
+		(Self { inner, state }, control)
+	}
+}
+
+impl<S> Stream for Controlled<S>
+where S: Stream<Item=io::Result<Bytes>> {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let me = self.project();
+
+		{
+			let mut state = me.state.lock().unwrap();
+			if state.aborted {
+				return Poll::Ready(Some(Err(io::Error::new(
+					io::ErrorKind::ConnectionAborted,
+					BodyAborted
+				))))
+			}
+
+			// store the waker so abort can wake us while the inner stream
+			// is pending
+			state.waker = Some(cx.waker().clone());
+			if state.paused {
+				return Poll::Pending
+			}
+		}
+
+		me.inner.poll_next(cx)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::Body;
+
+	use std::future::poll_fn;
+
+	use tokio_stream::StreamExt;
+
+
+	#[tokio::test]
+	async fn test_pause_resume_abort() {
+
+		let chunks = ["a", "b", "c"].map(|c| Ok(Bytes::from(c)));
+		let body = Body::from_async_bytes_streamer(tokio_stream::iter(chunks));
+		let (body, control) = body.with_control();
+		let mut stream = Box::pin(body.into_async_bytes_streamer());
+
+		assert_eq!(stream.next().await.unwrap().unwrap(), "a");
+
+		control.pause();
+		assert!(control.is_paused());
+		let poll = poll_fn(|cx| Poll::Ready(stream.as_mut().poll_next(cx)))
+			.await;
+		assert!(poll.is_pending());
+
+		// resume from another task while waiting for the next chunk
+		let resume = tokio::spawn({
+			let control = control.clone();
+			async move { control.resume() }
+		});
+		assert_eq!(stream.next().await.unwrap().unwrap(), "b");
+		resume.await.unwrap();
+		assert!(!control.is_paused());
+
+		control.abort();
+		assert!(control.is_aborted());
+		let e = stream.next().await.unwrap().unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::ConnectionAborted);
+		assert_eq!(
+			e.get_ref().unwrap().downcast_ref::<BodyAborted>(),
+			Some(&BodyAborted)
+		);
+
+	}
+}
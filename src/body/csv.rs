@@ -0,0 +1,55 @@
+use super::Body;
+
+use std::io;
+
+use futures_core::Stream;
+use tokio_stream::StreamExt;
+
+use csv_async::{AsyncDeserializer, AsyncWriterBuilder};
+
+
+fn csv_error(e: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+impl Body {
+	/// Creates a new Body which contains the csv serialized records, the
+	/// header row is created from the field names of the first record.
+	/// 
+	/// The records are serialized while the body is read.
+	#[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+	pub fn serialize_csv<I>(records: I) -> Self
+	where
+		I: IntoIterator + Send + 'static,
+		I::IntoIter: Send,
+		I::Item: serde::Serialize + Send
+	{
+		Self::from_writer(move |mut writer| async move {
+			let mut first = true;
+			for record in records {
+				let mut ser = AsyncWriterBuilder::new()
+					.has_headers(first)
+					.create_serializer(vec![]);
+				ser.serialize(record).await.map_err(csv_error)?;
+				let buf = ser.into_inner().await
+					.map_err(|e| csv_error(e.into_error()))?;
+
+				writer.write(buf).await?;
+				first = false;
+			}
+
+			Ok(())
+		})
+	}
+
+	/// Converts the Body into a stream of csv records, the first row is
+	/// expected to be a header row.
+	#[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+	pub fn deserialize_csv<D>(self) -> impl Stream<Item=io::Result<D>>
+	where D: serde::de::DeserializeOwned + 'static {
+		let reader = Box::pin(self.into_async_reader());
+		AsyncDeserializer::from_reader(reader)
+			.into_deserialize::<D>()
+			.map(|r| r.map_err(csv_error))
+	}
+}
@@ -0,0 +1,121 @@
+use super::BodyAsyncBytesStreamer;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use pin_project_lite::pin_project;
+
+use bytes::{Bytes, BytesMut};
+
+
+pin_project! {
+	/// A stream which returns chunks of an exact size, only the last chunk
+	/// might be smaller, see `Body::read_exact_chunks`.
+	pub struct ExactChunks {
+		#[pin]
+		inner: BodyAsyncBytesStreamer,
+		buf: BytesMut,
+		size: usize,
+		done: bool
+	}
+}
+
+impl ExactChunks {
+	pub(super) fn new(inner: BodyAsyncBytesStreamer, size: usize) -> Self {
+		assert!(size > 0, "chunk size needs to be bigger than zero");
+
+		Self {
+			inner,
+			buf: BytesMut::new(),
+			size,
+			done: false
+		}
+	}
+}
+
+impl Stream for ExactChunks {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let mut me = self.project();
+
+		loop {
+			if me.buf.len() >= *me.size {
+				return Poll::Ready(Some(Ok(me.buf.split_to(*me.size).freeze())))
+			}
+
+			if *me.done {
+				if me.buf.is_empty() {
+					return Poll::Ready(None)
+				}
+
+				return Poll::Ready(Some(Ok(me.buf.split().freeze())))
+			}
+
+			match me.inner.as_mut().poll_next(cx) {
+				Poll::Ready(Some(Ok(mut bytes))) => {
+					// return the chunk without copying if possible
+					if me.buf.is_empty() && bytes.len() >= *me.size {
+						let chunk = bytes.split_to(*me.size);
+						me.buf.extend_from_slice(&bytes);
+						return Poll::Ready(Some(Ok(chunk)))
+					}
+
+					me.buf.extend_from_slice(&bytes);
+				},
+				Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+				Poll::Ready(None) => *me.done = true,
+				Poll::Pending => return Poll::Pending
+			}
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use crate::Body;
+
+	use bytes::Bytes;
+	use tokio_stream::StreamExt;
+
+
+	#[tokio::test]
+	async fn test_chunk_boundaries() {
+
+		let chunks: Vec<_> = ["ab", "cdefg", "h", "ijklmnop"].into_iter()
+			.map(|c| Ok(Bytes::from_static(c.as_bytes())))
+			.collect();
+		let body = Body::from_async_bytes_streamer(tokio_stream::iter(chunks));
+
+		let chunks: Vec<_> = body.read_exact_chunks(3)
+			.map(|c| c.unwrap())
+			.collect().await;
+		assert_eq!(chunks, ["abc", "def", "ghi", "jkl", "mno", "p"]);
+
+		// a chunk which is big enough is split without copying
+		let body = Body::from("abcdefg");
+		let chunks: Vec<_> = body.read_exact_chunks(3)
+			.map(|c| c.unwrap())
+			.collect().await;
+		assert_eq!(chunks, ["abc", "def", "g"]);
+
+		let chunks: Vec<_> = Body::new().read_exact_chunks(3).collect().await;
+		assert!(chunks.is_empty());
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_zero_size() {
+
+		let _ = Body::new().read_exact_chunks(0);
+
+	}
+}
\ No newline at end of file
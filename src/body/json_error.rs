@@ -0,0 +1,189 @@
+//! Json deserialization errors with the location where they happened.
+
+use std::{io, fmt};
+use std::io::Read;
+use std::collections::VecDeque;
+
+
+// the maximum amount of bytes shown on each side of the error
+const SNIPPET_LEN: usize = 32;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The inner error of an `io::Error` returned by `Body::deserialize` if the
+/// json is not valid or does not match the type.
+///
+/// ## Note
+/// The location is best-effort. If the body was streamed the snippet only
+/// contains the bytes before the error.
+#[derive(Debug)]
+pub struct JsonError {
+	offset: usize,
+	line: usize,
+	column: usize,
+	snippet: String,
+	inner: BoxError
+}
+
+impl JsonError {
+	/// Creates an error from a buffered body, the snippet contains bytes
+	/// before and after the offset.
+	#[cfg_attr(not(feature = "simd-json"), allow(dead_code))]
+	pub(super) fn from_slice(
+		bytes: &[u8],
+		offset: usize,
+		inner: impl Into<BoxError>
+	) -> Self {
+		let offset = offset.min(bytes.len());
+		let before = &bytes[..offset];
+		let line = before.iter().filter(|b| **b == b'\n').count() + 1;
+		let line_start = before.iter().rposition(|b| *b == b'\n')
+			.map(|i| i + 1)
+			.unwrap_or(0);
+
+		let start = offset.saturating_sub(SNIPPET_LEN);
+		let end = (offset + SNIPPET_LEN).min(bytes.len());
+
+		Self {
+			offset,
+			line,
+			column: offset - line_start + 1,
+			snippet: String::from_utf8_lossy(&bytes[start..end]).into_owned(),
+			inner: inner.into()
+		}
+	}
+
+	/// Returns the byte offset in the body.
+	pub fn offset(&self) -> usize {
+		self.offset
+	}
+
+	/// Returns the line, starting at 1.
+	pub fn line(&self) -> usize {
+		self.line
+	}
+
+	/// Returns the column in bytes, starting at 1.
+	pub fn column(&self) -> usize {
+		self.column
+	}
+
+	/// Returns the bytes around the error, invalid utf8 is replaced.
+	pub fn snippet(&self) -> &str {
+		&self.snippet
+	}
+}
+
+impl fmt::Display for JsonError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} at byte {} (line {} column {}) near {:?}",
+			self.inner, self.offset, self.line, self.column, self.snippet
+		)
+	}
+}
+
+impl std::error::Error for JsonError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&*self.inner)
+	}
+}
+
+/// Counts the read bytes and keeps the last ones to build a `JsonError`.
+#[cfg_attr(feature = "simd-json", allow(dead_code))]
+pub(super) struct Recorder<R> {
+	inner: R,
+	offset: usize,
+	last: VecDeque<u8>
+}
+
+#[cfg_attr(feature = "simd-json", allow(dead_code))]
+impl<R> Recorder<R> {
+	pub fn new(inner: R) -> Self {
+		Self {
+			inner,
+			offset: 0,
+			last: VecDeque::with_capacity(SNIPPET_LEN)
+		}
+	}
+
+	pub fn inner(&self) -> &R {
+		&self.inner
+	}
+
+	/// Io errors, for example a reached size limit, are not a `JsonError`.
+	pub fn into_error(self, e: serde_json::Error) -> io::Error {
+		if e.is_io() {
+			return io::Error::new(io::ErrorKind::InvalidData, e)
+		}
+
+		let (a, b) = self.last.as_slices();
+		let error = JsonError {
+			offset: self.offset,
+			line: e.line(),
+			column: e.column(),
+			snippet: String::from_utf8_lossy(&[a, b].concat()).into_owned(),
+			inner: e.into()
+		};
+
+		io::Error::new(io::ErrorKind::InvalidData, error)
+	}
+}
+
+impl<R: Read> Read for Recorder<R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let read = self.inner.read(buf)?;
+		self.offset += read;
+
+		let new = &buf[..read];
+		let new = &new[new.len().saturating_sub(SNIPPET_LEN)..];
+		let overflow = (self.last.len() + new.len())
+			.saturating_sub(SNIPPET_LEN);
+		self.last.drain(..overflow);
+		self.last.extend(new);
+
+		Ok(read)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_location() {
+
+		let e = JsonError::from_slice(b"{\n  \"a\": x\n}", 9, "invalid");
+		assert_eq!((e.offset(), e.line(), e.column()), (9, 2, 8));
+		assert_eq!(e.snippet(), "{\n  \"a\": x\n}");
+
+		let bytes = b"{\"a\": 1,\n\"b\": x}";
+		let mut reader = Recorder::new(&bytes[..]);
+		let e = serde_json::from_reader::<_, serde_json::Value>(&mut reader)
+			.unwrap_err();
+		let e = reader.into_error(e).into_inner().unwrap()
+			.downcast::<JsonError>().unwrap();
+		assert_eq!((e.line(), e.column()), (2, 6));
+		assert_eq!(e.snippet(), "{\"a\": 1,\n\"b\": x");
+
+	}
+
+	#[tokio::test]
+	async fn test_deserialize() {
+
+		use crate::Body;
+
+		// large enough to be parsed in spawn_blocking with simd-json
+		let list: Vec<u32> = (0..20_000).collect();
+		let body = Body::from(serde_json::to_vec(&list).unwrap());
+		assert_eq!(body.deserialize::<Vec<u32>>().await.unwrap(), list);
+
+		let e = Body::from("{\"a\": x}").deserialize::<serde_json::Value>()
+			.await.unwrap_err();
+		assert!(e.get_ref().unwrap().is::<JsonError>());
+
+	}
+
+}
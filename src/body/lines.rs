@@ -0,0 +1,161 @@
+use super::BodyAsyncBytesStreamer;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use pin_project_lite::pin_project;
+
+use bytes::{Bytes, BytesMut};
+
+
+pin_project! {
+	/// A stream which returns the lines of a body, see `Body::lines`.
+	pub struct Lines {
+		#[pin]
+		inner: BodyAsyncBytesStreamer,
+		buf: BytesMut,
+		max_line_len: usize,
+		done: bool
+	}
+}
+
+impl Lines {
+	pub(super) fn new(
+		inner: BodyAsyncBytesStreamer,
+		max_line_len: usize
+	) -> Self {
+		Self {
+			inner,
+			buf: BytesMut::new(),
+			max_line_len,
+			done: false
+		}
+	}
+}
+
+fn line_too_long() -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, "line too long")
+}
+
+impl Stream for Lines {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let mut me = self.project();
+
+		loop {
+			if let Some(pos) = me.buf.iter().position(|b| *b == b'\n') {
+				let mut line = me.buf.split_to(pos + 1);
+				line.truncate(pos);
+				if line.last() == Some(&b'\r') {
+					line.truncate(pos - 1);
+				}
+
+				if line.len() > *me.max_line_len {
+					break
+				}
+
+				return Poll::Ready(Some(Ok(line.freeze())))
+			}
+
+			// the line could still end with \r\n
+			if me.buf.len() > *me.max_line_len + 1 {
+				break
+			}
+
+			if *me.done {
+				if me.buf.is_empty() {
+					return Poll::Ready(None)
+				}
+
+				if me.buf.len() > *me.max_line_len {
+					break
+				}
+
+				return Poll::Ready(Some(Ok(me.buf.split().freeze())))
+			}
+
+			match me.inner.as_mut().poll_next(cx) {
+				Poll::Ready(Some(Ok(bytes))) => {
+					me.buf.extend_from_slice(&bytes)
+				},
+				Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+				Poll::Ready(None) => *me.done = true,
+				Poll::Pending => return Poll::Pending
+			}
+		}
+
+		// the stream ends after a line which is too long
+		me.buf.clear();
+		*me.done = true;
+		Poll::Ready(Some(Err(line_too_long())))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use crate::Body;
+
+	use bytes::Bytes;
+	use tokio_stream::StreamExt;
+
+
+	fn chunked(chunks: &[&'static str]) -> Body {
+		let chunks: Vec<_> = chunks.iter()
+			.map(|c| Ok(Bytes::from_static(c.as_bytes())))
+			.collect();
+		Body::from_async_bytes_streamer(tokio_stream::iter(chunks))
+	}
+
+	async fn lines(body: Body, max: usize) -> Vec<Result<Bytes, ()>> {
+		body.lines(max)
+			.map(|l| l.map_err(|e| assert_eq!(e.to_string(), "line too long")))
+			.collect().await
+	}
+
+	#[tokio::test]
+	async fn test_lines() {
+
+		let body = chunked(&["one\r\ntw", "o\n\r\nthr", "ee"]);
+		assert_eq!(lines(body, 10).await, [
+			Ok("one".into()),
+			Ok("two".into()),
+			Ok("".into()),
+			// the last line does not need a newline
+			Ok("three".into())
+		]);
+
+		// exactly max_line_len
+		let body = chunked(&["abcd\r", "\nefgh\n", "ijkl"]);
+		assert_eq!(lines(body, 4).await, [
+			Ok("abcd".into()),
+			Ok("efgh".into()),
+			Ok("ijkl".into())
+		]);
+
+		assert!(lines(Body::new(), 4).await.is_empty());
+
+	}
+
+	#[tokio::test]
+	async fn test_too_long() {
+
+		// split across chunks without a newline
+		let body = chunked(&["abc", "def", "ghi\n", "jk\n"]);
+		assert_eq!(lines(body, 4).await, [Err(())]);
+
+		let body = chunked(&["ab\n", "cdefg\n"]);
+		assert_eq!(lines(body, 4).await, [Ok("ab".into()), Err(())]);
+
+		let body = chunked(&["ab\n", "cdefg"]);
+		assert_eq!(lines(body, 4).await, [Ok("ab".into()), Err(())]);
+
+	}
+}
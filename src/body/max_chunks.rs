@@ -0,0 +1,90 @@
+use super::BodyAsyncBytesStreamer;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use pin_project_lite::pin_project;
+
+use bytes::Bytes;
+
+
+pin_project! {
+	/// Splits chunks which are bigger than `max` without copying them.
+	pub(super) struct MaxChunks {
+		#[pin]
+		inner: BodyAsyncBytesStreamer,
+		rest: Bytes,
+		max: usize
+	}
+}
+
+impl MaxChunks {
+	pub fn new(inner: BodyAsyncBytesStreamer, max: usize) -> Self {
+		assert!(max > 0, "max chunk size needs to be bigger than zero");
+
+		Self { inner, rest: Bytes::new(), max }
+	}
+}
+
+impl Stream for MaxChunks {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let me = self.project();
+
+		if me.rest.is_empty() {
+			match me.inner.poll_next(cx) {
+				Poll::Ready(Some(Ok(bytes))) => *me.rest = bytes,
+				other => return other
+			}
+		}
+
+		let len = me.rest.len().min(*me.max);
+		Poll::Ready(Some(Ok(me.rest.split_to(len))))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use crate::Body;
+
+	use bytes::Bytes;
+	use tokio_stream::StreamExt;
+
+
+	#[tokio::test]
+	async fn test_split() {
+
+		let body = Body::from("0123456789").chunked_max(3);
+		assert!(body.len().is_none());
+
+		let chunks: Vec<_> = body.into_async_bytes_streamer()
+			.map(|c| c.unwrap())
+			.collect().await;
+		assert_eq!(chunks, ["012", "345", "678", "9"]);
+
+		// smaller chunks are not merged
+		let body = Body::from_iter(["01", "2345"].map(Bytes::from))
+			.chunked_max(3);
+		let chunks: Vec<_> = body.into_async_bytes_streamer()
+			.map(|c| c.unwrap())
+			.collect().await;
+		assert_eq!(chunks, ["01", "234", "5"]);
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_zero_max() {
+
+		let _ = Body::from("0123456789").chunked_max(0);
+
+	}
+}
@@ -10,24 +10,107 @@ mod async_bytes_streamer;
 pub use async_bytes_streamer::BodyAsyncBytesStreamer;
 use async_bytes_streamer::async_bytes_streamer_into_bytes;
 
+#[cfg(feature = "hyper_body")]
 mod body_http;
+#[cfg(feature = "hyper_body")]
 pub use body_http::BodyHttp;
+#[cfg(feature = "hyper_body")]
 use body_http::IncomingAsAsyncBytesStream;
 
+mod more_bytes;
+pub use more_bytes::MoreBytes;
+
+mod small_bytes;
+use small_bytes::SmallBytes;
+
+mod chunk_writer;
+pub use chunk_writer::ChunkWriter;
+use chunk_writer::WriterStream;
+
+#[cfg(feature = "stream-adapters")]
+mod exact_chunks;
+#[cfg(feature = "stream-adapters")]
+pub use exact_chunks::ExactChunks;
+
+#[cfg(feature = "stream-adapters")]
+mod lines;
+#[cfg(feature = "stream-adapters")]
+pub use lines::Lines;
+
+#[cfg(feature = "stream-adapters")]
+mod max_chunks;
+#[cfg(feature = "stream-adapters")]
+use max_chunks::MaxChunks;
+
+#[cfg(feature = "stream-adapters")]
+mod throttle;
+#[cfg(feature = "stream-adapters")]
+use throttle::Throttle;
+
+mod on_complete;
+use on_complete::OnComplete;
+
+mod strip_bom;
+use strip_bom::{StripBom, strip_bom, BOM};
+
+mod aborted;
+pub use aborted::AbortedHandle;
+
+mod control;
+pub use control::{BodyControl, BodyAborted};
+use control::Controlled;
+
+#[cfg(feature = "compression")]
+mod compression;
+
+#[cfg(feature = "json")]
+mod serialize_stream;
+
+#[cfg(feature = "json")]
+mod json_error;
+#[cfg(feature = "json")]
+pub use json_error::JsonError;
+
+mod aws_chunked;
+pub use aws_chunked::CRC32_TRAILER;
+
+#[cfg(feature = "csv")]
+mod csv;
+
+#[cfg(feature = "xml")]
+mod xml;
+
+mod transform;
+pub use transform::BodyTransform;
+
+mod sync_wrapper;
+use sync_wrapper::SyncWrapper;
+
+use crate::error::size_limit_reached;
+use crate::fingerprint::Fnv64;
+use crate::header::Charset;
+
 use std::{io, fmt, mem};
 use std::pin::Pin;
+use std::future::Future;
 use std::io::Read as SyncRead;
 use std::time::Duration;
 
 use tokio::task;
-use tokio::io::AsyncRead;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 
 use futures_core::Stream as AsyncStream;
+use tokio_stream::StreamExt;
 
+#[cfg(feature = "hyper_body")]
 use hyper::body::Incoming;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+
 
+// larger bodies are parsed in spawn_blocking to not block the runtime
+#[cfg(feature = "simd-json")]
+const SIMD_JSON_BLOCKING_LEN: usize = 64 * 1024;
 
 type PinnedAsyncRead = Pin<Box<dyn AsyncRead + Send + Sync>>;
 type BoxedSyncRead = Box<dyn SyncRead + Send + Sync>;
@@ -39,10 +122,16 @@ enum Inner {
 	Empty,
 	// Bytes will never be empty
 	Bytes(Bytes),
+	// will never be empty
+	Small(SmallBytes),
+	// contains at least two segments
+	MoreBytes(MoreBytes),
+	#[cfg(feature = "hyper_body")]
 	Incoming(Incoming),
 	SyncReader(BoxedSyncRead),
 	AsyncReader(PinnedAsyncRead),
-	AsyncBytesStreamer(PinnedAsyncBytesStream)
+	AsyncBytesStreamer(PinnedAsyncBytesStream),
+	Writer(WriterStream)
 }
 
 impl fmt::Debug for Inner {
@@ -50,10 +139,16 @@ impl fmt::Debug for Inner {
 		match self {
 			Self::Empty => f.write_str("Empty"),
 			Self::Bytes(b) => f.debug_tuple("Bytes").field(&b.len()).finish(),
+			Self::Small(s) => f.debug_tuple("Small").field(&s.len()).finish(),
+			Self::MoreBytes(m) => {
+				f.debug_tuple("MoreBytes").field(&m.len()).finish()
+			},
+			#[cfg(feature = "hyper_body")]
 			Self::Incoming(_) => f.write_str("Incoming"),
 			Self::SyncReader(_) => f.write_str("SyncReader"),
 			Self::AsyncReader(_) => f.write_str("AsyncReader"),
-			Self::AsyncBytesStreamer(_) => f.write_str("AsyncBytesStreamer")
+			Self::AsyncBytesStreamer(_) => f.write_str("AsyncBytesStreamer"),
+			Self::Writer(_) => f.write_str("Writer")
 		}
 	}
 }
@@ -77,6 +172,10 @@ pub struct Body {
 }
 
 impl Body {
+	/// Bodies created with `copy_from_slice` which are not longer than this
+	/// are stored inline without allocating.
+	pub const INLINE_LEN: usize = small_bytes::INLINE_LEN;
+
 	fn new_inner(inner: Inner) -> Self {
 		Self {
 			inner,
@@ -90,6 +189,9 @@ impl Body {
 	}
 
 	/// Creates a new `Body` from the given bytes.
+	/// 
+	/// Static data like `"ok"` or `b"{}"` does not allocate, use
+	/// `Bytes::from_static` or `Body::from` for it.
 	pub fn from_bytes(bytes: impl Into<Bytes>) -> Self {
 		let bytes = bytes.into();
 		if !bytes.is_empty() {
@@ -100,54 +202,125 @@ impl Body {
 	}
 
 	/// Creates a new `Body` from the given bytes.
+	/// 
+	/// Up to `Body::INLINE_LEN` bytes are stored inline without allocating,
+	/// which helps tiny responses like `"ok"` which are read with a reader.
+	/// Converting them into `Bytes` still allocates.
 	pub fn copy_from_slice(slice: impl AsRef<[u8]>) -> Self {
 		let slice = slice.as_ref();
-		if !slice.is_empty() {
-			Self::new_inner(Inner::Bytes(Bytes::copy_from_slice(slice)))
-		} else {
+		if slice.is_empty() {
 			Self::new()
+		} else if let Some(small) = SmallBytes::new(slice) {
+			Self::new_inner(Inner::Small(small))
+		} else {
+			Self::new_inner(Inner::Bytes(Bytes::copy_from_slice(slice)))
+		}
+	}
+
+	fn from_more_bytes(mut more: MoreBytes) -> Self {
+		match more.segments_len() {
+			0 => Self::new(),
+			1 => Self::from_bytes(more.pop_front().unwrap()),
+			_ => Self::new_inner(Inner::MoreBytes(more))
 		}
 	}
 
 	/// Creates a new Body from `Incoming`.
+	#[cfg(feature = "hyper_body")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "hyper_body")))]
 	pub fn from_incoming(incoming: Incoming) -> Self {
 		Self::new_inner(Inner::Incoming(incoming))
 	}
 
 	/// Creates a new Body from a `Read` implementation.
+	/// 
+	/// The reader does not need to be `Sync`.
 	pub fn from_sync_reader<R>(reader: R) -> Self
-	where R: SyncRead + Send + Sync + 'static {
-		Self::new_inner(Inner::SyncReader(Box::new(reader)))
+	where R: SyncRead + Send + 'static {
+		Self::new_inner(Inner::SyncReader(Box::new(SyncWrapper::new(reader))))
 	}
 
 	/// Creates a new Body from an `AsyncRead` implementation.
+	/// 
+	/// The reader does not need to be `Sync`.
 	pub fn from_async_reader<R>(reader: R) -> Self
-	where R: AsyncRead + Send + Sync + 'static {
-		Self::new_inner(Inner::AsyncReader(Box::pin(reader)))
+	where R: AsyncRead + Send + 'static {
+		Self::new_inner(Inner::AsyncReader(Box::pin(SyncWrapper::new(reader))))
 	}
 
 	/// Creates a new Body from a `Stream<Item=io::Result<Bytes>>`
 	/// implementation.
+	/// 
+	/// The stream does not need to be `Sync`.
 	pub fn from_async_bytes_streamer<S>(streamer: S) -> Self
-	where S: AsyncStream<Item=io::Result<Bytes>> + Send + Sync + 'static {
-		Self::new_inner(Inner::AsyncBytesStreamer(Box::pin(streamer)))
+	where S: AsyncStream<Item=io::Result<Bytes>> + Send + 'static {
+		Self::new_inner(Inner::AsyncBytesStreamer(
+			Box::pin(SyncWrapper::new(streamer))
+		))
+	}
+
+	/// Creates a new Body from a closure which writes the body in chunks.
+	/// 
+	/// The writer decides when a chunk should be flushed, for example after
+	/// `</head>` to allow the browser to start loading resources early. The
+	/// hint can be read with `BodyAsyncBytesStreamer::last_chunk_flushed`
+	/// or `BodyHttp::last_chunk_flushed`.
+	/// 
+	/// ## Note
+	/// Adapters which create a new body like `through`, `throttle` or
+	/// `with_progress` do not keep the hint.
+	/// 
+	/// The returned future is polled when the body is read and does not need
+	/// to be `Sync`. If the body is dropped before the writer finished, for
+	/// example in a spawned task, writing returns a `BrokenPipe` error.
+	pub fn from_writer<F, Fut>(f: F) -> Self
+	where
+		F: FnOnce(ChunkWriter) -> Fut,
+		Fut: Future<Output=io::Result<()>> + Send + 'static
+	{
+		Self::new_inner(Inner::Writer(WriterStream::new(f)))
 	}
 
 	/// Creates a new Body from a serializeable object.
+	/// 
+	/// With the feature `simd-json` simd-json is used instead of serde_json.
 	#[cfg(feature = "json")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 	pub fn serialize<S: ?Sized>(value: &S) -> io::Result<Self>
 	where S: serde::Serialize {
-		serde_json::to_vec(value)
-			.map(|v| v.into())
-			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+		#[cfg(feature = "simd-json")]
+		let v = simd_json::serde::to_vec(value)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+		#[cfg(not(feature = "simd-json"))]
+		let v = serde_json::to_vec(value)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+		Ok(v.into())
+	}
+
+	/// Creates a new Body from a serializeable object, formatting the json
+	/// with indentation.
+	/// 
+	/// Useful for debug endpoints, prefer `Body::serialize` otherwise.
+	#[cfg(feature = "json")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+	pub fn serialize_pretty<S>(value: &S) -> io::Result<Self>
+	where S: serde::Serialize + ?Sized {
+		#[cfg(feature = "simd-json")]
+		let v = simd_json::serde::to_vec_pretty(value)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+		#[cfg(not(feature = "simd-json"))]
+		let v = serde_json::to_vec_pretty(value)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+		Ok(v.into())
 	}
 
 	/// Returns true if we know the body is empty, the body still might be empty
 	/// but we just don't know it yet
 	pub fn is_empty(&self) -> bool {
-		// we don't need to check the Inner::Bytes(b) since it will never
-		// be empty
+		// we don't need to check Inner::Bytes(b) or Inner::Small(s) since
+		// they will never be empty
 		matches!(self.inner, Inner::Empty)
 	}
 
@@ -156,6 +329,64 @@ impl Body {
 		match &self.inner {
 			Inner::Empty => Some(0),
 			Inner::Bytes(b) => Some(b.len()),
+			Inner::Small(s) => Some(s.len()),
+			Inner::MoreBytes(m) => Some(m.len()),
+			_ => None
+		}
+	}
+
+	/// Returns the segments if the body was created from more than one
+	/// `Bytes`, see `MoreBytes`.
+	/// 
+	/// Allows a server to write the whole body with a single vectored write.
+	pub fn as_more_bytes(&self) -> Option<&MoreBytes> {
+		match &self.inner {
+			Inner::MoreBytes(m) => Some(m),
+			_ => None
+		}
+	}
+
+	/// Returns a cheap copy of the body if it is buffered, the data is shared
+	/// and not copied.
+	/// 
+	/// Returns None for streaming bodies since they can only be read once.
+	pub fn try_clone(&self) -> Option<Self> {
+		let inner = match &self.inner {
+			Inner::Empty => Inner::Empty,
+			Inner::Bytes(b) => Inner::Bytes(b.clone()),
+			Inner::Small(s) => Inner::Small(*s),
+			Inner::MoreBytes(m) => Inner::MoreBytes(m.clone()),
+			_ => return None
+		};
+
+		Some(Self { inner, constraints: self.constraints.clone() })
+	}
+
+	/// Returns a weak etag like `W/"5-a430d84680aabd0b"` if the body is
+	/// buffered.
+	/// 
+	/// The etag is computed from a fast non-cryptographic hash (FNV-1a) and
+	/// the length, it is stable between restarts.
+	pub fn etag(&self) -> Option<String> {
+		let chunks = self.buffered()?;
+
+		let mut hasher = Fnv64::new();
+		for chunk in &chunks {
+			hasher.write(chunk);
+		}
+		let hash = hasher.finish();
+		let len: usize = chunks.iter().map(|c| c.len()).sum();
+
+		Some(format!("W/\"{len:x}-{hash:016x}\""))
+	}
+
+	/// Returns the chunks of data if the body is buffered.
+	pub(crate) fn buffered(&self) -> Option<Vec<&[u8]>> {
+		match &self.inner {
+			Inner::Empty => Some(vec![]),
+			Inner::Bytes(b) => Some(vec![b]),
+			Inner::Small(s) => Some(vec![s]),
+			Inner::MoreBytes(m) => Some(m.chunks().map(|b| &**b).collect()),
 			_ => None
 		}
 	}
@@ -187,6 +418,23 @@ impl Body {
 				}
 				Ok(b)
 			},
+			Inner::Small(s) => {
+				if let Some(size_limit) = self.constraints.size {
+					if s.len() > size_limit {
+						return Err(size_limit_reached("Bytes to big"))
+					}
+				}
+				Ok(s.to_bytes())
+			},
+			Inner::MoreBytes(m) => {
+				if let Some(size_limit) = self.constraints.size {
+					if m.len() > size_limit {
+						return Err(size_limit_reached("Bytes to big"))
+					}
+				}
+				Ok(m.into_bytes())
+			},
+			#[cfg(feature = "hyper_body")]
 			Inner::Incoming(i) => {
 				async_bytes_streamer_into_bytes(
 					IncomingAsAsyncBytesStream::new(i),
@@ -204,8 +452,299 @@ impl Body {
 			},
 			Inner::AsyncBytesStreamer(s) => {
 				async_bytes_streamer_into_bytes(s, self.constraints).await
+			},
+			Inner::Writer(w) => {
+				async_bytes_streamer_into_bytes(w, self.constraints).await
+			}
+		}
+	}
+
+	/// Reads and discards the body returning the number of discarded bytes.
+	/// 
+	/// This allows a connection to be reused if the body was not read.
+	/// 
+	/// ## Errors
+	/// If the body contains more than `max` bytes, the set constraints are
+	/// reached or the body could not be read.
+	pub async fn drain(self, max: u64) -> io::Result<u64> {
+		let stream = self.into_async_bytes_streamer();
+		tokio::pin!(stream);
+
+		let mut discarded = 0u64;
+		while let Some(bytes) = stream.next().await {
+			discarded += bytes?.len() as u64;
+			if discarded > max {
+				return Err(size_limit_reached("drain limit reached"))
+			}
+		}
+
+		Ok(discarded)
+	}
+
+	/// Reads at most `n` bytes, returning them and true if the body contained
+	/// more data which was dropped.
+	/// 
+	/// The chunks are returned as they were read, only the last one gets
+	/// split, so no bytes are copied.
+	/// 
+	/// In contrast to the size limit, reaching `n` is not an error.
+	pub async fn read_at_most(
+		self,
+		n: usize
+	) -> io::Result<(MoreBytes, bool)> {
+		let stream = self.into_async_bytes_streamer();
+		tokio::pin!(stream);
+
+		let mut more = MoreBytes::new();
+		while let Some(bytes) = stream.next().await {
+			let mut bytes = bytes?;
+
+			let missing = n - more.len();
+			if bytes.len() > missing {
+				bytes.truncate(missing);
+				more.push_bytes(bytes);
+				return Ok((more, true))
 			}
+
+			more.push_bytes(bytes);
 		}
+
+		Ok((more, false))
+	}
+
+	/// Returns a new Body which calls `f` with the total number of bytes read
+	/// so far every time a chunk passes through.
+	///
+	/// The constraints of this body are applied to the inner body.
+	///
+	/// ## Note
+	/// The length of the returned body is not known anymore.
+	pub fn with_progress<F>(self, mut f: F) -> Self
+	where F: FnMut(u64) + Send + 'static {
+		let mut total = 0u64;
+		let stream = self.into_async_bytes_streamer().map(move |r| {
+			if let Ok(bytes) = &r {
+				total += bytes.len() as u64;
+				f(total);
+			}
+
+			r
+		});
+
+		Self::from_async_bytes_streamer(stream)
+	}
+
+	/// Returns a new Body which calls `f` once it was completely read with
+	/// the total number of bytes, or with the error if reading failed.
+	///
+	/// If the body is dropped before it was completely read `f` is called
+	/// with an error.
+	///
+	/// ## Note
+	/// The length of the returned body is not known anymore.
+	pub fn on_complete<F>(self, f: F) -> Self
+	where F: FnOnce(io::Result<u64>) + Send + 'static {
+		let stream = OnComplete::new(self.into_async_bytes_streamer(), f);
+		Self::from_async_bytes_streamer(stream)
+	}
+
+	/// Returns a new Body and a handle which detects if the body could not
+	/// be read completely.
+	///
+	/// For a response body this happens if the connection is closed before
+	/// the body was sent, which allows a handler to cancel expensive work.
+	///
+	/// ## Note
+	/// The length of the returned body is not known anymore.
+	///
+	/// The handle only fires if the body is dropped before it was read
+	/// completely or if reading it fails, a closed connection is only
+	/// detected once the server drops the body. A body which was already
+	/// read completely (for example into a write buffer) is never aborted,
+	/// even if the client did not receive all of it.
+	pub fn aborted_handle(self) -> (Self, AbortedHandle) {
+		let handle = AbortedHandle::new();
+		let h = handle.clone();
+		let body = self.on_complete(move |r| {
+			if r.is_err() {
+				h.set_aborted();
+			}
+		});
+
+		(body, handle)
+	}
+
+	/// Returns a new Body which splits chunks bigger than `max` into multiple
+	/// chunks, without copying them.
+	/// 
+	/// This keeps the size of each chunk predictable if they get processed,
+	/// for example hashed or encrypted.
+	/// 
+	/// ## Note
+	/// The length of the returned body is not known anymore.
+	/// 
+	/// ## Panics
+	/// If `max` is zero.
+	#[cfg(feature = "stream-adapters")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "stream-adapters")))]
+	pub fn chunked_max(self, max: usize) -> Self {
+		let stream = MaxChunks::new(self.into_async_bytes_streamer(), max);
+		Self::from_async_bytes_streamer(stream)
+	}
+
+	/// Returns a new Body which returns at most `bytes_per_sec` bytes per
+	/// second on average, chunks are split so that there are no large bursts.
+	/// 
+	/// ## Note
+	/// The length of the returned body is not known anymore.
+	/// 
+	/// ## Panics
+	/// If `bytes_per_sec` is zero.
+	#[cfg(feature = "stream-adapters")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "stream-adapters")))]
+	pub fn throttle(self, bytes_per_sec: u64) -> Self {
+		// allow around ten chunks per second
+		let max = (bytes_per_sec / 10).clamp(1, usize::MAX as u64) as usize;
+		let stream = MaxChunks::new(self.into_async_bytes_streamer(), max);
+		Self::from_async_bytes_streamer(Throttle::new(stream, bytes_per_sec))
+	}
+
+	/// Returns a new Body and a handle which allows to pause, resume or abort
+	/// it from another task.
+	/// 
+	/// ## Note
+	/// The length of the returned body is not known anymore.
+	pub fn with_control(self) -> (Self, BodyControl) {
+		let (stream, control) = Controlled::new(
+			self.into_async_bytes_streamer()
+		);
+		(Self::from_async_bytes_streamer(stream), control)
+	}
+
+	/// Passes the body through all transforms in order, for example to set a
+	/// limit, report progress and compress it.
+	/// 
+	/// A transform can be any `FnOnce(Body) -> Body`.
+	pub fn through(self, transforms: Vec<Box<dyn BodyTransform>>) -> Self {
+		transforms.into_iter().fold(self, |body, t| t.transform(body))
+	}
+
+	/// Writes the entire body to `writer` returning the number of written
+	/// bytes.
+	/// 
+	/// The chunks of the body are written directly without copying them into
+	/// an intermediate buffer.
+	pub async fn copy_to_async_write<W>(self, writer: &mut W) -> io::Result<u64>
+	where W: AsyncWrite + Unpin + ?Sized {
+		let stream = self.into_async_bytes_streamer();
+		tokio::pin!(stream);
+
+		let mut written = 0u64;
+		while let Some(bytes) = stream.next().await {
+			let bytes = bytes?;
+			writer.write_all(&bytes).await?;
+			written += bytes.len() as u64;
+		}
+
+		Ok(written)
+	}
+
+	/// Converts the Body into a stream which returns chunks of exactly `size`
+	/// bytes, only the last chunk might be smaller.
+	/// 
+	/// ## Panics
+	/// If `size` is zero.
+	#[cfg(feature = "stream-adapters")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "stream-adapters")))]
+	pub fn read_exact_chunks(self, size: usize) -> ExactChunks {
+		ExactChunks::new(self.into_async_bytes_streamer(), size)
+	}
+
+	/// Splits the body into the first `n` bytes and the rest.
+	/// 
+	/// Buffered bodies are split without copying the data. Otherwise only
+	/// the first `n` bytes are read and the returned rest streams the
+	/// remaining data lazily. If the body is shorter the rest is empty.
+	/// 
+	/// ## Note
+	/// The length of the rest is only known if the length of this body was
+	/// known.
+	pub async fn split_at(self, n: usize) -> io::Result<(Self, Self)> {
+		if self.len().is_some_and(|len| len <= n) {
+			return Ok((self, Self::new()))
+		}
+
+		if let Some(size_limit) = self.constraints.size {
+			if self.len().is_some_and(|len| len > size_limit) {
+				return Err(size_limit_reached("Bytes to big"))
+			}
+		}
+
+		// buffered bodies are split without a stream
+		let inner = match self.inner {
+			Inner::Bytes(mut rest) => {
+				let prefix = rest.split_to(n);
+				return Ok((Self::from_bytes(prefix), Self::from_bytes(rest)))
+			},
+			Inner::Small(mut rest) => {
+				let prefix = Self::copy_from_slice(&rest[..n]);
+				rest.advance(n);
+				return Ok((prefix, Self::new_inner(Inner::Small(rest))))
+			},
+			Inner::MoreBytes(mut rest) => {
+				let mut prefix = MoreBytes::new();
+				while prefix.len() < n {
+					let missing = n - prefix.len();
+					let front = rest.front_mut().unwrap();
+					if front.len() > missing {
+						prefix.push_bytes(front.split_to(missing));
+						rest.advanced(missing);
+					} else {
+						prefix.push_bytes(rest.pop_front().unwrap());
+					}
+				}
+
+				return Ok((prefix.into(), rest.into()))
+			},
+			inner => inner
+		};
+		let body = Self { inner, constraints: self.constraints };
+
+		let mut stream = Box::pin(body.into_async_bytes_streamer());
+
+		let mut prefix = BytesMut::new();
+		let mut rest = Bytes::new();
+		while prefix.len() < n {
+			let Some(bytes) = stream.next().await else {
+				return Ok((Self::from_bytes(prefix.freeze()), Self::new()))
+			};
+			let mut bytes = bytes?;
+
+			let missing = n - prefix.len();
+			if bytes.len() > missing {
+				rest = bytes.split_off(missing);
+			}
+
+			prefix.extend_from_slice(&bytes);
+		}
+
+		let rest = (!rest.is_empty()).then_some(Ok(rest));
+		let rest = tokio_stream::iter(rest).chain(stream);
+		Ok((
+			Self::from_bytes(prefix.freeze()),
+			Self::from_async_bytes_streamer(rest)
+		))
+	}
+
+	/// Converts the Body into a stream of lines without the trailing `\n` or
+	/// `\r\n`.
+	/// 
+	/// If a line is longer than `max_line_len` an `InvalidData` error is
+	/// returned and the stream ends.
+	#[cfg(feature = "stream-adapters")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "stream-adapters")))]
+	pub fn lines(self, max_line_len: usize) -> Lines {
+		Lines::new(self.into_async_bytes_streamer(), max_line_len)
 	}
 
 	/// Converts the Body into a string.
@@ -215,6 +754,81 @@ impl Body {
 			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 	}
 
+	/// Converts the Body into a string, replacing invalid utf8 with `�`.
+	pub async fn into_string_lossy(self) -> io::Result<String> {
+		let bytes = self.into_bytes().await?;
+		Ok(String::from_utf8_lossy(&bytes).into_owned())
+	}
+
+	/// Converts the Body into a string decoding it with the given charset,
+	/// for example from `RequestHeader::charset`.
+	pub async fn into_string_with_charset(
+		self,
+		charset: Charset
+	) -> io::Result<String> {
+		let bytes = self.into_bytes().await?;
+		charset.decode(&bytes)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+	}
+
+	/// Converts the Body into a string, if it starts with a byte order mark
+	/// it is removed and the charset of the mark is used instead of
+	/// `fallback`.
+	pub async fn into_string_with_bom(
+		self,
+		fallback: Charset
+	) -> io::Result<String> {
+		let bytes = self.into_bytes().await?;
+		let (charset, bytes) = Charset::strip_bom(&bytes);
+		charset.unwrap_or(fallback).decode(bytes)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+	}
+
+	/// Removes a leading utf8 byte order mark while the body is read.
+	/// 
+	/// Use it before `Body::deserialize` since json parsers reject a byte
+	/// order mark.
+	/// 
+	/// ## Note
+	/// Only the utf8 byte order mark is removed, a utf16 body needs to be
+	/// decoded with `Body::into_string_with_bom`.
+	pub fn strip_utf8_bom(mut self) -> Self {
+		// buffered bodies are sliced without copying and keep their length
+		self.inner = match mem::replace(&mut self.inner, Inner::Empty) {
+			Inner::Empty => Inner::Empty,
+			Inner::Bytes(bytes) if bytes.is_empty() => Inner::Empty,
+			Inner::Bytes(bytes) => Self::from(strip_bom(bytes)).inner,
+			Inner::Small(mut small) => {
+				if small.starts_with(BOM) {
+					small.advance(BOM.len());
+				}
+				if small.is_empty() {
+					Inner::Empty
+				} else {
+					Inner::Small(small)
+				}
+			},
+			Inner::MoreBytes(mut more)
+				if more.chunks().next().is_some_and(|b| b.len() >= BOM.len()) =>
+			{
+				let front = more.front_mut().unwrap();
+				let len = front.len();
+				*front = strip_bom(mem::take(front));
+				let stripped = len - front.len();
+				more.advanced(stripped);
+				Self::from(more).inner
+			},
+			inner => {
+				self.inner = inner;
+				return Self::from_async_bytes_streamer(
+					StripBom::new(self.into_async_bytes_streamer())
+				)
+			}
+		};
+
+		self
+	}
+
 	/// Converts the Body into a type that implements `Read`.
 	pub fn into_sync_reader(self) -> BodySyncReader {
 		BodySyncReader::new(self.inner, self.constraints)
@@ -232,23 +846,69 @@ impl Body {
 	}
 
 	/// Converts the Body into a type that implements `hyper::body::Body`.
+	#[cfg(feature = "hyper_body")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "hyper_body")))]
 	pub fn into_http_body(self) -> BodyHttp {
 		BodyHttp::new(self.inner, self.constraints)
 	}
 
 	/// Converts the Body into a deserializeable type.
+	/// 
+	/// With the feature `simd-json` the body is buffered and parsed with
+	/// simd-json, which selects the fastest implementation supported by the
+	/// cpu at runtime. Bodies larger than 64KiB are parsed with
+	/// `spawn_blocking`.
+	/// 
+	/// ## Errors
+	/// If the json is not valid the inner error is a `JsonError` containing
+	/// the location.
 	#[cfg(feature = "json")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 	pub async fn deserialize<D>(self) -> io::Result<D>
 	where D: serde::de::DeserializeOwned + Send + 'static {
-		let reader = self.into_sync_reader();
-		if reader.needs_spawn_blocking() {
-			task::spawn_blocking(|| serde_json::from_reader(reader)).await
+		#[cfg(feature = "simd-json")]
+		{
+			self.deserialize_simd_json().await
+		}
+
+		#[cfg(not(feature = "simd-json"))]
+		{
+			self.deserialize_serde_json().await
+		}
+	}
+
+	#[cfg(feature = "simd-json")]
+	async fn deserialize_simd_json<D>(self) -> io::Result<D>
+	where D: serde::de::DeserializeOwned + Send + 'static {
+		// simd-json parses in place, the buffer is only copied if it is
+		// shared
+		let buf = BytesMut::from(self.into_bytes().await?);
+		let needs_spawn_blocking = buf.len() > SIMD_JSON_BLOCKING_LEN;
+		let deserialize = move || simd_json_from_buf(buf);
+
+		if needs_spawn_blocking {
+			task::spawn_blocking(deserialize).await
 				.map_err(join_error)?
-				.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 		} else {
-			serde_json::from_reader(reader)
-				.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+			deserialize()
+		}
+	}
+
+	#[cfg(all(feature = "json", not(feature = "simd-json")))]
+	async fn deserialize_serde_json<D>(self) -> io::Result<D>
+	where D: serde::de::DeserializeOwned + Send + 'static {
+		let mut reader = json_error::Recorder::new(self.into_sync_reader());
+		let needs_spawn_blocking = reader.inner().needs_spawn_blocking();
+		let deserialize = move || {
+			serde_json::from_reader(&mut reader)
+				.map_err(|e| reader.into_error(e))
+		};
+
+		if needs_spawn_blocking {
+			task::spawn_blocking(deserialize).await
+				.map_err(join_error)?
+		} else {
+			deserialize()
 		}
 	}
 }
@@ -277,14 +937,45 @@ impl From<&'static str> for Body {
 	}
 }
 
+impl From<&'static [u8]> for Body {
+	fn from(b: &'static [u8]) -> Self {
+		Self::from_bytes(Bytes::from_static(b))
+	}
+}
+
+/// Creates a buffered body which returns each `Bytes` as a separate chunk
+/// without concatenating them, see `MoreBytes`.
+impl From<Vec<Bytes>> for Body {
+	fn from(chunks: Vec<Bytes>) -> Self {
+		MoreBytes::from(chunks).into()
+	}
+}
+
+/// Creates a buffered body which returns each `Bytes` as a separate chunk
+/// without concatenating them, see `MoreBytes`.
+impl FromIterator<Bytes> for Body {
+	fn from_iter<I: IntoIterator<Item=Bytes>>(iter: I) -> Self {
+		iter.into_iter().collect::<MoreBytes>().into()
+	}
+}
+
+#[cfg(feature = "hyper_body")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hyper_body")))]
 impl From<Incoming> for Body {
 	fn from(i: Incoming) -> Self {
 		Self::from_incoming(i)
 	}
 }
 
-fn size_limit_reached(msg: &'static str) -> io::Error {
-	io::Error::new(io::ErrorKind::UnexpectedEof, msg)
+#[cfg(feature = "simd-json")]
+fn simd_json_from_buf<D>(mut buf: BytesMut) -> io::Result<D>
+where D: serde::de::DeserializeOwned {
+	simd_json::serde::from_slice(&mut buf).map_err(|e| {
+		// strings before the error might already be unescaped
+		let offset = e.index();
+		let e = JsonError::from_slice(&buf, offset, e);
+		io::Error::new(io::ErrorKind::InvalidData, e)
+	})
 }
 
 fn timed_out(msg: &'static str) -> io::Error {
@@ -293,4 +984,190 @@ fn timed_out(msg: &'static str) -> io::Error {
 
 fn join_error(error: task::JoinError) -> io::Error {
 	io::Error::new(io::ErrorKind::Other, error)
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::error::SizeLimitReached;
+
+
+	/// Returns a body with unknown length which yields every chunk separately.
+	fn chunked(chunks: &[&'static str]) -> Body {
+		let chunks: Vec<_> = chunks.iter()
+			.map(|c| Ok(Bytes::from_static(c.as_bytes())))
+			.collect();
+		Body::from_async_bytes_streamer(tokio_stream::iter(chunks))
+	}
+
+	fn segments(more: &MoreBytes) -> Vec<&[u8]> {
+		more.chunks().map(|b| &**b).collect()
+	}
+
+	#[tokio::test]
+	async fn test_read_at_most() {
+
+		let (more, truncated) = chunked(&["abc", "def", "ghi"])
+			.read_at_most(5).await.unwrap();
+		assert_eq!(segments(&more), [&b"abc"[..], b"de"]);
+		assert!(truncated);
+
+		// ending on a chunk boundary
+		let (more, truncated) = chunked(&["abc", "def", "ghi"])
+			.read_at_most(6).await.unwrap();
+		assert_eq!(segments(&more), [&b"abc"[..], b"def"]);
+		assert!(truncated);
+
+		let (more, truncated) = chunked(&["abc", "", "def"])
+			.read_at_most(6).await.unwrap();
+		assert_eq!(more.len(), 6);
+		assert!(!truncated);
+
+		let (more, truncated) = chunked(&["abc", "def"])
+			.read_at_most(100).await.unwrap();
+		assert_eq!(more.len(), 6);
+		assert!(!truncated);
+
+		let (more, truncated) = chunked(&["abc"])
+			.read_at_most(0).await.unwrap();
+		assert!(more.is_empty());
+		assert!(truncated);
+
+		let (more, truncated) = Body::new().read_at_most(0).await.unwrap();
+		assert!(more.is_empty());
+		assert!(!truncated);
+
+		let (more, truncated) = Body::from("hello")
+			.read_at_most(4).await.unwrap();
+		assert_eq!(more.into_bytes(), "hell");
+		assert!(truncated);
+
+	}
+
+	#[tokio::test]
+	async fn test_drain() {
+
+		let body = chunked(&["abc", "def", "ghi"]);
+		assert_eq!(body.drain(9).await.unwrap(), 9);
+
+		assert_eq!(Body::new().drain(0).await.unwrap(), 0);
+
+		let e = chunked(&["abc", "def", "ghi"]).drain(8).await.unwrap_err();
+		assert!(e.get_ref().unwrap().is::<SizeLimitReached>());
+
+		// the constraints still apply
+		let mut body = chunked(&["abc", "def", "ghi"]);
+		body.set_size_limit(Some(4));
+		let e = body.drain(100).await.unwrap_err();
+		assert!(e.get_ref().unwrap().is::<SizeLimitReached>());
+
+	}
+	#[tokio::test]
+	async fn test_bytes_read() {
+
+		use std::io::Read;
+		use tokio::io::AsyncReadExt;
+
+		let mut body = chunked(&["hello ", "world"]);
+		body.set_size_limit(Some(20));
+		let mut stream = Box::pin(body.into_async_bytes_streamer());
+		assert_eq!(stream.bytes_read(), 0);
+		assert_eq!(stream.size_limit_remaining(), Some(20));
+		stream.next().await.unwrap().unwrap();
+		assert_eq!(stream.bytes_read(), 6);
+		assert_eq!(stream.size_limit_remaining(), Some(14));
+
+		let mut body = chunked(&["hello ", "world"]);
+		body.set_size_limit(Some(20));
+		let mut reader = Box::pin(body.into_async_reader());
+		let mut buf = [0; 4];
+		reader.read_exact(&mut buf).await.unwrap();
+		assert_eq!(reader.bytes_read(), 4);
+		assert_eq!(reader.size_limit_remaining(), Some(16));
+
+		let mut body = Body::from("hello world");
+		body.set_size_limit(Some(20));
+		let mut reader = body.into_sync_reader();
+		reader.read_exact(&mut buf).unwrap();
+		assert_eq!(reader.bytes_read(), 4);
+		assert_eq!(reader.size_limit_remaining(), Some(16));
+
+		// a sync reader over a stream
+		let mut body = chunked(&["hello ", "world"]);
+		body.set_size_limit(Some(20));
+		let mut reader = body.into_sync_reader();
+		assert!(reader.needs_spawn_blocking());
+		let reader = task::spawn_blocking(move || {
+			reader.read_exact(&mut buf).unwrap();
+			reader
+		}).await.unwrap();
+		assert_eq!(reader.bytes_read(), 4);
+		assert_eq!(reader.size_limit_remaining(), Some(16));
+
+		let reader = Body::new().into_sync_reader();
+		assert_eq!(reader.bytes_read(), 0);
+		assert_eq!(reader.size_limit_remaining(), None);
+
+	}
+	#[tokio::test]
+	async fn test_copy_to_async_write() {
+
+		let mut out = vec![];
+		let written = chunked(&["hello ", "", "world"])
+			.copy_to_async_write(&mut out).await.unwrap();
+		assert_eq!(written, 11);
+		assert_eq!(out, b"hello world");
+
+		let mut out = vec![];
+		let written = Body::new().copy_to_async_write(&mut out).await.unwrap();
+		assert_eq!(written, 0);
+		assert!(out.is_empty());
+
+	}
+	#[tokio::test]
+	async fn test_split_at() {
+
+		async fn split(body: Body, n: usize) -> (String, String) {
+			let (prefix, rest) = body.split_at(n).await.unwrap();
+			(
+				prefix.into_string().await.unwrap(),
+				rest.into_string().await.unwrap()
+			)
+		}
+
+		let cases = [
+			(0, "", "abcdefghi"),
+			(2, "ab", "cdefghi"),
+			(3, "abc", "defghi"),
+			(7, "abcdefg", "hi"),
+			(9, "abcdefghi", ""),
+			(20, "abcdefghi", "")
+		];
+
+		for (n, prefix, rest) in cases {
+			let (p, r) = split(chunked(&["abc", "def", "ghi"]), n).await;
+			assert_eq!((p.as_str(), r.as_str()), (prefix, rest), "stream {n}");
+
+			let body = Body::from_iter(["abc", "def", "ghi"].map(Bytes::from));
+			let (p, r) = split(body, n).await;
+			assert_eq!((p.as_str(), r.as_str()), (prefix, rest), "more {n}");
+
+			let (p, r) = split(Body::from("abcdefghi"), n).await;
+			assert_eq!((p.as_str(), r.as_str()), (prefix, rest), "bytes {n}");
+		}
+
+		// buffered segments are not copied and keep their length
+		let body = Body::from_iter(["abc", "def", "ghi"].map(Bytes::from));
+		let (prefix, rest) = body.split_at(4).await.unwrap();
+		assert_eq!(prefix.as_more_bytes().unwrap().segments_len(), 2);
+		assert_eq!(rest.len(), Some(5));
+		assert_eq!(rest.as_more_bytes().unwrap().segments_len(), 2);
+
+		let (prefix, rest) = chunked(&["abc", "def"]).split_at(4).await.unwrap();
+		assert_eq!(prefix.len(), Some(4));
+		assert!(rest.len().is_none());
+
+	}
 }
\ No newline at end of file
@@ -0,0 +1,217 @@
+use super::Body;
+
+use std::io::IoSlice;
+use std::collections::VecDeque;
+
+use bytes::{Bytes, BytesMut};
+
+
+/// A buffered body made of several `Bytes` segments which are not
+/// concatenated, for example the parts of a multipart body.
+///
+/// Converting it into a `Body` keeps the length known, so `content-length`
+/// can still be set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoreBytes {
+	// never contains an empty segment
+	segments: VecDeque<Bytes>,
+	len: usize
+}
+
+impl MoreBytes {
+	/// Creates an empty `MoreBytes`.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Appends a segment without copying it, empty bytes are ignored.
+	pub fn push_bytes(&mut self, bytes: Bytes) {
+		if bytes.is_empty() {
+			return
+		}
+
+		self.len += bytes.len();
+		self.segments.push_back(bytes);
+	}
+
+	/// Appends a copy of the slice as a new segment.
+	pub fn extend_from_slice(&mut self, slice: &[u8]) {
+		self.push_bytes(Bytes::copy_from_slice(slice));
+	}
+
+	/// Returns the total length of all segments.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns true if there are no bytes.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns the number of segments.
+	pub fn segments_len(&self) -> usize {
+		self.segments.len()
+	}
+
+	/// Concatenates all segments, a single segment is returned without
+	/// copying it.
+	pub fn into_bytes(mut self) -> Bytes {
+		if self.segments.len() <= 1 {
+			return self.segments.pop_front().unwrap_or_default()
+		}
+
+		let mut v = BytesMut::with_capacity(self.len);
+		for segment in &self.segments {
+			v.extend_from_slice(segment);
+		}
+
+		v.freeze()
+	}
+
+	/// Returns an iterator over the segments, which are never empty.
+	pub fn chunks(&self) -> impl Iterator<Item=&Bytes> {
+		self.segments.iter()
+	}
+
+	/// Returns all segments as `IoSlice`s, to write them with a single
+	/// `write_vectored` call.
+	pub fn as_io_slices(&self) -> Vec<IoSlice<'_>> {
+		self.segments.iter().map(|b| IoSlice::new(b)).collect()
+	}
+
+	pub(super) fn pop_front(&mut self) -> Option<Bytes> {
+		let bytes = self.segments.pop_front()?;
+		self.len -= bytes.len();
+		Some(bytes)
+	}
+
+	/// Returns the first segment mutably, which is never empty.
+	pub(super) fn front_mut(&mut self) -> Option<&mut Bytes> {
+		self.segments.front_mut()
+	}
+
+	/// Needs to be called after bytes were removed from the front segment.
+	pub(super) fn advanced(&mut self, read: usize) {
+		self.len -= read;
+		if self.segments.front().is_some_and(|b| b.is_empty()) {
+			self.segments.pop_front();
+		}
+	}
+}
+
+impl From<Bytes> for MoreBytes {
+	fn from(bytes: Bytes) -> Self {
+		let mut more = Self::new();
+		more.push_bytes(bytes);
+		more
+	}
+}
+
+impl From<Vec<Bytes>> for MoreBytes {
+	fn from(segments: Vec<Bytes>) -> Self {
+		segments.into_iter().collect()
+	}
+}
+
+impl FromIterator<Bytes> for MoreBytes {
+	fn from_iter<I: IntoIterator<Item=Bytes>>(iter: I) -> Self {
+		let mut more = Self::new();
+		more.extend(iter);
+		more
+	}
+}
+
+impl Extend<Bytes> for MoreBytes {
+	fn extend<I: IntoIterator<Item=Bytes>>(&mut self, iter: I) {
+		for bytes in iter {
+			self.push_bytes(bytes);
+		}
+	}
+}
+
+impl From<MoreBytes> for Body {
+	fn from(more: MoreBytes) -> Self {
+		Self::from_more_bytes(more)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use tokio_stream::StreamExt;
+
+	#[tokio::test]
+	async fn test_more_bytes() {
+
+		let mut more = MoreBytes::new();
+		more.push_bytes(Bytes::from_static(b"hello"));
+		more.push_bytes(Bytes::new());
+		more.extend_from_slice(b" ");
+		more.push_bytes(Bytes::from_static(b"world"));
+		assert_eq!(more.len(), 11);
+		assert_eq!(more.segments_len(), 3);
+
+		let body = Body::from(more);
+		assert_eq!(body.len(), Some(11));
+		let copy = body.try_clone().unwrap();
+		assert_eq!(copy.len(), Some(11));
+		assert_eq!(body.into_bytes().await.unwrap(), "hello world");
+
+		let body: Body = vec![Bytes::from("a"), Bytes::from("bc")].into();
+		assert_eq!(body.len(), Some(3));
+		let mut stream = Box::pin(body.into_async_bytes_streamer());
+		assert_eq!(stream.next().await.unwrap().unwrap(), "a");
+		assert_eq!(stream.next().await.unwrap().unwrap(), "bc");
+		assert!(stream.next().await.is_none());
+
+		let more: MoreBytes = vec![Bytes::from("a"), Bytes::from("bc")].into();
+		let chunks: Vec<_> = more.chunks().collect();
+		assert_eq!(chunks, ["a", "bc"]);
+		let slices = more.as_io_slices();
+		assert_eq!(slices.len(), 2);
+		assert_eq!(&*slices[1], b"bc");
+
+		let mut written = vec![];
+		let n = std::io::Write::write_vectored(&mut written, &slices).unwrap();
+		assert_eq!(n, 3);
+		assert_eq!(written, b"abc");
+
+		let body = Body::from(more);
+		assert_eq!(body.as_more_bytes().unwrap().segments_len(), 2);
+		assert!(Body::from("a").as_more_bytes().is_none());
+
+		let body: Body = std::iter::empty::<Bytes>().collect();
+		assert!(body.is_empty());
+
+	}
+
+	#[tokio::test]
+	async fn test_readers() {
+
+		use std::io::Read;
+		use tokio::io::AsyncReadExt;
+
+		let more: MoreBytes = ["ab", "cde", "f"].into_iter()
+			.map(Bytes::from)
+			.collect();
+
+		let mut s = String::new();
+		let mut reader = Box::pin(Body::from(more.clone()).into_async_reader());
+		reader.read_to_string(&mut s).await.unwrap();
+		assert_eq!(s, "abcdef");
+
+		let mut s = String::new();
+		let mut reader = Body::from(more.clone()).into_sync_reader();
+		reader.read_to_string(&mut s).unwrap();
+		assert_eq!(s, "abcdef");
+
+		let mut body = Body::from(more);
+		body.set_size_limit(Some(5));
+		assert!(body.into_bytes().await.is_err());
+
+	}
+
+}
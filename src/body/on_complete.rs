@@ -0,0 +1,153 @@
+use super::BodyAsyncBytesStreamer;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use pin_project_lite::pin_project;
+
+use bytes::Bytes;
+
+
+pin_project! {
+	/// Calls `f` once the inner stream finished, returned an error or was
+	/// dropped.
+	pub(super) struct OnComplete<F>
+	where F: FnOnce(io::Result<u64>) {
+		#[pin]
+		inner: BodyAsyncBytesStreamer,
+		total: u64,
+		f: Option<F>
+	}
+
+	impl<F> PinnedDrop for OnComplete<F>
+	where F: FnOnce(io::Result<u64>) {
+		fn drop(this: Pin<&mut Self>) {
+			if let Some(f) = this.project().f.take() {
+				f(Err(io::Error::other(
+					"body dropped before it was completely read"
+				)));
+			}
+		}
+	}
+}
+
+impl<F> OnComplete<F>
+where F: FnOnce(io::Result<u64>) {
+	pub fn new(inner: BodyAsyncBytesStreamer, f: F) -> Self {
+		Self { inner, total: 0, f: Some(f) }
+	}
+}
+
+impl<F> Stream for OnComplete<F>
+where F: FnOnce(io::Result<u64>) {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let me = self.project();
+
+		let r = match me.inner.poll_next(cx) {
+			Poll::Pending => return Poll::Pending,
+			Poll::Ready(r) => r
+		};
+
+		match &r {
+			Some(Ok(bytes)) => *me.total += bytes.len() as u64,
+			Some(Err(e)) => {
+				if let Some(f) = me.f.take() {
+					// io::Error is not Clone
+					f(Err(io::Error::new(e.kind(), e.to_string())));
+				}
+			},
+			None => {
+				if let Some(f) = me.f.take() {
+					f(Ok(*me.total));
+				}
+			}
+		}
+
+		Poll::Ready(r)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use crate::Body;
+
+	use std::io;
+	use std::sync::{Arc, Mutex};
+
+	use bytes::Bytes;
+	use tokio_stream::StreamExt;
+
+
+	type Completed = Arc<Mutex<Vec<io::Result<u64>>>>;
+
+	fn on_complete(body: Body) -> (Body, Completed) {
+		let completed = Completed::default();
+		let body = body.on_complete({
+			let completed = completed.clone();
+			move |r| completed.lock().unwrap().push(r)
+		});
+
+		(body, completed)
+	}
+
+	#[tokio::test]
+	async fn test_total() {
+
+		let (body, completed) = on_complete(Body::from_iter(
+			["hello ", "world"].map(Bytes::from)
+		));
+		assert!(body.len().is_none());
+		assert_eq!(body.into_string().await.unwrap(), "hello world");
+
+		let completed = completed.lock().unwrap();
+		assert_eq!(completed.len(), 1);
+		assert_eq!(completed[0].as_ref().unwrap(), &11);
+
+	}
+
+	#[tokio::test]
+	async fn test_error() {
+
+		let chunks = [
+			Ok(Bytes::from("hello")),
+			Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+		];
+		let body = Body::from_async_bytes_streamer(tokio_stream::iter(chunks));
+		let (body, completed) = on_complete(body);
+		assert!(body.into_bytes().await.is_err());
+
+		let completed = completed.lock().unwrap();
+		assert_eq!(completed.len(), 1);
+		let e = completed[0].as_ref().unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::ConnectionReset);
+		assert_eq!(e.to_string(), "reset");
+
+	}
+
+	#[tokio::test]
+	async fn test_dropped() {
+
+		let (body, completed) = on_complete(Body::from_iter(
+			["hello ", "world"].map(Bytes::from)
+		));
+		let mut stream = Box::pin(body.into_async_bytes_streamer());
+		assert_eq!(stream.next().await.unwrap().unwrap(), "hello ");
+		assert!(completed.lock().unwrap().is_empty());
+		drop(stream);
+
+		let completed = completed.lock().unwrap();
+		assert_eq!(completed.len(), 1);
+		let e = completed[0].as_ref().unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::Other);
+
+	}
+}
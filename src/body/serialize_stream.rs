@@ -0,0 +1,189 @@
+use super::Body;
+
+use std::{io, thread, fmt};
+use std::io::{Read, Write, BufWriter};
+use std::pin::pin;
+use std::sync::mpsc::{self, SyncSender, Receiver};
+
+use bytes::{Bytes, Buf};
+
+use futures_core::Stream as AsyncStream;
+use tokio_stream::StreamExt;
+
+use serde::Serialize;
+
+
+const CHUNK_SIZE: usize = 8 * 1024;
+// how many chunks can be serialized ahead of the reader
+const BUFFERED_CHUNKS: usize = 4;
+
+impl Body {
+	/// Creates a new Body which serializes the value as json while the body
+	/// is read.
+	///
+	/// The value is serialized on a separate thread into chunks of around
+	/// 8KiB, which waits if the reader does not keep up. This avoids
+	/// allocating the whole json at once.
+	///
+	/// A serialization error is returned when reading the body.
+	#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+	pub fn serialize_stream<S>(value: S) -> Self
+	where S: Serialize + Send + 'static {
+		Self::from_sync_reader(SerializeReader {
+			value: Some(value),
+			rx: None,
+			chunk: Bytes::new()
+		})
+	}
+
+	/// Creates a new Body which serializes the items of the stream as a
+	/// json array while the body is read, without buffering the whole list.
+	///
+	/// If the stream returns an error, or an item cannot be serialized, the
+	/// array ends with an `{"error":"<message>"}` object so the body is
+	/// still valid json and the client can detect the truncation.
+	#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+	pub fn serialize_json_array<St, T, E>(stream: St) -> Self
+	where
+		St: AsyncStream<Item=Result<T, E>> + Send + 'static,
+		T: Serialize,
+		E: fmt::Display
+	{
+		Self::from_writer(move |mut writer| async move {
+			let mut stream = pin!(stream);
+			let mut first = true;
+			let mut buf = vec![];
+
+			writer.write("[").await?;
+			loop {
+				// the item is not Send so it cannot be kept across an await
+				let r = match stream.next().await {
+					Some(item) => serialize_item(item, &mut buf),
+					None => break
+				};
+
+				if !first {
+					writer.write(",").await?;
+				}
+				first = false;
+
+				match r {
+					Ok(()) => writer.write(&buf).await?,
+					Err(e) => {
+						let error = serde_json::json!({ "error": e });
+						writer.write(error.to_string()).await?;
+						break
+					}
+				}
+			}
+			writer.write("]").await?;
+
+			Ok(())
+		})
+	}
+}
+
+fn serialize_item<T, E>(
+	item: Result<T, E>,
+	buf: &mut Vec<u8>
+) -> Result<(), String>
+where T: Serialize, E: fmt::Display {
+	buf.clear();
+	let item = item.map_err(|e| e.to_string())?;
+	serde_json::to_writer(buf, &item).map_err(|e| e.to_string())
+}
+
+struct SerializeReader<S> {
+	// the serialization is started on the first read
+	value: Option<S>,
+	rx: Option<Receiver<io::Result<Bytes>>>,
+	chunk: Bytes
+}
+
+impl<S> Read for SerializeReader<S>
+where S: Serialize + Send + 'static {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		if let Some(value) = self.value.take() {
+			let (tx, rx) = mpsc::sync_channel(BUFFERED_CHUNKS);
+			self.rx = Some(rx);
+			thread::spawn(move || serialize(value, tx));
+		}
+
+		if self.chunk.is_empty() {
+			let rx = self.rx.as_ref().unwrap();
+			match rx.recv() {
+				Ok(chunk) => self.chunk = chunk?,
+				// the sender is dropped after everything was sent
+				Err(_) => return Ok(0)
+			}
+		}
+
+		let len = self.chunk.len().min(buf.len());
+		buf[..len].copy_from_slice(&self.chunk[..len]);
+		self.chunk.advance(len);
+
+		Ok(len)
+	}
+}
+
+fn serialize<S: Serialize>(value: S, tx: SyncSender<io::Result<Bytes>>) {
+	let mut writer = BufWriter::with_capacity(
+		CHUNK_SIZE,
+		ChannelWriter { tx: tx.clone() }
+	);
+
+	let r = serde_json::to_writer(&mut writer, &value)
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+		.and_then(|_| writer.flush());
+
+	if let Err(e) = r {
+		// if the reader was dropped this fails which is fine
+		let _ = tx.send(Err(e));
+	}
+}
+
+struct ChannelWriter {
+	tx: SyncSender<io::Result<Bytes>>
+}
+
+impl Write for ChannelWriter {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.tx.send(Ok(Bytes::copy_from_slice(buf)))
+			.map_err(|_| {
+				io::Error::new(io::ErrorKind::BrokenPipe, "body dropped")
+			})?;
+
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_serialize_json_array() {
+
+		let items: Vec<Result<u32, &str>> = vec![Ok(1), Ok(2)];
+		let body = Body::serialize_json_array(tokio_stream::iter(items));
+		assert_eq!(body.into_string().await.unwrap(), "[1,2]");
+
+		let items: Vec<Result<u32, &str>> = vec![Ok(1), Err("failed"), Ok(3)];
+		let body = Body::serialize_json_array(tokio_stream::iter(items));
+		assert_eq!(
+			body.into_string().await.unwrap(),
+			r#"[1,{"error":"failed"}]"#
+		);
+
+		let empty = tokio_stream::empty::<Result<u32, &str>>();
+		let body = Body::serialize_json_array(empty);
+		assert_eq!(body.into_string().await.unwrap(), "[]");
+
+	}
+
+}
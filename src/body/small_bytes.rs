@@ -0,0 +1,119 @@
+use std::ops::Deref;
+
+use bytes::Bytes;
+
+
+/// The maximum length of a body which is stored inline, see
+/// `Body::INLINE_LEN`.
+///
+/// Chosen so that the inline buffer is not bigger than `Bytes`, a `Body`
+/// does not get bigger because of it.
+pub(super) const INLINE_LEN: usize = 30;
+
+/// A small body which is stored inline without allocating.
+#[derive(Clone, Copy)]
+pub(super) struct SmallBytes {
+	buf: [u8; INLINE_LEN],
+	start: u8,
+	end: u8
+}
+
+impl SmallBytes {
+	/// Returns None if the slice is longer than `INLINE_LEN`.
+	pub fn new(slice: &[u8]) -> Option<Self> {
+		if slice.len() > INLINE_LEN {
+			return None
+		}
+
+		let mut buf = [0; INLINE_LEN];
+		buf[..slice.len()].copy_from_slice(slice);
+
+		Some(Self { buf, start: 0, end: slice.len() as u8 })
+	}
+
+	/// Removes `cnt` bytes from the front.
+	///
+	/// ## Panics
+	/// If `cnt` is bigger than the length.
+	pub fn advance(&mut self, cnt: usize) {
+		assert!(cnt <= self.len(), "cannot advance past the end");
+		self.start += cnt as u8;
+	}
+
+	/// Copies the bytes into a `Bytes`.
+	pub fn to_bytes(self) -> Bytes {
+		Bytes::copy_from_slice(&self)
+	}
+}
+
+impl Deref for SmallBytes {
+	type Target = [u8];
+
+	fn deref(&self) -> &[u8] {
+		&self.buf[self.start as usize..self.end as usize]
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::Body;
+
+	use std::io::Read;
+	use std::mem::size_of;
+
+	use tokio::io::AsyncReadExt;
+
+
+	#[test]
+	fn test_size() {
+
+		assert_eq!(size_of::<SmallBytes>(), size_of::<Bytes>());
+
+	}
+
+	#[test]
+	fn test_advance() {
+
+		assert!(SmallBytes::new(&[0; INLINE_LEN + 1]).is_none());
+
+		let mut small = SmallBytes::new(b"hello").unwrap();
+		small.advance(2);
+		assert_eq!(&*small, b"llo");
+		small.advance(3);
+		assert!(small.is_empty());
+		assert_eq!(small.to_bytes(), Bytes::new());
+
+	}
+
+	#[tokio::test]
+	async fn test_readers() {
+
+		let body = Body::copy_from_slice("hello");
+		assert_eq!(body.len(), Some(5));
+		assert_eq!(body.into_bytes().await.unwrap(), "hello");
+
+		let mut s = String::new();
+		Body::copy_from_slice("hello").into_sync_reader()
+			.read_to_string(&mut s).unwrap();
+		assert_eq!(s, "hello");
+
+		let mut s = String::new();
+		Box::pin(Body::copy_from_slice("hello").into_async_reader())
+			.read_to_string(&mut s).await.unwrap();
+		assert_eq!(s, "hello");
+
+		let (prefix, rest) = Body::copy_from_slice("hello")
+			.split_at(2).await.unwrap();
+		assert_eq!(prefix.into_bytes().await.unwrap(), "he");
+		assert_eq!(rest.len(), Some(3));
+		assert_eq!(rest.into_bytes().await.unwrap(), "llo");
+
+		let mut body = Body::copy_from_slice("hello");
+		body.set_size_limit(Some(4));
+		assert!(body.into_bytes().await.is_err());
+
+	}
+}
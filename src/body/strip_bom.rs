@@ -0,0 +1,198 @@
+use super::BodyAsyncBytesStreamer;
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use pin_project_lite::pin_project;
+
+use bytes::{Bytes, BytesMut};
+
+
+pub(super) const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// Removes a leading utf8 bom without copying.
+pub(super) fn strip_bom(bytes: Bytes) -> Bytes {
+	match bytes.starts_with(BOM) {
+		true => bytes.slice(BOM.len()..),
+		false => bytes
+	}
+}
+
+pin_project! {
+	/// Removes a leading utf8 bom, only the first chunks which are needed to
+	/// decide if the body starts with a bom are inspected.
+	pub(super) struct StripBom {
+		#[pin]
+		inner: BodyAsyncBytesStreamer,
+		// the start of the body if it is shorter than the bom, None after the
+		// start was returned
+		start: Option<BytesMut>
+	}
+}
+
+impl StripBom {
+	pub fn new(inner: BodyAsyncBytesStreamer) -> Self {
+		Self { inner, start: Some(BytesMut::new()) }
+	}
+}
+
+impl Stream for StripBom {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let mut me = self.project();
+
+		while let Some(start) = me.start {
+			let bytes = match me.inner.as_mut().poll_next(cx) {
+				Poll::Ready(Some(Ok(bytes))) => bytes,
+				Poll::Ready(None) => {
+					// the body is shorter than the bom
+					let start = me.start.take().unwrap();
+					if start.is_empty() {
+						return Poll::Ready(None)
+					}
+
+					return Poll::Ready(Some(Ok(start.freeze())))
+				},
+				r => return r
+			};
+
+			let bytes = match start.is_empty() {
+				true => bytes,
+				false => {
+					start.extend_from_slice(&bytes);
+					start.split().freeze()
+				}
+			};
+
+			// the body could still start with a bom
+			if bytes.len() < BOM.len() && BOM.starts_with(&bytes) {
+				start.extend_from_slice(&bytes);
+				continue
+			}
+
+			*me.start = None;
+			let bytes = strip_bom(bytes);
+			if !bytes.is_empty() {
+				return Poll::Ready(Some(Ok(bytes)))
+			}
+		}
+
+		me.inner.poll_next(cx)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use crate::body::{Body, MoreBytes};
+
+	use std::io;
+
+	use bytes::Bytes;
+
+	fn stream(chunks: &[&'static [u8]]) -> Body {
+		let chunks: Vec<io::Result<Bytes>> = chunks.iter()
+			.map(|c| Ok(Bytes::from_static(c)))
+			.collect();
+		Body::from_async_bytes_streamer(tokio_stream::iter(chunks))
+	}
+
+	#[tokio::test]
+	async fn test_buffered() {
+
+		let bytes = Bytes::from_static(b"\xef\xbb\xbf{}");
+		let body = Body::from(bytes.clone()).strip_utf8_bom();
+		assert_eq!(body.len(), Some(2));
+		let stripped = body.into_bytes().await.unwrap();
+		assert_eq!(stripped, "{}");
+		// not copied
+		assert_eq!(stripped.as_ptr(), bytes[3..].as_ptr());
+
+		let body = Body::from("{}").strip_utf8_bom();
+		assert_eq!(body.into_bytes().await.unwrap(), "{}");
+
+		let body = Body::from(&b"\xef\xbb\xbf"[..]).strip_utf8_bom();
+		assert!(body.is_empty());
+
+		let body = Body::new().strip_utf8_bom();
+		assert_eq!(body.len(), Some(0));
+
+		let body = Body::copy_from_slice(b"\xef\xbb\xbf{}").strip_utf8_bom();
+		assert_eq!(body.len(), Some(2));
+		assert_eq!(body.into_bytes().await.unwrap(), "{}");
+
+		let mut body = Body::from(&b"\xef\xbb\xbf1234"[..]);
+		body.set_size_limit(Some(3));
+		assert!(body.strip_utf8_bom().into_bytes().await.is_err());
+
+		let more: MoreBytes = vec![
+			Bytes::from_static(b"\xef\xbb\xbf{"),
+			Bytes::from_static(b"}")
+		].into();
+		let body = Body::from(more).strip_utf8_bom();
+		assert_eq!(body.len(), Some(2));
+		assert_eq!(body.into_bytes().await.unwrap(), "{}");
+
+		let more: MoreBytes = vec![
+			Bytes::from_static(b"\xef\xbb\xbf"),
+			Bytes::from_static(b"{}")
+		].into();
+		let body = Body::from(more).strip_utf8_bom();
+		assert_eq!(body.len(), Some(2));
+		assert_eq!(body.into_bytes().await.unwrap(), "{}");
+
+		// the first segment is shorter than the bom
+		let more: MoreBytes = vec![
+			Bytes::from_static(b"\xef"),
+			Bytes::from_static(b"\xbb\xbf{}")
+		].into();
+		let body = Body::from(more).strip_utf8_bom();
+		assert_eq!(body.into_bytes().await.unwrap(), "{}");
+
+	}
+
+	#[tokio::test]
+	async fn test_stream() {
+
+		let cases: &[(&[&[u8]], &[u8])] = &[
+			(&[b"\xef\xbb\xbf{", b"}"], b"{}"),
+			(&[b"\xef\xbb", b"\xbf{", b"}"], b"{}"),
+			(&[b"\xef", b"\xbb", b"\xbf", b"{}"], b"{}"),
+			(&[b"\xef\xbb\xbf", b"", b"{}"], b"{}"),
+			(&[b"\xef\xbb", b"{}"], b"\xef\xbb{}"),
+			(&[b"\xef\xbb"], b"\xef\xbb"),
+			(&[b"a", b"b"], b"ab"),
+			(&[b"\xef\xbb\xbf"], b""),
+			(&[], b"")
+		];
+
+		for (chunks, expected) in cases {
+			let body = stream(chunks).strip_utf8_bom();
+			let bytes = body.into_bytes().await.unwrap();
+			assert_eq!(bytes, expected, "{chunks:?}");
+		}
+
+		// chunks after the start are passed through
+		let chunk = Bytes::from_static(b"later");
+		let chunks: Vec<io::Result<Bytes>> = vec![
+			Ok(Bytes::from_static(b"\xef\xbb\xbf")),
+			Ok(chunk.clone())
+		];
+		let body = Body::from_async_bytes_streamer(tokio_stream::iter(chunks))
+			.strip_utf8_bom();
+		let mut stream = Box::pin(body.into_async_bytes_streamer());
+		let next = tokio_stream::StreamExt::next(&mut stream).await
+			.unwrap()
+			.unwrap();
+		assert_eq!(next.as_ptr(), chunk.as_ptr());
+
+	}
+
+}
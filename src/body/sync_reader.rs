@@ -1,4 +1,7 @@
-use super::{size_limit_reached, Constraints, BodyAsyncReader, BoxedSyncRead};
+use super::{
+	size_limit_reached, Constraints, BodyAsyncReader, BoxedSyncRead, SmallBytes,
+	MoreBytes
+};
 
 use std::io;
 use std::io::Read;
@@ -6,7 +9,7 @@ use std::pin::Pin;
 
 use tokio_util::io::SyncIoBridge;
 
-use bytes::Bytes;
+use bytes::{Bytes, Buf};
 
 
 /// ## Panics
@@ -18,23 +21,48 @@ pub struct BodySyncReader {
 impl BodySyncReader {
 	pub(super) fn new(inner: super::Inner, constraints: Constraints) -> Self {
 		let inner = match inner {
-			super::Inner::Empty => Inner::Empty,
+			super::Inner::Empty => {
+				Inner::Sync(ConstrainedSyncReader::new(
+					InnerSync::Bytes(Bytes::new()),
+					constraints
+				))
+			},
 			super::Inner::Bytes(b) => {
 				Inner::Sync(ConstrainedSyncReader::new(
 					InnerSync::Bytes(b),
 					constraints
 				))
 			},
+			super::Inner::Small(s) => {
+				Inner::Sync(ConstrainedSyncReader::new(
+					InnerSync::Small(s),
+					constraints
+				))
+			},
+			super::Inner::MoreBytes(m) => {
+				Inner::Sync(ConstrainedSyncReader::new(
+					InnerSync::MoreBytes(m),
+					constraints
+				))
+			},
 			super::Inner::SyncReader(r) => {
 				Inner::Sync(ConstrainedSyncReader::new(
 					InnerSync::SyncReader(r),
 					constraints
 				))
 			},
-			i => Inner::Async(SyncIoBridge::new(Box::pin(BodyAsyncReader::new(
-				i,
-				constraints
-			))))
+			i => {
+				// the size limit is checked here so that it can be observed
+				let size = Constraints { size: constraints.size, timeout: None };
+				let reader = BodyAsyncReader::new(i, Constraints {
+					size: None,
+					..constraints
+				});
+				Inner::Async(ConstrainedSyncReader::new(
+					SyncIoBridge::new(Box::pin(reader)),
+					size
+				))
+			}
 		};
 
 		Self { inner }
@@ -44,6 +72,23 @@ impl BodySyncReader {
 	pub fn needs_spawn_blocking(&self) -> bool {
 		matches!(self.inner, Inner::Async(_))
 	}
+
+	/// Returns the number of bytes read so far.
+	pub fn bytes_read(&self) -> u64 {
+		match &self.inner {
+			Inner::Sync(r) => r.read,
+			Inner::Async(r) => r.read
+		}
+	}
+
+	/// Returns how many bytes can still be read before the size limit is
+	/// reached, or `None` if there is no limit.
+	pub fn size_limit_remaining(&self) -> Option<usize> {
+		match &self.inner {
+			Inner::Sync(r) => r.size_limit,
+			Inner::Async(r) => r.size_limit
+		}
+	}
 }
 
 impl Read for BodySyncReader {
@@ -53,15 +98,13 @@ impl Read for BodySyncReader {
 }
 
 enum Inner {
-	Empty,
 	Sync(ConstrainedSyncReader<InnerSync>),
-	Async(SyncIoBridge<Pin<Box<BodyAsyncReader>>>)
+	Async(ConstrainedSyncReader<SyncIoBridge<Pin<Box<BodyAsyncReader>>>>)
 }
 
 impl Read for Inner {
 	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
 		match self {
-			Self::Empty => Ok(0),
 			Self::Sync(r) => r.read(buf),
 			Self::Async(r) => r.read(buf)
 		}
@@ -70,6 +113,8 @@ impl Read for Inner {
 
 enum InnerSync {
 	Bytes(Bytes),
+	Small(SmallBytes),
+	MoreBytes(MoreBytes),
 	SyncReader(BoxedSyncRead)
 }
 
@@ -80,6 +125,24 @@ impl Read for InnerSync {
 			Self::Bytes(b) => {
 				let read = buf.len().min(b.len());
 				buf[..read].copy_from_slice(&b[..read]);
+				b.advance(read);
+				Ok(read)
+			},
+			Self::Small(s) => {
+				let read = buf.len().min(s.len());
+				buf[..read].copy_from_slice(&s[..read]);
+				s.advance(read);
+				Ok(read)
+			},
+			Self::MoreBytes(m) => {
+				let Some(b) = m.front_mut() else {
+					return Ok(0)
+				};
+
+				let read = buf.len().min(b.len());
+				buf[..read].copy_from_slice(&b[..read]);
+				b.advance(read);
+				m.advanced(read);
 				Ok(read)
 			},
 			Self::SyncReader(r) => r.read(buf)
@@ -90,14 +153,16 @@ impl Read for InnerSync {
 /// Only using size constraint
 struct ConstrainedSyncReader<R> {
 	inner: R,
-	size_limit: Option<usize>
+	size_limit: Option<usize>,
+	read: u64
 }
 
 impl<R> ConstrainedSyncReader<R> {
 	pub fn new(reader: R, constraints: Constraints) -> Self {
 		Self {
 			inner: reader,
-			size_limit: constraints.size
+			size_limit: constraints.size,
+			read: 0
 		}
 	}
 }
@@ -105,6 +170,7 @@ impl<R> ConstrainedSyncReader<R> {
 impl<R: Read> Read for ConstrainedSyncReader<R> {
 	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
 		let read = self.inner.read(buf)?;
+		self.read += read as u64;
 
 		if let Some(size_limit) = &mut self.size_limit {
 			match size_limit.checked_sub(read) {
@@ -127,4 +193,28 @@ pub(super) fn sync_reader_into_bytes(
 	reader.read_to_end(&mut v)?;
 
 	Ok(v.into())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::Body;
+
+	use std::io::Read;
+
+	#[test]
+	fn test_bytes_advance() {
+
+		let mut reader = Body::from("hello world").into_sync_reader();
+
+		let mut buf = [0u8; 6];
+		assert_eq!(reader.read(&mut buf).unwrap(), 6);
+		assert_eq!(&buf, b"hello ");
+
+		assert_eq!(reader.read(&mut buf).unwrap(), 5);
+		assert_eq!(&buf[..5], b"world");
+
+		assert_eq!(reader.read(&mut buf).unwrap(), 0);
+
+	}
+
+}
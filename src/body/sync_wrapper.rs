@@ -0,0 +1,67 @@
+use std::io;
+use std::pin::Pin;
+use std::future::Future;
+use std::io::Read as SyncRead;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+use futures_core::Stream;
+
+use pin_project_lite::pin_project;
+
+pin_project! {
+	/// Makes a type which is only `Send` also `Sync`.
+	///
+	/// This is sound because the inner value is only ever accessed through
+	/// `&mut self` or `Pin<&mut Self>`, so it can never be shared between
+	/// threads.
+	pub(super) struct SyncWrapper<T> {
+		#[pin]
+		inner: T
+	}
+}
+
+// Safety: see the struct documentation, `project_ref` is never used
+unsafe impl<T: Send> Sync for SyncWrapper<T> {}
+
+impl<T> SyncWrapper<T> {
+	pub fn new(inner: T) -> Self {
+		Self { inner }
+	}
+}
+
+impl<R: SyncRead> SyncRead for SyncWrapper<R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		self.inner.read(buf)
+	}
+}
+
+impl<R: AsyncRead> AsyncRead for SyncWrapper<R> {
+	fn poll_read(
+		self: Pin<&mut Self>,
+		cx: &mut Context,
+		buf: &mut ReadBuf
+	) -> Poll<io::Result<()>> {
+		self.project().inner.poll_read(cx, buf)
+	}
+}
+
+impl<S: Stream> Stream for SyncWrapper<S> {
+	type Item = S::Item;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<S::Item>> {
+		self.project().inner.poll_next(cx)
+	}
+}
+
+impl<F: Future> Future for SyncWrapper<F> {
+	type Output = F::Output;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<F::Output> {
+		self.project().inner.poll(cx)
+	}
+}
@@ -0,0 +1,120 @@
+use std::io;
+use std::pin::Pin;
+use std::future::Future;
+use std::time::Duration;
+use std::task::{Context, Poll};
+
+use tokio::time::{Instant, Sleep};
+
+use futures_core::Stream;
+
+use pin_project_lite::pin_project;
+
+use bytes::Bytes;
+
+
+pin_project! {
+	/// Delays chunks so that on average at most `rate` bytes per second are
+	/// returned.
+	pub(super) struct Throttle<S> {
+		#[pin]
+		inner: S,
+		// created on the first chunk so that no runtime is needed to
+		// construct the body
+		sleep: Option<Pin<Box<Sleep>>>,
+		start: Option<Instant>,
+		sent: u64,
+		rate: u64
+	}
+}
+
+impl<S> Throttle<S> {
+	pub fn new(inner: S, rate: u64) -> Self {
+		assert!(rate > 0, "rate needs to be bigger than zero");
+
+		Self {
+			inner,
+			sleep: None,
+			start: None,
+			sent: 0,
+			rate
+		}
+	}
+}
+
+impl<S> Stream for Throttle<S>
+where S: Stream<Item=io::Result<Bytes>> {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let me = self.project();
+
+		if let Some(sleep) = me.sleep {
+			if sleep.as_mut().poll(cx).is_pending() {
+				return Poll::Pending
+			}
+		}
+
+		let bytes = match me.inner.poll_next(cx) {
+			Poll::Ready(Some(Ok(b))) => b,
+			other => return other
+		};
+
+		let start = *me.start.get_or_insert_with(Instant::now);
+		*me.sent += bytes.len() as u64;
+		let wait = Duration::from_secs_f64(*me.sent as f64 / *me.rate as f64);
+		match me.sleep {
+			Some(sleep) => sleep.as_mut().reset(start + wait),
+			None => *me.sleep = Some(Box::pin(tokio::time::sleep_until(
+				start + wait
+			)))
+		}
+
+		Poll::Ready(Some(Ok(bytes)))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::body::Body;
+
+	use tokio_stream::StreamExt;
+
+
+	#[test]
+	fn test_new_outside_runtime() {
+
+		let body = Body::from("hello world").throttle(4);
+		assert!(body.len().is_none());
+
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_rate() {
+
+		let stream = tokio_stream::iter(
+			(0..3).map(|_| Ok(Bytes::from_static(b"0123456789")))
+		);
+		let mut throttle = Box::pin(Throttle::new(stream, 10));
+
+		let start = Instant::now();
+		let mut total = 0;
+		while let Some(bytes) = throttle.next().await {
+			total += bytes.unwrap().len();
+			// each chunk may only be returned after the previous chunks
+			// had enough time
+			let min = Duration::from_secs(total as u64 / 10 - 1);
+			assert!(start.elapsed() >= min);
+		}
+
+		assert_eq!(total, 30);
+		assert_eq!(start.elapsed(), Duration::from_secs(3));
+
+	}
+}
@@ -0,0 +1,53 @@
+use super::Body;
+
+
+/// Maps a body to another body, see `Body::through`.
+///
+/// Implemented for every `FnOnce(Body) -> Body + Send`, transforms need to be
+/// `Send` so that a pipeline can be built on one task and applied on another.
+pub trait BodyTransform: Send {
+	/// Consumes the transform and returns the mapped body.
+	fn transform(self: Box<Self>, body: Body) -> Body;
+}
+
+impl<F> BodyTransform for F
+where F: FnOnce(Body) -> Body + Send {
+	fn transform(self: Box<Self>, body: Body) -> Body {
+		(*self)(body)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use bytes::Bytes;
+	use tokio_stream::StreamExt;
+
+
+	fn append(s: &'static str) -> Box<dyn BodyTransform> {
+		Box::new(move |body: Body| {
+			let suffix = Ok(Bytes::from_static(s.as_bytes()));
+			let stream = body.into_async_bytes_streamer()
+				.chain(tokio_stream::once(suffix));
+			Body::from_async_bytes_streamer(stream)
+		})
+	}
+
+	#[tokio::test]
+	async fn test_through_order() {
+
+		let transforms = vec![append("a"), append("b"), append("c")];
+		// pipelines can be moved to other tasks
+		let transforms = tokio::spawn(async move { transforms }).await
+			.unwrap();
+
+		let body = Body::from("0").through(transforms);
+		assert_eq!(body.into_string().await.unwrap(), "0abc");
+
+		let body = Body::from("0").through(vec![]);
+		assert_eq!(body.into_string().await.unwrap(), "0");
+
+	}
+}
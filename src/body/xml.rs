@@ -0,0 +1,118 @@
+use super::{Body, join_error};
+
+use std::io;
+use std::io::BufReader;
+
+use tokio::task;
+
+
+fn xml_error(e: quick_xml::DeError) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+impl Body {
+	/// Creates a new Body from a serializeable object as xml, the name of
+	/// the type is used as the root element.
+	#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+	pub fn serialize_xml<S>(value: &S) -> io::Result<Self>
+	where S: serde::Serialize + ?Sized {
+		quick_xml::se::to_string(value)
+			.map(|v| v.into())
+			.map_err(xml_error)
+	}
+
+	/// Converts the Body from xml into a deserializeable type.
+	#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+	pub async fn deserialize_xml<D>(self) -> io::Result<D>
+	where D: serde::de::DeserializeOwned + Send + 'static {
+		let reader = self.into_sync_reader();
+		if reader.needs_spawn_blocking() {
+			task::spawn_blocking(|| {
+				quick_xml::de::from_reader(BufReader::new(reader))
+			}).await
+				.map_err(join_error)?
+				.map_err(xml_error)
+		} else {
+			quick_xml::de::from_reader(BufReader::new(reader))
+				.map_err(xml_error)
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::{Request, Response};
+	use crate::header::{test_request_header, ContentType, Mime, Method};
+
+	use serde::{Serialize, Deserialize};
+
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Item {
+		name: String,
+		tags: Vec<String>
+	}
+
+	fn item() -> Item {
+		Item {
+			name: "a <b> & c".into(),
+			tags: vec!["x".into(), "y".into()]
+		}
+	}
+
+	#[tokio::test]
+	async fn test_body_roundtrip() {
+
+		let body = Body::serialize_xml(&item()).unwrap();
+		let xml = body.into_string().await.unwrap();
+		assert_eq!(
+			xml,
+			"<Item><name>a &lt;b&gt; &amp; c</name><tags>x</tags><tags>y</tags>\
+			</Item>"
+		);
+
+		let parsed: Item = Body::from(xml).deserialize_xml().await.unwrap();
+		assert_eq!(parsed, item());
+
+		// streaming bodies are parsed in spawn_blocking
+		let reader = Body::serialize_xml(&item()).unwrap().into_async_reader();
+		let parsed: Item = Body::from_async_reader(reader)
+			.deserialize_xml().await.unwrap();
+		assert_eq!(parsed, item());
+
+		let e = Body::from("<Item>").deserialize_xml::<Item>().await
+			.unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+	}
+
+	#[tokio::test]
+	async fn test_response_request_roundtrip() {
+
+		let resp = Response::builder().xml(&item()).unwrap().build();
+		assert_eq!(resp.header.content_type, ContentType::from(Mime::XML));
+
+		let content_type = resp.header.content_type.as_str();
+		let header = test_request_header(
+			Method::POST,
+			"/",
+			&[("content-type", content_type)]
+		);
+		let mut req = Request::new(header, resp.body);
+		assert_eq!(req.deserialize_xml::<Item>().await.unwrap(), item());
+
+		// the content type is checked
+		let header = test_request_header(
+			Method::POST,
+			"/",
+			&[("content-type", "application/json")]
+		);
+		let body = Body::serialize_xml(&item()).unwrap();
+		let mut req = Request::new(header, body);
+		assert!(req.deserialize_xml::<Item>().await.is_err());
+
+	}
+}
\ No newline at end of file
@@ -0,0 +1,181 @@
+//! Double submit csrf tokens.
+//!
+//! The server sets a random token as a cookie, the client then needs to send
+//! the same token in a header (or a form field). Since another site cannot
+//! read the cookie it cannot send the correct token.
+
+use crate::header::{RequestHeader, Method, SetCookie};
+use crate::header::cookie::SameSite;
+
+use std::fmt;
+
+
+/// The default name of the cookie containing the token.
+pub const COOKIE_NAME: &str = "csrf-token";
+
+/// The default name of the header the client sends the token in.
+pub const HEADER_NAME: &str = "x-csrf-token";
+
+/// A csrf token.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CsrfToken(String);
+
+impl CsrfToken {
+	/// Generates a new random token with 244 bits of randomness.
+	#[cfg(feature = "uuid")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+	pub fn generate() -> Self {
+		let a = uuid::Uuid::new_v4().simple();
+		let b = uuid::Uuid::new_v4().simple();
+		Self(format!("{a}{b}"))
+	}
+
+	/// Creates a token from a random string which was generated elsewhere.
+	///
+	/// ## Panics
+	/// If the token is empty or contains characters which are not allowed in
+	/// a cookie.
+	pub fn from_string(token: impl Into<String>) -> Self {
+		let token = token.into();
+		assert!(
+			!token.is_empty() && token.bytes().all(is_cookie_byte),
+			"invalid csrf token"
+		);
+
+		Self(token)
+	}
+
+	/// Returns the token.
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// Returns a cookie named `COOKIE_NAME` containing the token.
+	///
+	/// The cookie is `Secure`, `SameSite=Strict` and valid for the whole
+	/// site. It is not `HttpOnly` since javascript needs to read it to send
+	/// it in the header.
+	pub fn set_cookie(&self) -> SetCookie {
+		let mut cookie = SetCookie::new(COOKIE_NAME, self.0.as_str());
+		cookie.path = Some("/".into());
+		cookie.secure = true;
+		cookie.same_site = Some(SameSite::Strict);
+		cookie
+	}
+}
+
+/// The token is not printed to avoid leaking it into logs.
+impl fmt::Debug for CsrfToken {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("CsrfToken(***)")
+	}
+}
+
+/// The result of validating a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CsrfOutcome {
+	/// The submitted token matches the cookie.
+	Valid,
+	/// The method is safe (`GET`, `HEAD`, `OPTIONS` or `TRACE`) and does
+	/// not need a token.
+	Exempt,
+	MissingCookie,
+	MissingToken,
+	Mismatch
+}
+
+impl CsrfOutcome {
+	/// Returns true if the request should be processed.
+	pub fn is_ok(&self) -> bool {
+		matches!(self, Self::Valid | Self::Exempt)
+	}
+}
+
+/// Validates the token of the `HEADER_NAME` header against the cookie.
+pub fn validate(header: &RequestHeader) -> CsrfOutcome {
+	validate_with(header, header.value(HEADER_NAME))
+}
+
+/// Validates a token which was submitted in another way, for example in a
+/// form field, against the cookie.
+///
+/// The tokens are compared in constant time.
+pub fn validate_with(
+	header: &RequestHeader,
+	submitted: Option<&str>
+) -> CsrfOutcome {
+	if matches!(
+		*header.method(),
+		Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+	) {
+		return CsrfOutcome::Exempt
+	}
+
+	let cookie = header.cookie(COOKIE_NAME).filter(|c| !c.is_empty());
+	let Some(cookie) = cookie else {
+		return CsrfOutcome::MissingCookie
+	};
+
+	let submitted = submitted.map(str::trim).filter(|s| !s.is_empty());
+	let Some(submitted) = submitted else {
+		return CsrfOutcome::MissingToken
+	};
+
+	match constant_time_eq(cookie.as_bytes(), submitted.as_bytes()) {
+		true => CsrfOutcome::Valid,
+		false => CsrfOutcome::Mismatch
+	}
+}
+
+/// Only the length can be learned from the time it takes.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+	if a.len() != b.len() {
+		return false
+	}
+
+	a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn is_cookie_byte(b: u8) -> bool {
+	b.is_ascii_graphic() && !b"\",;\\".contains(&b)
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::test_request_header;
+
+	#[test]
+	fn test_validate() {
+
+		let token = CsrfToken::from_string("abc123");
+		assert_eq!(
+			token.set_cookie().to_string(),
+			"csrf-token=abc123; Path=/; Secure; SameSite=Strict"
+		);
+
+		let cookie = ("cookie", "session=1; csrf-token=abc123");
+		let get = test_request_header(Method::GET, "/", &[]);
+		assert_eq!(validate(&get), CsrfOutcome::Exempt);
+		let post = |token| {
+			let values = [cookie, (HEADER_NAME, token)];
+			test_request_header(Method::POST, "/", &values)
+		};
+		assert_eq!(validate(&post("abc123")), CsrfOutcome::Valid);
+		assert_eq!(validate(&post("abc124")), CsrfOutcome::Mismatch);
+		assert_eq!(
+			validate(&test_request_header(Method::POST, "/", &[cookie])),
+			CsrfOutcome::MissingToken
+		);
+		assert_eq!(
+			validate_with(
+				&test_request_header(Method::DELETE, "/", &[]),
+				Some("abc123")
+			),
+			CsrfOutcome::MissingCookie
+		);
+
+	}
+
+}
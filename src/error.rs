@@ -0,0 +1,195 @@
+//! A typed error which can be converted from and into `io::Error`.
+
+use crate::header::StatusCode;
+
+use std::{io, fmt};
+
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// An error returned by this crate.
+///
+/// Most apis return an `io::Error` for compatibility, convert it with
+/// `Error::from` to find out what went wrong.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+	/// An io error which does not fit into any other variant.
+	Io(io::Error),
+	/// A size limit was reached.
+	SizeLimit,
+	/// A timeout was reached.
+	Timeout,
+	/// A header is missing or not valid.
+	InvalidHeader(BoxError),
+	/// Data could not be parsed.
+	Parse(BoxError),
+	/// Data could not be serialized or deserialized.
+	Serde(BoxError)
+}
+
+impl Error {
+	/// Creates an `InvalidHeader` error from a message or an error.
+	pub fn invalid_header(e: impl Into<BoxError>) -> Self {
+		Self::InvalidHeader(e.into())
+	}
+
+	/// Creates a `Parse` error from a message or an error.
+	pub fn parse(e: impl Into<BoxError>) -> Self {
+		Self::Parse(e.into())
+	}
+
+	/// Creates a `Serde` error from a message or an error.
+	pub fn serde(e: impl Into<BoxError>) -> Self {
+		Self::Serde(e.into())
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "io error {e}"),
+			Self::SizeLimit => f.write_str("size limit reached"),
+			Self::Timeout => f.write_str("timed out"),
+			Self::InvalidHeader(e) => write!(f, "invalid header {e}"),
+			Self::Parse(e) => write!(f, "failed to parse {e}"),
+			Self::Serde(e) => write!(f, "serde error {e}")
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Self::Io(e) => Some(e),
+			Self::InvalidHeader(e) | Self::Parse(e) | Self::Serde(e) => {
+				Some(&**e)
+			},
+			_ => None
+		}
+	}
+}
+
+/// Size limit and timeout errors are detected by their kind or inner error,
+/// `InvalidData` errors are treated as parse errors, unless they contain a
+/// serde error.
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		if e.get_ref().is_some_and(|i| i.is::<SizeLimitReached>()) {
+			return Self::SizeLimit
+		}
+
+		match e.kind() {
+			io::ErrorKind::TimedOut => Self::Timeout,
+			io::ErrorKind::InvalidData => {
+				let Some(inner) = e.into_inner() else {
+					return Self::Parse("invalid data".into())
+				};
+
+				#[cfg(feature = "json")]
+				if inner.is::<serde_json::Error>() ||
+					inner.is::<crate::body::JsonError>()
+				{
+					return Self::Serde(inner)
+				}
+
+				#[cfg(feature = "simd-json")]
+				if inner.is::<simd_json::Error>() {
+					return Self::Serde(inner)
+				}
+
+				Self::Parse(inner)
+			},
+			_ => Self::Io(e)
+		}
+	}
+}
+
+/// Converting back into an `Error` returns the same variant, except for
+/// `InvalidHeader` which becomes `Io`.
+impl From<Error> for io::Error {
+	fn from(e: Error) -> Self {
+		match e {
+			Error::Io(e) => e,
+			Error::SizeLimit => size_limit_reached("size limit reached"),
+			Error::Timeout => {
+				io::Error::new(io::ErrorKind::TimedOut, "timed out")
+			},
+			Error::InvalidHeader(e) => {
+				io::Error::new(io::ErrorKind::InvalidInput, e)
+			},
+			Error::Parse(e) | Error::Serde(e) => {
+				io::Error::new(io::ErrorKind::InvalidData, e)
+			}
+		}
+	}
+}
+
+/// Returns the status code a server should respond with.
+impl From<&Error> for StatusCode {
+	fn from(e: &Error) -> Self {
+		match e {
+			Error::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+			Error::SizeLimit => StatusCode::PAYLOAD_TOO_LARGE,
+			Error::Timeout => StatusCode::REQUEST_TIMEOUT,
+			Error::InvalidHeader(_) | Error::Parse(_) | Error::Serde(_) => {
+				StatusCode::BAD_REQUEST
+			}
+		}
+	}
+}
+
+/// The inner error of an `io::Error` returned if a size limit was reached.
+#[derive(Debug)]
+pub(crate) struct SizeLimitReached(&'static str);
+
+impl fmt::Display for SizeLimitReached {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.0)
+	}
+}
+
+impl std::error::Error for SizeLimitReached {}
+
+pub(crate) fn size_limit_reached(msg: &'static str) -> io::Error {
+	io::Error::new(io::ErrorKind::UnexpectedEof, SizeLimitReached(msg))
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_io_roundtrip() {
+
+		let e = Error::from(size_limit_reached("to big"));
+		assert!(matches!(e, Error::SizeLimit));
+		assert!(matches!(
+			Error::from(io::Error::from(e)),
+			Error::SizeLimit
+		));
+
+		let e = io::Error::new(io::ErrorKind::TimedOut, "slow");
+		assert!(matches!(Error::from(e), Error::Timeout));
+
+		let e = io::Error::new(io::ErrorKind::InvalidData, "not utf8");
+		assert!(matches!(Error::from(e), Error::Parse(_)));
+
+		let e = Error::invalid_header("missing host");
+		assert!(matches!(
+			Error::from(io::Error::from(e)),
+			Error::Io(_)
+		));
+
+		let e = io::Error::new(io::ErrorKind::BrokenPipe, "closed");
+		assert!(matches!(Error::from(e), Error::Io(_)));
+
+		assert_eq!(
+			StatusCode::from(&Error::SizeLimit),
+			StatusCode::PAYLOAD_TOO_LARGE
+		);
+
+	}
+
+}
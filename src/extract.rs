@@ -0,0 +1,352 @@
+//! Traits to extract typed values from a request, so frameworks building on
+//! this crate can share extractors.
+
+use crate::header::{self, RequestHeader, Method, Uri, Cookies, HeaderValues};
+use crate::{Request, Body};
+
+use std::future::{self, Future};
+use std::convert::Infallible;
+
+
+/// A type which can be extracted from a `RequestHeader`.
+pub trait FromRequestHeader: Sized {
+	type Error;
+
+	/// Extracts the value from the header.
+	/// 
+	/// ## Errors
+	/// If the value is missing or invalid.
+	fn from_request_header(header: &RequestHeader) -> Result<Self, Self::Error>;
+}
+
+/// A type which can be extracted from a `Request`, possibly consuming the
+/// body.
+/// 
+/// Implemented for every `Send` type implementing `FromRequestHeader`.
+pub trait FromRequest: Sized {
+	type Error;
+
+	/// Extracts the value from the request, an extractor which needs the
+	/// body takes it with `Request::take_body`, so only one of them can be
+	/// used per request.
+	/// 
+	/// ## Errors
+	/// If the value is missing or invalid, or the body could not be read.
+	fn from_request(
+		req: &mut Request
+	) -> impl Future<Output=Result<Self, Self::Error>> + Send;
+}
+
+impl<T> FromRequest for T
+where
+	T: FromRequestHeader + Send,
+	T::Error: Send
+{
+	type Error = T::Error;
+
+	fn from_request(
+		req: &mut Request
+	) -> impl Future<Output=Result<Self, Self::Error>> + Send {
+		future::ready(T::from_request_header(req.header()))
+	}
+}
+
+impl FromRequestHeader for Method {
+	type Error = Infallible;
+
+	fn from_request_header(header: &RequestHeader) -> Result<Self, Infallible> {
+		Ok(header.method().clone())
+	}
+}
+
+impl FromRequestHeader for Uri {
+	type Error = Infallible;
+
+	fn from_request_header(header: &RequestHeader) -> Result<Self, Infallible> {
+		Ok(header.uri().clone())
+	}
+}
+
+impl FromRequestHeader for header::Query {
+	type Error = Infallible;
+
+	fn from_request_header(header: &RequestHeader) -> Result<Self, Infallible> {
+		Ok(header::Query::from_uri(header.uri()))
+	}
+}
+
+impl FromRequestHeader for Cookies {
+	type Error = Infallible;
+
+	fn from_request_header(header: &RequestHeader) -> Result<Self, Infallible> {
+		Ok(Cookies::parse(header.value("cookie").unwrap_or("")))
+	}
+}
+
+impl FromRequestHeader for HeaderValues {
+	type Error = Infallible;
+
+	fn from_request_header(header: &RequestHeader) -> Result<Self, Infallible> {
+		Ok(header.values().clone())
+	}
+}
+
+/// Takes the body, leaving an empty one.
+impl FromRequest for Body {
+	type Error = Infallible;
+
+	async fn from_request(req: &mut Request) -> Result<Self, Infallible> {
+		Ok(req.take_body())
+	}
+}
+
+/// Deserializes the json body, see `Request::deserialize`.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "json")]
+impl<T> FromRequest for Json<T>
+where T: serde::de::DeserializeOwned + Send + 'static {
+	type Error = crate::request::DeserializeError;
+
+	async fn from_request(
+		req: &mut Request
+	) -> Result<Self, crate::request::DeserializeError> {
+		req.deserialize().await.map(Json)
+	}
+}
+
+/// Deserializes the query string of the uri, values like numbers or bools are
+/// parsed from their string.
+/// 
+/// A missing query string is treated like an empty one. To access the raw
+/// pairs use `header::Query`.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Query<T>(pub T);
+
+#[cfg(feature = "serde")]
+impl<T> FromRequestHeader for Query<T>
+where T: serde::de::DeserializeOwned {
+	type Error = QueryError;
+
+	fn from_request_header(header: &RequestHeader) -> Result<Self, QueryError> {
+		let query = header.uri().query().unwrap_or("");
+		let pairs = form_urlencoded::parse(query.as_bytes())
+			.map(|(k, v)| (k, query_de::Part(v)));
+
+		let de = serde::de::value::MapDeserializer::new(pairs);
+		T::deserialize(de).map(Query)
+	}
+}
+
+/// The error returned if the query string could not be deserialized, see
+/// `Query`.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+	message: String
+}
+
+#[cfg(feature = "serde")]
+impl std::fmt::Display for QueryError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "invalid query {}", self.message)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for QueryError {}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for QueryError {
+	fn custom<T: std::fmt::Display>(msg: T) -> Self {
+		Self { message: msg.to_string() }
+	}
+}
+
+#[cfg(feature = "serde")]
+mod query_de {
+	use super::QueryError;
+
+	use std::borrow::Cow;
+
+	use serde::de::{self, Visitor, IntoDeserializer, Error};
+
+	/// A decoded query value, which parses primitives from the string.
+	pub struct Part<'a>(pub Cow<'a, str>);
+
+	impl<'de> IntoDeserializer<'de, QueryError> for Part<'_> {
+		type Deserializer = Self;
+
+		fn into_deserializer(self) -> Self {
+			self
+		}
+	}
+
+	macro_rules! deserialize_parsed {
+		($($method:ident => $visit:ident),*) => ($(
+			fn $method<V>(self, visitor: V) -> Result<V::Value, QueryError>
+			where V: Visitor<'de> {
+				visitor.$visit(self.0.parse().map_err(QueryError::custom)?)
+			}
+		)*)
+	}
+
+	impl<'de> de::Deserializer<'de> for Part<'_> {
+		type Error = QueryError;
+
+		fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, QueryError>
+		where V: Visitor<'de> {
+			match self.0 {
+				Cow::Borrowed(s) => visitor.visit_str(s),
+				Cow::Owned(s) => visitor.visit_string(s)
+			}
+		}
+
+		fn deserialize_option<V>(
+			self,
+			visitor: V
+		) -> Result<V::Value, QueryError>
+		where V: Visitor<'de> {
+			visitor.visit_some(self)
+		}
+
+		fn deserialize_enum<V>(
+			self,
+			_name: &'static str,
+			_variants: &'static [&'static str],
+			visitor: V
+		) -> Result<V::Value, QueryError>
+		where V: Visitor<'de> {
+			visitor.visit_enum(self.0.into_owned().into_deserializer())
+		}
+
+		deserialize_parsed! {
+			deserialize_bool => visit_bool,
+			deserialize_i8 => visit_i8,
+			deserialize_i16 => visit_i16,
+			deserialize_i32 => visit_i32,
+			deserialize_i64 => visit_i64,
+			deserialize_u8 => visit_u8,
+			deserialize_u16 => visit_u16,
+			deserialize_u32 => visit_u32,
+			deserialize_u64 => visit_u64,
+			deserialize_f32 => visit_f32,
+			deserialize_f64 => visit_f64
+		}
+
+		serde::forward_to_deserialize_any! {
+			char str string bytes byte_buf unit unit_struct newtype_struct
+			seq tuple tuple_struct map struct identifier ignored_any
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::header::test_request_header;
+
+
+	fn request() -> Request {
+		let header = test_request_header(
+			Method::POST,
+			"/items?page=2&q=a+b",
+			&[
+				("cookie", "session=abc; theme=dark"),
+				("content-type", "application/json")
+			]
+		);
+
+		Request::new(header, Body::from(r#"{"id":1}"#))
+	}
+
+	#[tokio::test]
+	async fn test_header_extractors() {
+
+		let mut req = request();
+
+		let method = Method::from_request(&mut req).await.unwrap();
+		assert_eq!(method, Method::POST);
+
+		let uri = Uri::from_request_header(req.header()).unwrap();
+		assert_eq!(uri.path(), "/items");
+
+		let query = header::Query::from_request_header(req.header()).unwrap();
+		assert_eq!(query.get("page"), Some("2"));
+		assert_eq!(query.get("q"), Some("a b"));
+
+		let cookies = Cookies::from_request_header(req.header()).unwrap();
+		assert_eq!(cookies.get("theme"), Some("dark"));
+
+		let values = HeaderValues::from_request_header(req.header()).unwrap();
+		assert_eq!(values.get_str("cookie"), Some("session=abc; theme=dark"));
+
+		// header extractors do not consume the body
+		let body = Body::from_request(&mut req).await.unwrap();
+		assert_eq!(body.into_string().await.unwrap(), r#"{"id":1}"#);
+		let body = Body::from_request(&mut req).await.unwrap();
+		assert!(body.is_empty());
+
+	}
+
+	#[cfg(feature = "json")]
+	#[tokio::test]
+	async fn test_json() {
+
+		#[derive(Debug, serde::Deserialize)]
+		struct Item {
+			id: u32
+		}
+
+		let Json(item) = Json::<Item>::from_request(&mut request()).await
+			.unwrap();
+		assert_eq!(item.id, 1);
+
+		let mut req = request();
+		req.header.values.insert("content-type", "text/plain");
+		assert!(Json::<Item>::from_request(&mut req).await.is_err());
+
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn test_typed_query() {
+
+		#[derive(Debug, PartialEq, serde::Deserialize)]
+		struct Search {
+			q: String,
+			page: u32,
+			exact: Option<bool>
+		}
+
+		let req = request();
+		let Query(search) = Query::<Search>::from_request_header(req.header())
+			.unwrap();
+		assert_eq!(search, Search { q: "a b".into(), page: 2, exact: None });
+
+		let header = test_request_header(
+			Method::GET,
+			"/?q=%F0%9F%9A%80&page=1&exact=true",
+			&[]
+		);
+		let Query(search) = Query::<Search>::from_request_header(&header)
+			.unwrap();
+		assert_eq!(search, Search { q: "🚀".into(), page: 1, exact: Some(true) });
+
+		let header = test_request_header(Method::GET, "/?q=a&page=x", &[]);
+		let e = Query::<Search>::from_request_header(&header).unwrap_err();
+		assert_eq!(e.to_string(), "invalid query invalid digit found in string");
+
+		let header = test_request_header(Method::GET, "/", &[]);
+		assert!(Query::<Search>::from_request_header(&header).is_err());
+
+	}
+}
\ No newline at end of file
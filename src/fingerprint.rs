@@ -0,0 +1,131 @@
+//! Stable request fingerprints, see `Request::fingerprint`.
+
+use crate::header::{RequestHeader, UriExt};
+use crate::body::Body;
+
+
+/// Decides which parts of a request are part of the fingerprint.
+///
+/// The method and the normalized uri are always included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FingerprintConfig {
+	headers: Vec<String>,
+	body: bool
+}
+
+impl FingerprintConfig {
+	/// Creates a new `FingerprintConfig` which only includes the method and
+	/// the uri.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Includes all values of a header, for example `host`, `accept` or
+	/// `authorization`.
+	pub fn header(mut self, name: &str) -> Self {
+		self.headers.push(name.to_ascii_lowercase());
+		self
+	}
+
+	/// Sets if the body should be included, this only works with buffered
+	/// bodies.
+	pub fn body(mut self, body: bool) -> Self {
+		self.body = body;
+		self
+	}
+
+	pub(crate) fn fingerprint(
+		&self,
+		header: &RequestHeader,
+		body: &Body
+	) -> Option<u64> {
+		let mut hasher = Fnv64::new();
+
+		hasher.write_field(header.method().as_str().as_bytes());
+		hasher.write_field(header.uri().normalize().to_string().as_bytes());
+
+		for name in &self.headers {
+			hasher.write_field(name.as_bytes());
+			let values = header.values().get_all(name.as_str());
+			for value in values {
+				hasher.write_field(value.as_bytes());
+			}
+			// separates the values of this header from the next name
+			hasher.write(&[0xff]);
+		}
+
+		if self.body {
+			let chunks = body.buffered()?;
+			let len: usize = chunks.iter().map(|c| c.len()).sum();
+			hasher.write(&(len as u64).to_le_bytes());
+			for chunk in chunks {
+				hasher.write(chunk);
+			}
+		}
+
+		Some(hasher.finish())
+	}
+}
+
+/// The FNV-1a 64 bit hash, which is fast and stable between restarts.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Fnv64(u64);
+
+impl Fnv64 {
+	pub fn new() -> Self {
+		Self(0xcbf29ce484222325)
+	}
+
+	pub fn write(&mut self, bytes: &[u8]) {
+		for b in bytes {
+			self.0 = (self.0 ^ *b as u64).wrapping_mul(0x100000001b3);
+		}
+	}
+
+	/// Writes the length before the bytes so fields cannot be confused.
+	fn write_field(&mut self, bytes: &[u8]) {
+		self.write(&(bytes.len() as u64).to_le_bytes());
+		self.write(bytes);
+	}
+
+	pub fn finish(&self) -> u64 {
+		self.0
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::{test_request_header, Method};
+
+	#[test]
+	fn test_fingerprint() {
+
+		let config = FingerprintConfig::new().header("Accept");
+		let print = |uri, values: &[(&'static str, &str)]| {
+			let req = test_request_header(Method::GET, uri, values);
+			config.fingerprint(&req, &Body::new()).unwrap()
+		};
+
+		let a = print("/a/./b?q=%7e", &[("accept", "text/html")]);
+		assert_eq!(a, print("/a/b?q=~", &[("accept", "text/html")]));
+		assert_ne!(a, print("/a/b?q=~", &[("accept", "application/json")]));
+		// other headers are ignored
+		assert_eq!(a, print("/a/b?q=~", &[
+			("accept", "text/html"),
+			("user-agent", "curl")
+		]));
+
+		let config = config.body(true);
+		let req = test_request_header(Method::GET, "/", &[]);
+		assert_ne!(
+			config.fingerprint(&req, &Body::from("a")),
+			config.fingerprint(&req, &Body::from("b"))
+		);
+		let streaming = Body::from_async_reader(tokio::io::empty());
+		assert_eq!(config.fingerprint(&req, &streaming), None);
+
+	}
+
+}
@@ -0,0 +1,265 @@
+//! GraphQL requests and responses as defined by the GraphQL over HTTP spec.
+
+use crate::header::{RequestHeader, Method, Query, StatusCode, ContentType};
+use crate::{Body, Request, Response};
+
+use std::{io, fmt};
+
+use serde::{Serialize, Deserialize};
+use serde_json::{Value, Map};
+
+
+/// The media type of a graphql response.
+pub const GRAPHQL_RESPONSE_JSON: &str =
+	"application/graphql-response+json; charset=utf-8";
+
+/// A graphql request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlRequest {
+	pub query: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub operation_name: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub variables: Option<Map<String, Value>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub extensions: Option<Map<String, Value>>
+}
+
+impl GraphQlRequest {
+	/// Extracts a graphql request.
+	///
+	/// `GET` requests are read from the query parameters, `POST` requests
+	/// need to have the content type `application/json` or
+	/// `application/graphql`.
+	///
+	/// The body is taken from the request.
+	pub async fn from_request(
+		req: &mut Request
+	) -> Result<Self, GraphQlRequestError> {
+		match *req.header().method() {
+			Method::GET => {
+				Self::from_query(&Query::from_uri(req.header().uri()))
+			},
+			Method::POST => Self::from_post(req).await,
+			_ => Err(GraphQlRequestError::MethodNotAllowed)
+		}
+	}
+
+	fn from_query(query: &Query) -> Result<Self, GraphQlRequestError> {
+		let map = |key| {
+			query.get(key)
+				.filter(|v| !v.is_empty())
+				.map(serde_json::from_str)
+				.transpose()
+				.map_err(|_| GraphQlRequestError::InvalidRequest)
+		};
+
+		Ok(Self {
+			query: query.get("query")
+				.ok_or(GraphQlRequestError::InvalidRequest)?
+				.to_string(),
+			operation_name: query.get("operationName")
+				.filter(|v| !v.is_empty())
+				.map(|v| v.to_string()),
+			variables: map("variables")?,
+			extensions: map("extensions")?
+		})
+	}
+
+	async fn from_post(req: &mut Request) -> Result<Self, GraphQlRequestError> {
+		let content_type = req.header().value("content-type")
+			.unwrap_or("");
+		// ignore parameters like the charset
+		let mime = content_type.split(';').next().unwrap()
+			.trim()
+			.to_ascii_lowercase();
+
+		match mime.as_str() {
+			"application/json" => {
+				req.take_body().deserialize().await
+					.map_err(GraphQlRequestError::from_io)
+			},
+			"application/graphql" => {
+				let query = req.take_body().into_string().await
+					.map_err(GraphQlRequestError::from_io)?;
+
+				Ok(Self {
+					query,
+					operation_name: None,
+					variables: None,
+					extensions: None
+				})
+			},
+			_ => Err(GraphQlRequestError::UnsupportedContentType(
+				content_type.to_string()
+			))
+		}
+	}
+}
+
+/// Returned if a graphql request could not be extracted.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GraphQlRequestError {
+	MethodNotAllowed,
+	UnsupportedContentType(String),
+	/// The query is missing or a parameter is not valid.
+	InvalidRequest,
+	Reading(io::Error)
+}
+
+impl GraphQlRequestError {
+	fn from_io(e: io::Error) -> Self {
+		match e.kind() {
+			io::ErrorKind::InvalidData => Self::InvalidRequest,
+			_ => Self::Reading(e)
+		}
+	}
+
+	/// Returns the status code which should be responded with.
+	pub fn status_code(&self) -> StatusCode {
+		match self {
+			Self::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+			Self::UnsupportedContentType(_) => {
+				StatusCode::UNSUPPORTED_MEDIA_TYPE
+			},
+			Self::InvalidRequest | Self::Reading(_) => StatusCode::BAD_REQUEST
+		}
+	}
+}
+
+impl fmt::Display for GraphQlRequestError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::MethodNotAllowed => f.write_str("method not allowed"),
+			Self::UnsupportedContentType(ct) => {
+				write!(f, "unsupported content type {ct:?}")
+			},
+			Self::InvalidRequest => f.write_str("invalid graphql request"),
+			Self::Reading(e) => write!(f, "failed to read body {e}")
+		}
+	}
+}
+
+impl std::error::Error for GraphQlRequestError {}
+
+/// A location in the graphql query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphQlLocation {
+	pub line: u32,
+	pub column: u32
+}
+
+/// An error inside a graphql response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphQlError {
+	pub message: String,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub locations: Vec<GraphQlLocation>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub path: Vec<Value>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub extensions: Option<Map<String, Value>>
+}
+
+impl GraphQlError {
+	/// Creates a new `GraphQlError` with only a message.
+	pub fn new(message: impl Into<String>) -> Self {
+		Self {
+			message: message.into(),
+			locations: vec![],
+			path: vec![],
+			extensions: None
+		}
+	}
+}
+
+/// A graphql response.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphQlResponse {
+	/// Is `None` if the request failed before execution.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub data: Option<Value>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub errors: Vec<GraphQlError>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub extensions: Option<Map<String, Value>>
+}
+
+impl GraphQlResponse {
+	/// Creates a successful response.
+	pub fn data(data: Value) -> Self {
+		Self { data: Some(data), ..Default::default() }
+	}
+
+	/// Creates a response for a request which could not be executed.
+	pub fn error(error: GraphQlError) -> Self {
+		Self { errors: vec![error], ..Default::default() }
+	}
+
+	/// Serializes the response into a `Response`.
+	///
+	/// If the client accepts `application/graphql-response+json` it is used
+	/// as the content type and a response without data gets the status
+	/// `400 Bad Request`. Else the legacy `application/json` is used with the
+	/// status `200 OK`.
+	pub fn into_response(self, req: &RequestHeader) -> io::Result<Response> {
+		let accepts_graphql = req.value("accept")
+			.map(|a| a.contains("application/graphql-response+json"))
+			.unwrap_or(false);
+
+		let (status_code, content_type) = if accepts_graphql {
+			let status_code = match self.data {
+				Some(_) => StatusCode::OK,
+				None => StatusCode::BAD_REQUEST
+			};
+
+			(status_code, ContentType::from(GRAPHQL_RESPONSE_JSON))
+		} else {
+			(StatusCode::OK, ContentType::from(crate::header::Mime::JSON))
+		};
+
+		Ok(Response::builder()
+			.status_code(status_code)
+			.content_type(content_type)
+			.body(Body::serialize(&self)?)
+			.build())
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_from_query() {
+
+		let query = Query::parse(
+			"query=%7Bme%7Bname%7D%7D&operationName=\
+			&variables=%7B%22a%22%3A1%7D"
+		);
+		let req = GraphQlRequest::from_query(&query).unwrap();
+		assert_eq!(req.query, "{me{name}}");
+		assert_eq!(req.operation_name, None);
+		assert_eq!(req.variables.unwrap()["a"], 1);
+
+		let query = Query::parse("query=a&variables=invalid");
+		assert!(GraphQlRequest::from_query(&query).is_err());
+		assert!(GraphQlRequest::from_query(&Query::new()).is_err());
+
+	}
+
+	#[test]
+	fn test_response() {
+
+		let resp = GraphQlResponse::error(GraphQlError::new("failed"));
+		assert_eq!(
+			serde_json::to_string(&resp).unwrap(),
+			r#"{"errors":[{"message":"failed"}]}"#
+		);
+
+	}
+
+}
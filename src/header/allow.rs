@@ -0,0 +1,201 @@
+//! Types related to the `Allow` http header.
+
+use super::{HeaderValue, HeaderValues, Method};
+
+use std::fmt;
+use std::str::FromStr;
+use std::convert::Infallible;
+
+
+const STANDARD: [Method; 9] = [
+	Method::GET,
+	Method::HEAD,
+	Method::POST,
+	Method::PUT,
+	Method::DELETE,
+	Method::CONNECT,
+	Method::OPTIONS,
+	Method::TRACE,
+	Method::PATCH
+];
+
+/// A set of methods which are allowed for a resource.
+///
+/// Standard methods are stored in a bitset, extension methods in a list.
+/// `HEAD` is always allowed if `GET` is allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllowedMethods {
+	bits: u16,
+	extensions: Vec<Method>
+}
+
+impl AllowedMethods {
+	/// Creates a new `AllowedMethods` which does not allow any method.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a method.
+	pub fn with(mut self, method: Method) -> Self {
+		self.insert(method);
+		self
+	}
+
+	/// Adds a method.
+	pub fn insert(&mut self, method: Method) {
+		match bit(&method) {
+			Some(bit) => self.bits |= bit,
+			None if !self.extensions.contains(&method) => {
+				self.extensions.push(method);
+			},
+			None => {}
+		}
+	}
+
+	/// Removes a method.
+	pub fn remove(&mut self, method: &Method) {
+		match bit(method) {
+			Some(bit) => self.bits &= !bit,
+			None => self.extensions.retain(|m| m != method)
+		}
+	}
+
+	/// Returns true if the method is allowed.
+	pub fn contains(&self, method: &Method) -> bool {
+		if *method == Method::HEAD {
+			return self.bits & (bit_of(0) | bit_of(1)) != 0
+		}
+
+		match bit(method) {
+			Some(bit) => self.bits & bit != 0,
+			None => self.extensions.contains(method)
+		}
+	}
+
+	/// Returns true if a request with this method should be answered with
+	/// `405 Method Not Allowed`.
+	pub fn is_not_allowed(&self, method: &Method) -> bool {
+		!self.contains(method)
+	}
+
+	/// Returns true if no method is allowed.
+	pub fn is_empty(&self) -> bool {
+		self.bits == 0 && self.extensions.is_empty()
+	}
+
+	/// Returns an iterator over all allowed methods, standard methods come
+	/// first.
+	pub fn iter(&self) -> impl Iterator<Item=&Method> {
+		STANDARD.iter()
+			.filter(|m| self.contains(m))
+			.chain(self.extensions.iter())
+	}
+
+	/// Adds the allowed methods to the `access-control-allow-methods`
+	/// header, keeping methods which are already present.
+	pub fn merge_cors(&self, values: &mut HeaderValues) {
+		let mut merged: Self = values.get_str("access-control-allow-methods")
+			.map(|v| v.parse().unwrap())
+			.unwrap_or_default();
+
+		for method in self.iter() {
+			merged.insert(method.clone());
+		}
+
+		values.insert("access-control-allow-methods", merged);
+	}
+}
+
+impl fmt::Display for AllowedMethods {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, method) in self.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			f.write_str(method.as_str())?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Parses a comma separated list of methods, invalid methods are ignored.
+impl FromStr for AllowedMethods {
+	type Err = Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Infallible> {
+		Ok(s.split(',')
+			.filter_map(|m| m.trim().parse().ok())
+			.collect())
+	}
+}
+
+impl FromIterator<Method> for AllowedMethods {
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item=Method> {
+		let mut allowed = Self::new();
+		for method in iter {
+			allowed.insert(method);
+		}
+
+		allowed
+	}
+}
+
+impl From<&AllowedMethods> for HeaderValue {
+	fn from(allowed: &AllowedMethods) -> Self {
+		// methods are tokens which are valid header values
+		HeaderValue::from_str(&allowed.to_string()).unwrap()
+	}
+}
+
+impl From<AllowedMethods> for HeaderValue {
+	fn from(allowed: AllowedMethods) -> Self {
+		(&allowed).into()
+	}
+}
+
+const fn bit_of(idx: usize) -> u16 {
+	1 << idx
+}
+
+fn bit(method: &Method) -> Option<u16> {
+	STANDARD.iter()
+		.position(|m| m == method)
+		.map(bit_of)
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_allowed_methods() {
+
+		let ext = Method::from_bytes(b"PURGE").unwrap();
+		let allowed = AllowedMethods::new()
+			.with(Method::POST)
+			.with(Method::GET)
+			.with(ext.clone());
+
+		assert!(allowed.contains(&Method::HEAD));
+		assert!(allowed.contains(&ext));
+		assert!(allowed.is_not_allowed(&Method::PUT));
+		assert_eq!(allowed.to_string(), "GET, HEAD, POST, PURGE");
+		assert_eq!(
+			allowed.to_string().parse::<AllowedMethods>().unwrap(),
+			allowed.clone().with(Method::HEAD)
+		);
+
+		let mut values = HeaderValues::new();
+		values.insert("access-control-allow-methods", "PUT, GET");
+		allowed.merge_cors(&mut values);
+		assert_eq!(
+			values.get_str("access-control-allow-methods").unwrap(),
+			"GET, HEAD, POST, PUT, PURGE"
+		);
+
+	}
+
+}
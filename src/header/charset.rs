@@ -0,0 +1,169 @@
+//! The `charset` parameter of the `Content-Type` http header.
+
+use std::fmt;
+use std::str::FromStr;
+
+
+/// A character encoding which can be decoded into a `String`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Charset {
+	#[default]
+	Utf8,
+	/// Only bytes below 0x80 are valid.
+	Ascii,
+	/// `iso-8859-1`, every byte maps to the unicode code point with the same
+	/// value.
+	Latin1,
+	Utf16Le,
+	Utf16Be
+}
+
+impl Charset {
+	/// Returns the lowercase name.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Utf8 => "utf-8",
+			Self::Ascii => "us-ascii",
+			Self::Latin1 => "iso-8859-1",
+			Self::Utf16Le => "utf-16le",
+			Self::Utf16Be => "utf-16be"
+		}
+	}
+
+	/// Returns the charset parameter of a `Content-Type` value like
+	/// `text/plain; charset=iso-8859-1`.
+	///
+	/// Returns None if there is no charset parameter or it is not known.
+	pub fn from_content_type(value: &str) -> Option<Self> {
+		value.split(';')
+			.skip(1)
+			.filter_map(|param| param.split_once('='))
+			.find(|(k, _)| k.trim().eq_ignore_ascii_case("charset"))
+			.and_then(|(_, v)| v.trim().trim_matches('"').parse().ok())
+	}
+
+	/// Detects a byte order mark and returns the charset it belongs to and
+	/// the bytes without it.
+	pub fn strip_bom(bytes: &[u8]) -> (Option<Self>, &[u8]) {
+		let boms: [(&[u8], Self); 3] = [
+			(b"\xef\xbb\xbf", Self::Utf8),
+			(b"\xff\xfe", Self::Utf16Le),
+			(b"\xfe\xff", Self::Utf16Be)
+		];
+
+		for (bom, charset) in boms {
+			if let Some(rest) = bytes.strip_prefix(bom) {
+				return (Some(charset), rest)
+			}
+		}
+
+		(None, bytes)
+	}
+
+	/// Decodes the bytes into a `String`.
+	///
+	/// ## Errors
+	/// If the bytes are not valid in this charset.
+	pub fn decode(&self, bytes: &[u8]) -> Result<String, DecodeError> {
+		match self {
+			Self::Utf8 => {
+				String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError)
+			},
+			Self::Ascii if !bytes.is_ascii() => Err(DecodeError),
+			Self::Ascii | Self::Latin1 => {
+				Ok(bytes.iter().map(|b| *b as char).collect())
+			},
+			Self::Utf16Le | Self::Utf16Be => {
+				if !bytes.len().is_multiple_of(2) {
+					return Err(DecodeError)
+				}
+
+				let units = bytes.chunks_exact(2).map(|c| match self {
+					Self::Utf16Le => u16::from_le_bytes([c[0], c[1]]),
+					_ => u16::from_be_bytes([c[0], c[1]])
+				});
+
+				char::decode_utf16(units)
+					.collect::<Result<_, _>>()
+					.map_err(|_| DecodeError)
+			}
+		}
+	}
+}
+
+impl fmt::Display for Charset {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// Parses a charset name ignoring the case, common aliases are accepted.
+impl FromStr for Charset {
+	type Err = UnknownCharset;
+
+	fn from_str(s: &str) -> Result<Self, UnknownCharset> {
+		match s.trim().to_ascii_lowercase().as_str() {
+			"utf-8" | "utf8" => Ok(Self::Utf8),
+			"us-ascii" | "ascii" => Ok(Self::Ascii),
+			"iso-8859-1" | "iso8859-1" | "latin1" | "l1" => Ok(Self::Latin1),
+			"utf-16le" => Ok(Self::Utf16Le),
+			"utf-16be" => Ok(Self::Utf16Be),
+			_ => Err(UnknownCharset)
+		}
+	}
+}
+
+/// Returned if a charset is not known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownCharset;
+
+impl fmt::Display for UnknownCharset {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("unknown charset")
+	}
+}
+
+impl std::error::Error for UnknownCharset {}
+
+/// Returned if bytes are not valid in a charset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError;
+
+impl fmt::Display for DecodeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("bytes are not valid in the charset")
+	}
+}
+
+impl std::error::Error for DecodeError {}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decode() {
+
+		assert_eq!(
+			Charset::from_content_type("text/plain; Charset=\"ISO-8859-1\""),
+			Some(Charset::Latin1)
+		);
+		assert_eq!(Charset::from_content_type("text/plain"), None);
+
+		assert_eq!(Charset::Latin1.decode(b"caf\xe9").unwrap(), "café");
+		assert_eq!(Charset::Utf8.decode(b"caf\xe9"), Err(DecodeError));
+		assert_eq!(Charset::Ascii.decode(b"caf\xe9"), Err(DecodeError));
+		assert_eq!(Charset::Utf16Le.decode(b"h\0i\0").unwrap(), "hi");
+		assert_eq!(Charset::Utf16Be.decode(b"\0h\0i").unwrap(), "hi");
+
+		assert_eq!(
+			Charset::strip_bom(b"\xff\xfeh\0"),
+			(Some(Charset::Utf16Le), &b"h\0"[..])
+		);
+		assert_eq!(Charset::strip_bom(b"{}"), (None, &b"{}"[..]));
+
+	}
+
+}
@@ -0,0 +1,285 @@
+//! Types related to http client hints (RFC 8942), the `Accept-CH` response
+//! header and the `Sec-CH-UA*`, `Viewport-Width` and `DPR` request headers.
+
+use super::RequestHeader;
+
+use std::fmt;
+use std::str::FromStr;
+
+
+/// A client hint which can be requested with `Accept-CH`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ClientHint {
+	Ua,
+	UaMobile,
+	UaPlatform,
+	UaPlatformVersion,
+	UaArch,
+	UaModel,
+	UaFullVersionList,
+	ViewportWidth,
+	Dpr,
+	Width,
+	DeviceMemory
+}
+
+impl ClientHint {
+	/// Returns the lowercase name of the request header.
+	pub fn header_name(&self) -> &'static str {
+		match self {
+			Self::Ua => "sec-ch-ua",
+			Self::UaMobile => "sec-ch-ua-mobile",
+			Self::UaPlatform => "sec-ch-ua-platform",
+			Self::UaPlatformVersion => "sec-ch-ua-platform-version",
+			Self::UaArch => "sec-ch-ua-arch",
+			Self::UaModel => "sec-ch-ua-model",
+			Self::UaFullVersionList => "sec-ch-ua-full-version-list",
+			Self::ViewportWidth => "sec-ch-viewport-width",
+			Self::Dpr => "sec-ch-dpr",
+			Self::Width => "sec-ch-width",
+			Self::DeviceMemory => "sec-ch-device-memory"
+		}
+	}
+
+	/// Formats a list of hints as an `Accept-CH` value.
+	pub fn format_list(hints: &[ClientHint]) -> String {
+		hints.iter()
+			.map(|h| h.header_name())
+			.collect::<Vec<_>>()
+			.join(", ")
+	}
+
+	const ALL: [Self; 11] = [
+		Self::Ua, Self::UaMobile, Self::UaPlatform, Self::UaPlatformVersion,
+		Self::UaArch, Self::UaModel, Self::UaFullVersionList,
+		Self::ViewportWidth, Self::Dpr, Self::Width, Self::DeviceMemory
+	];
+}
+
+impl fmt::Display for ClientHint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.header_name())
+	}
+}
+
+/// Parses the header name ignoring the case, the legacy names without
+/// `sec-ch-` (for example `DPR`) are accepted as well.
+impl FromStr for ClientHint {
+	type Err = UnknownClientHint;
+
+	fn from_str(s: &str) -> Result<Self, UnknownClientHint> {
+		let s = s.trim().to_ascii_lowercase();
+		let name = match s.as_str() {
+			"viewport-width" | "dpr" | "width" | "device-memory" => {
+				format!("sec-ch-{s}")
+			},
+			_ => s
+		};
+
+		Self::ALL.into_iter()
+			.find(|h| h.header_name() == name)
+			.ok_or(UnknownClientHint)
+	}
+}
+
+/// Returned if a client hint is not known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownClientHint;
+
+impl fmt::Display for UnknownClientHint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("unknown client hint")
+	}
+}
+
+impl std::error::Error for UnknownClientHint {}
+
+/// A brand and it's version from the `sec-ch-ua` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Brand {
+	pub brand: String,
+	pub version: String
+}
+
+/// The client hints sent with a request, see `RequestHeader::client_hints`.
+///
+/// Values which are missing or invalid are returned as None.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientHints<'a> {
+	header: &'a RequestHeader
+}
+
+impl<'a> ClientHints<'a> {
+	pub(super) fn new(header: &'a RequestHeader) -> Self {
+		Self { header }
+	}
+
+	/// Returns the brands of the `sec-ch-ua` header.
+	pub fn ua(&self) -> Option<Vec<Brand>> {
+		self.brands(ClientHint::Ua)
+	}
+
+	/// Returns the brands with the full version of the
+	/// `sec-ch-ua-full-version-list` header.
+	pub fn full_version_list(&self) -> Option<Vec<Brand>> {
+		self.brands(ClientHint::UaFullVersionList)
+	}
+
+	/// Returns true if the client prefers a mobile experience.
+	pub fn mobile(&self) -> Option<bool> {
+		match self.value(ClientHint::UaMobile)? {
+			"?1" => Some(true),
+			"?0" => Some(false),
+			_ => None
+		}
+	}
+
+	/// Returns the platform, for example `Windows`.
+	pub fn platform(&self) -> Option<String> {
+		self.string(ClientHint::UaPlatform)
+	}
+
+	pub fn platform_version(&self) -> Option<String> {
+		self.string(ClientHint::UaPlatformVersion)
+	}
+
+	pub fn arch(&self) -> Option<String> {
+		self.string(ClientHint::UaArch)
+	}
+
+	pub fn model(&self) -> Option<String> {
+		self.string(ClientHint::UaModel)
+	}
+
+	/// Returns the width of the layout viewport in css pixels.
+	pub fn viewport_width(&self) -> Option<u32> {
+		self.number(ClientHint::ViewportWidth)
+	}
+
+	/// Returns the device pixel ratio.
+	pub fn dpr(&self) -> Option<f64> {
+		self.number(ClientHint::Dpr)
+	}
+
+	/// Returns the width of the requested image in physical pixels.
+	pub fn width(&self) -> Option<u32> {
+		self.number(ClientHint::Width)
+	}
+
+	/// Returns the approximate amount of ram in GiB.
+	pub fn device_memory(&self) -> Option<f64> {
+		self.number(ClientHint::DeviceMemory)
+	}
+
+	/// Returns the trimmed value, falls back to the legacy name without
+	/// `sec-ch-`.
+	fn value(&self, hint: ClientHint) -> Option<&'a str> {
+		let name = hint.header_name();
+		self.header.value(name)
+			.or_else(|| self.header.value(name.strip_prefix("sec-ch-")?))
+			.map(str::trim)
+	}
+
+	fn string(&self, hint: ClientHint) -> Option<String> {
+		let (s, rest) = parse_sf_string(self.value(hint)?)?;
+		rest.trim().is_empty().then_some(s)
+	}
+
+	fn number<T: FromStr>(&self, hint: ClientHint) -> Option<T> {
+		self.value(hint)?.parse().ok()
+	}
+
+	fn brands(&self, hint: ClientHint) -> Option<Vec<Brand>> {
+		let mut rest = self.value(hint)?;
+		let mut brands = vec![];
+
+		while !rest.is_empty() {
+			let (brand, r) = parse_sf_string(rest)?;
+			rest = r.trim_start();
+
+			let mut version = None;
+			while let Some(r) = rest.strip_prefix(';') {
+				let (key, r) = r.trim_start().split_once('=')?;
+				let (value, r) = parse_sf_string(r)?;
+				if key == "v" {
+					version = Some(value);
+				}
+				rest = r.trim_start();
+			}
+
+			brands.push(Brand { brand, version: version.unwrap_or_default() });
+
+			rest = match rest.strip_prefix(',') {
+				Some(r) => r.trim_start(),
+				None if rest.is_empty() => rest,
+				None => return None
+			};
+		}
+
+		Some(brands)
+	}
+}
+
+/// Parses a structured field string returning the unescaped string and the
+/// rest.
+fn parse_sf_string(s: &str) -> Option<(String, &str)> {
+	let s = s.strip_prefix('"')?;
+	let mut out = String::new();
+	let mut chars = s.char_indices();
+
+	while let Some((i, c)) = chars.next() {
+		match c {
+			'"' => return Some((out, &s[i + 1..])),
+			'\\' => match chars.next()? {
+				(_, c @ ('"' | '\\')) => out.push(c),
+				_ => return None
+			},
+			c => out.push(c)
+		}
+	}
+
+	// unterminated string
+	None
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::{test_request_header, Method};
+
+	#[test]
+	fn test_client_hints() {
+
+		let header = test_request_header(Method::GET, "/", &[
+			(
+				"sec-ch-ua",
+				"\"Chromium\";v=\"118\", \"Not=A?\\\"Brand\";v=\"99\""
+			),
+			("sec-ch-ua-mobile", "?0"),
+			("sec-ch-ua-platform", "\"Windows\""),
+			("viewport-width", "1280"),
+			("sec-ch-dpr", "1.5")
+		]);
+
+		let hints = header.client_hints();
+		assert_eq!(hints.ua().unwrap(), [
+			Brand { brand: "Chromium".into(), version: "118".into() },
+			Brand { brand: "Not=A?\"Brand".into(), version: "99".into() }
+		]);
+		assert_eq!(hints.mobile(), Some(false));
+		assert_eq!(hints.platform().unwrap(), "Windows");
+		assert_eq!(hints.viewport_width(), Some(1280));
+		assert_eq!(hints.dpr(), Some(1.5));
+		assert_eq!(hints.width(), None);
+
+		assert_eq!("DPR".parse(), Ok(ClientHint::Dpr));
+		assert_eq!(
+			ClientHint::format_list(&[ClientHint::UaMobile, ClientHint::Dpr]),
+			"sec-ch-ua-mobile, sec-ch-dpr"
+		);
+
+	}
+
+}
@@ -0,0 +1,349 @@
+//! Evaluation of conditional requests as defined in RFC 9110 section 13.
+
+use super::{RequestHeader, HttpDate, Method, StatusCode};
+
+use std::fmt;
+use std::str::FromStr;
+
+
+/// The validators of the selected resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceMeta {
+	/// If the resource currently exists, `*` in `if-match` and
+	/// `if-none-match` matches any existing resource even without an etag.
+	pub exists: bool,
+	/// The full etag including quotes, for example `"abc"` or `W/"abc"`.
+	pub etag: Option<String>,
+	pub last_modified: Option<HttpDate>
+}
+
+impl ResourceMeta {
+	/// Creates a new `ResourceMeta` of an existing resource without any
+	/// validators.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new `ResourceMeta` of a resource which does not exist, for
+	/// example the target of a `PUT` creating it.
+	pub fn missing() -> Self {
+		Self { exists: false, ..Self::default() }
+	}
+}
+
+impl Default for ResourceMeta {
+	fn default() -> Self {
+		Self { exists: true, etag: None, last_modified: None }
+	}
+}
+
+/// The `if-range` header, a strong etag or a date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfRange {
+	/// The full etag including quotes, for example `"abc"`.
+	ETag(String),
+	Date(HttpDate)
+}
+
+impl IfRange {
+	/// Returns true if the resource did not change and the requested ranges
+	/// can be sent.
+	/// 
+	/// An etag needs to match with the strong comparison and a date needs to
+	/// be exactly the last modified date.
+	pub fn allows_partial(&self, meta: &ResourceMeta) -> bool {
+		match self {
+			Self::ETag(tag) => meta.etag.as_deref()
+				.map(|e| strong_eq(tag, e))
+				.unwrap_or(false),
+			Self::Date(date) => meta.last_modified == Some(*date)
+		}
+	}
+}
+
+impl fmt::Display for IfRange {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::ETag(tag) => f.write_str(tag),
+			Self::Date(date) => date.fmt(f)
+		}
+	}
+}
+
+/// Weak etags are not allowed in `if-range`.
+impl FromStr for IfRange {
+	type Err = InvalidIfRange;
+
+	fn from_str(s: &str) -> Result<Self, InvalidIfRange> {
+		let s = s.trim();
+		if s.starts_with('"') {
+			if s.len() < 2 || !s.ends_with('"') {
+				return Err(InvalidIfRange)
+			}
+
+			Ok(Self::ETag(s.to_string()))
+		} else if is_weak(s) {
+			Err(InvalidIfRange)
+		} else {
+			s.parse().map(Self::Date).map_err(|_| InvalidIfRange)
+		}
+	}
+}
+
+/// Returned if an `if-range` value is neither a strong etag nor a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidIfRange;
+
+impl fmt::Display for InvalidIfRange {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("invalid if-range")
+	}
+}
+
+impl std::error::Error for InvalidIfRange {}
+
+/// How the request should be responded to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Decision {
+	/// Process the request normally, a `range` header should be ignored.
+	Proceed,
+	/// Respond with the requested ranges.
+	PartialContent,
+	NotModified,
+	PreconditionFailed
+}
+
+impl Decision {
+	/// Returns the status code of the response.
+	pub fn status_code(&self) -> StatusCode {
+		match self {
+			Self::Proceed => StatusCode::OK,
+			Self::PartialContent => StatusCode::PARTIAL_CONTENT,
+			Self::NotModified => StatusCode::NOT_MODIFIED,
+			Self::PreconditionFailed => StatusCode::PRECONDITION_FAILED
+		}
+	}
+}
+
+/// Evaluates the preconditions of the request against the resource in the
+/// order defined by RFC 9110 section 13.2.2.
+///
+/// Invalid dates are ignored, `if-modified-since` and `if-unmodified-since`
+/// are ignored if the last modified date is unknown.
+pub fn evaluate(header: &RequestHeader, meta: &ResourceMeta) -> Decision {
+	let get_or_head = matches!(*header.method(), Method::GET | Method::HEAD);
+
+	// step 1 and 2
+	if let Some(if_match) = header.value("if-match") {
+		if !matches_list(if_match, meta, true) {
+			return Decision::PreconditionFailed
+		}
+	} else if let (Some(date), Some(last_modified)) =
+		(header_date(header, "if-unmodified-since"), meta.last_modified)
+	{
+		if last_modified > date {
+			return Decision::PreconditionFailed
+		}
+	}
+
+	// step 3 and 4
+	if let Some(if_none_match) = header.value("if-none-match") {
+		if matches_list(if_none_match, meta, false) {
+			return match get_or_head {
+				true => Decision::NotModified,
+				false => Decision::PreconditionFailed
+			}
+		}
+	} else if let (true, Some(date), Some(last_modified)) = (
+		get_or_head,
+		header_date(header, "if-modified-since"),
+		meta.last_modified
+	) {
+		if last_modified <= date {
+			return Decision::NotModified
+		}
+	}
+
+	// step 5
+	if *header.method() != Method::GET || header.value("range").is_none() {
+		return Decision::Proceed
+	}
+
+	let range_valid = match header.value("if-range") {
+		// a weak etag or an invalid value never allows a partial response
+		Some(v) => v.parse::<IfRange>()
+			.is_ok_and(|if_range| if_range.allows_partial(meta)),
+		None => true
+	};
+
+	match range_valid {
+		true => Decision::PartialContent,
+		false => Decision::Proceed
+	}
+}
+
+fn header_date(header: &RequestHeader, key: &str) -> Option<HttpDate> {
+	header.value(key)?.trim().parse().ok()
+}
+
+/// Returns true if the list contains `*` or an etag which matches.
+///
+/// `*` matches if the resource exists (RFC 9110 section 13.1.1 and 13.1.2).
+fn matches_list(list: &str, meta: &ResourceMeta, strong: bool) -> bool {
+	let list = list.trim();
+	if list == "*" {
+		return meta.exists
+	}
+
+	let Some(etag) = meta.etag.as_deref() else { return false };
+
+	list.split(',')
+		.map(str::trim)
+		.any(|tag| match strong {
+			true => strong_eq(tag, etag),
+			false => weak_eq(tag, etag)
+		})
+}
+
+fn is_weak(tag: &str) -> bool {
+	tag.starts_with("W/")
+}
+
+fn opaque(tag: &str) -> &str {
+	tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+fn strong_eq(a: &str, b: &str) -> bool {
+	!is_weak(a) && !is_weak(b) && a == b
+}
+
+fn weak_eq(a: &str, b: &str) -> bool {
+	opaque(a) == opaque(b)
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::test_request_header;
+
+	#[test]
+	fn test_evaluate() {
+
+		let meta = ResourceMeta {
+			exists: true,
+			etag: Some("\"v2\"".into()),
+			last_modified: Some(HttpDate::from_unix_secs(1_000_000))
+		};
+		let old = HttpDate::from_unix_secs(500_000).to_string();
+		let lm = meta.last_modified.unwrap().to_string();
+
+		let eval = |method, values: &[(&'static str, &str)]| {
+			evaluate(&test_request_header(method, "/", values), &meta)
+		};
+
+		assert_eq!(eval(Method::GET, &[]), Decision::Proceed);
+
+		// if-match uses the strong comparison
+		assert_eq!(
+			eval(Method::PUT, &[("if-match", "\"v1\", W/\"v2\"")]),
+			Decision::PreconditionFailed
+		);
+		assert_eq!(
+			eval(Method::PUT, &[("if-match", "\"v1\", \"v2\"")]),
+			Decision::Proceed
+		);
+		assert_eq!(
+			eval(Method::PUT, &[("if-unmodified-since", &old)]),
+			Decision::PreconditionFailed
+		);
+		// if-match takes precedence over if-unmodified-since
+		assert_eq!(
+			eval(Method::PUT, &[
+				("if-match", "*"),
+				("if-unmodified-since", &old)
+			]),
+			Decision::Proceed
+		);
+
+		// if-none-match uses the weak comparison
+		assert_eq!(
+			eval(Method::GET, &[("if-none-match", "W/\"v2\"")]),
+			Decision::NotModified
+		);
+		assert_eq!(
+			eval(Method::POST, &[("if-none-match", "*")]),
+			Decision::PreconditionFailed
+		);
+		assert_eq!(
+			eval(Method::GET, &[("if-modified-since", &lm)]),
+			Decision::NotModified
+		);
+		// if-none-match takes precedence over if-modified-since
+		assert_eq!(
+			eval(Method::GET, &[
+				("if-none-match", "\"v1\""),
+				("if-modified-since", &lm)
+			]),
+			Decision::Proceed
+		);
+
+		assert_eq!(
+			eval(Method::GET, &[("range", "bytes=0-1")]),
+			Decision::PartialContent
+		);
+		assert_eq!(
+			eval(Method::GET, &[
+				("range", "bytes=0-1"),
+				("if-range", "\"v2\"")
+			]),
+			Decision::PartialContent
+		);
+		assert_eq!(
+			eval(Method::GET, &[("range", "bytes=0-1"), ("if-range", &old)]),
+			Decision::Proceed
+		);
+		assert_eq!(
+			eval(Method::GET, &[
+				("range", "bytes=0-1"),
+				("if-range", "W/\"v2\"")
+			]),
+			Decision::Proceed
+		);
+
+		let if_range: IfRange = lm.parse().unwrap();
+		assert!(if_range.allows_partial(&meta));
+		assert_eq!(if_range.to_string(), lm);
+		assert!(!IfRange::ETag("\"v1\"".into()).allows_partial(&meta));
+
+	}
+
+	#[test]
+	fn test_star() {
+
+		let eval = |values: &[(&'static str, &str)], meta| {
+			evaluate(&test_request_header(Method::PUT, "/", values), meta)
+		};
+
+		// an existing resource without an etag is not overwritten
+		let existing = ResourceMeta::new();
+		assert_eq!(
+			eval(&[("if-none-match", "*")], &existing),
+			Decision::PreconditionFailed
+		);
+		assert_eq!(eval(&[("if-match", "*")], &existing), Decision::Proceed);
+		assert_eq!(
+			eval(&[("if-match", "\"v1\"")], &existing),
+			Decision::PreconditionFailed
+		);
+
+		let missing = ResourceMeta::missing();
+		assert_eq!(eval(&[("if-none-match", "*")], &missing), Decision::Proceed);
+		assert_eq!(
+			eval(&[("if-match", "*")], &missing),
+			Decision::PreconditionFailed
+		);
+
+	}
+
+}
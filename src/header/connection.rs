@@ -0,0 +1,112 @@
+//! Metadata of the connection a request was received on.
+
+use super::{Version, VersionExt};
+
+use std::net::SocketAddr;
+use std::time::SystemTime;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Information about a connection, shared by all requests received on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionInfo {
+	/// An id which is unique in this process.
+	pub id: u64,
+	pub peer_addr: SocketAddr,
+	pub local_addr: Option<SocketAddr>,
+	/// Is `None` if the connection is not encrypted.
+	pub tls: Option<TlsInfo>,
+	/// The negotiated ALPN protocol id, for example `h2`.
+	pub alpn: Option<Vec<u8>>,
+	/// When the connection was accepted.
+	pub started: SystemTime
+}
+
+impl ConnectionInfo {
+	/// Creates a new `ConnectionInfo` with a new unique id, started now.
+	pub fn new(peer_addr: SocketAddr) -> Self {
+		Self {
+			id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+			peer_addr,
+			local_addr: None,
+			tls: None,
+			alpn: None,
+			started: SystemTime::now()
+		}
+	}
+
+	/// Returns true if the connection is encrypted.
+	pub fn is_tls(&self) -> bool {
+		self.tls.is_some()
+	}
+
+	/// Returns the http version negotiated with ALPN.
+	pub fn alpn_version(&self) -> Option<Version> {
+		Version::from_alpn(self.alpn.as_deref()?)
+	}
+}
+
+/// Information about an encrypted connection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsInfo {
+	/// The server name sent by the client (SNI).
+	pub server_name: Option<String>,
+	/// For example `TLSv1.3`.
+	pub protocol_version: Option<String>,
+	/// For example `TLS13_AES_128_GCM_SHA256`.
+	pub cipher_suite: Option<String>
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn addr() -> SocketAddr {
+		([127, 0, 0, 1], 8080).into()
+	}
+
+	#[test]
+	fn test_unique_id() {
+
+		let a = ConnectionInfo::new(addr());
+		let b = ConnectionInfo::new(addr());
+		assert_ne!(a.id, b.id);
+		assert_eq!(a.clone(), a);
+
+	}
+
+	#[test]
+	fn test_tls() {
+
+		let mut conn = ConnectionInfo::new(addr());
+		assert!(!conn.is_tls());
+
+		conn.tls = Some(TlsInfo {
+			server_name: Some("example.com".into()),
+			..Default::default()
+		});
+		assert!(conn.is_tls());
+
+	}
+
+	#[test]
+	fn test_alpn_version() {
+
+		let mut conn = ConnectionInfo::new(addr());
+		assert_eq!(conn.alpn_version(), None);
+
+		conn.alpn = Some(b"h2".to_vec());
+		assert_eq!(conn.alpn_version(), Some(Version::HTTP_2));
+
+		conn.alpn = Some(b"http/1.1".to_vec());
+		assert_eq!(conn.alpn_version(), Some(Version::HTTP_11));
+
+		conn.alpn = Some(b"spdy/1".to_vec());
+		assert_eq!(conn.alpn_version(), None);
+
+	}
+
+}
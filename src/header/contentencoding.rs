@@ -0,0 +1,86 @@
+//! Types related to the `Content-Encoding` http header.
+
+use super::HeaderValue;
+
+use std::fmt;
+use std::str::FromStr;
+use std::convert::Infallible;
+
+
+/// Http `Content-Encoding` (or `Accept-Encoding` coding).
+/// 
+/// Parsing is case-insensitive and never fails, unknown codings are stored
+/// lowercase in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContentEncoding {
+	Gzip,
+	Deflate,
+	Br,
+	Zstd,
+	Identity,
+	Other(String)
+}
+
+impl ContentEncoding {
+	pub fn as_str(&self) -> &str {
+		match self {
+			Self::Gzip => "gzip",
+			Self::Deflate => "deflate",
+			Self::Br => "br",
+			Self::Zstd => "zstd",
+			Self::Identity => "identity",
+			Self::Other(s) => s
+		}
+	}
+
+	/// Parses a comma separated list of encodings in the order they were
+	/// applied, empty entries are skipped.
+	pub fn parse_list(s: &str) -> Vec<Self> {
+		s.split(',')
+			.map(str::trim)
+			.filter(|e| !e.is_empty())
+			.map(|e| e.parse().unwrap())
+			.collect()
+	}
+
+	/// Formats a list of encodings comma separated.
+	pub fn format_list(list: &[Self]) -> String {
+		list.iter()
+			.map(|e| e.as_str())
+			.collect::<Vec<_>>()
+			.join(", ")
+	}
+}
+
+impl fmt::Display for ContentEncoding {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl FromStr for ContentEncoding {
+	type Err = Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Infallible> {
+		let s = s.trim().to_ascii_lowercase();
+		Ok(match s.as_str() {
+			"gzip" | "x-gzip" => Self::Gzip,
+			"deflate" => Self::Deflate,
+			"br" => Self::Br,
+			"zstd" => Self::Zstd,
+			"identity" => Self::Identity,
+			_ => Self::Other(s)
+		})
+	}
+}
+
+impl TryFrom<ContentEncoding> for HeaderValue {
+	type Error = super::values::InvalidHeaderValue;
+
+	fn try_from(e: ContentEncoding) -> Result<Self, Self::Error> {
+		match e {
+			ContentEncoding::Other(s) => s.try_into(),
+			e => Ok(Self::from_str(e.as_str()).unwrap())
+		}
+	}
+}
@@ -49,8 +49,9 @@ impl FromStr for Mime {
 }
 
 /// Http `ContentType` header.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum ContentType {
+	#[default]
 	None,
 	Known(Mime),
 	Unknown(String)
@@ -0,0 +1,256 @@
+//! Types related to the `Cookie` and `Set-Cookie` http headers.
+
+use super::{HeaderValue, HttpDate};
+
+use std::fmt;
+use std::str::FromStr;
+
+
+/// Returns an iterator over the name value pairs of a `Cookie` header value
+/// like `a=1; b=2`.
+///
+/// Pairs without a `=` are skipped.
+pub fn parse_cookie_pairs(s: &str) -> impl Iterator<Item=(&str, &str)> {
+	s.split(';').filter_map(|pair| {
+		let (name, value) = pair.split_once('=')?;
+		let name = name.trim();
+		if name.is_empty() {
+			return None
+		}
+
+		Some((name, strip_quotes(value.trim())))
+	})
+}
+
+fn strip_quotes(s: &str) -> &str {
+	s.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(s)
+}
+
+/// The owned name value pairs of a `Cookie` header.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookies {
+	pairs: Vec<(String, String)>
+}
+
+impl Cookies {
+	/// Parses a `Cookie` header value, see `parse_cookie_pairs`.
+	pub fn parse(s: &str) -> Self {
+		let pairs = parse_cookie_pairs(s)
+			.map(|(n, v)| (n.to_string(), v.to_string()))
+			.collect();
+
+		Self { pairs }
+	}
+
+	/// Returns the value of the first cookie with the given name.
+	pub fn get(&self, name: &str) -> Option<&str> {
+		self.pairs.iter()
+			.find(|(n, _)| n == name)
+			.map(|(_, v)| v.as_str())
+	}
+
+	/// Returns the number of cookies.
+	pub fn len(&self) -> usize {
+		self.pairs.len()
+	}
+
+	/// Returns true if there are no cookies.
+	pub fn is_empty(&self) -> bool {
+		self.pairs.is_empty()
+	}
+
+	/// Returns an iterator over all names and values.
+	pub fn iter(&self) -> impl Iterator<Item=(&str, &str)> {
+		self.pairs.iter().map(|(n, v)| (n.as_str(), v.as_str()))
+	}
+}
+
+/// The `SameSite` attribute of a cookie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SameSite {
+	Strict,
+	Lax,
+	None
+}
+
+impl SameSite {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Strict => "Strict",
+			Self::Lax => "Lax",
+			Self::None => "None"
+		}
+	}
+}
+
+/// A cookie as sent by a server in a `Set-Cookie` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCookie {
+	pub name: String,
+	pub value: String,
+	pub expires: Option<HttpDate>,
+	/// In seconds, can be negative.
+	pub max_age: Option<i64>,
+	pub domain: Option<String>,
+	pub path: Option<String>,
+	pub secure: bool,
+	pub http_only: bool,
+	pub same_site: Option<SameSite>
+}
+
+impl SetCookie {
+	/// Creates a new `SetCookie` without any attributes.
+	pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			value: value.into(),
+			expires: None,
+			max_age: None,
+			domain: None,
+			path: None,
+			secure: false,
+			http_only: false,
+			same_site: None
+		}
+	}
+}
+
+impl fmt::Display for SetCookie {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}={}", self.name, self.value)?;
+
+		if let Some(expires) = &self.expires {
+			write!(f, "; Expires={expires}")?;
+		}
+
+		if let Some(max_age) = &self.max_age {
+			write!(f, "; Max-Age={max_age}")?;
+		}
+
+		if let Some(domain) = &self.domain {
+			write!(f, "; Domain={domain}")?;
+		}
+
+		if let Some(path) = &self.path {
+			write!(f, "; Path={path}")?;
+		}
+
+		if self.secure {
+			f.write_str("; Secure")?;
+		}
+
+		if self.http_only {
+			f.write_str("; HttpOnly")?;
+		}
+
+		if let Some(same_site) = &self.same_site {
+			write!(f, "; SameSite={}", same_site.as_str())?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Parses a `Set-Cookie` value, unknown or invalid attributes are ignored.
+impl FromStr for SetCookie {
+	type Err = InvalidSetCookie;
+
+	fn from_str(s: &str) -> Result<Self, InvalidSetCookie> {
+		let mut parts = s.split(';');
+
+		let (name, value) = parts.next()
+			.and_then(|p| p.split_once('='))
+			.ok_or(InvalidSetCookie)?;
+		let name = name.trim();
+		if name.is_empty() {
+			return Err(InvalidSetCookie)
+		}
+
+		let mut cookie = Self::new(name, strip_quotes(value.trim()));
+
+		for attr in parts {
+			let (key, val) = match attr.split_once('=') {
+				Some((k, v)) => (k.trim(), v.trim()),
+				None => (attr.trim(), "")
+			};
+
+			match key.to_ascii_lowercase().as_str() {
+				"expires" => cookie.expires = val.parse().ok(),
+				"max-age" => cookie.max_age = val.parse().ok(),
+				"domain" if !val.is_empty() => {
+					let domain = val.strip_prefix('.').unwrap_or(val);
+					cookie.domain = Some(domain.to_ascii_lowercase());
+				},
+				"path" if val.starts_with('/') => {
+					cookie.path = Some(val.to_string())
+				},
+				"secure" => cookie.secure = true,
+				"httponly" => cookie.http_only = true,
+				"samesite" => {
+					cookie.same_site = match val.to_ascii_lowercase().as_str() {
+						"strict" => Some(SameSite::Strict),
+						"lax" => Some(SameSite::Lax),
+						"none" => Some(SameSite::None),
+						_ => None
+					}
+				},
+				_ => {}
+			}
+		}
+
+		Ok(cookie)
+	}
+}
+
+impl TryFrom<SetCookie> for HeaderValue {
+	type Error = super::values::InvalidHeaderValue;
+
+	fn try_from(cookie: SetCookie) -> Result<Self, Self::Error> {
+		cookie.to_string().try_into()
+	}
+}
+
+/// Returned if a `Set-Cookie` value could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidSetCookie;
+
+impl fmt::Display for InvalidSetCookie {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("invalid set-cookie value")
+	}
+}
+
+impl std::error::Error for InvalidSetCookie {}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+
+		let pairs: Vec<_> = parse_cookie_pairs("a=1; b=\"2\";c; =d").collect();
+		assert_eq!(pairs, [("a", "1"), ("b", "2")]);
+
+		let cookie: SetCookie = "id=a3fWa; \
+			Expires=Thu, 21 Oct 2021 07:28:00 GMT; Domain=.Example.com; \
+			Path=/; Secure; HttpOnly; SameSite=Lax"
+			.parse().unwrap();
+		assert_eq!(cookie.name, "id");
+		assert_eq!(cookie.value, "a3fWa");
+		assert_eq!(cookie.expires.unwrap().unix_secs(), 1634801280);
+		assert_eq!(cookie.domain.as_deref(), Some("example.com"));
+		assert!(cookie.secure && cookie.http_only);
+		assert_eq!(cookie.same_site, Some(SameSite::Lax));
+		assert_eq!(
+			cookie.to_string(),
+			"id=a3fWa; Expires=Thu, 21 Oct 2021 07:28:00 GMT; \
+			Domain=example.com; Path=/; Secure; HttpOnly; SameSite=Lax"
+		);
+
+		assert!("no-value".parse::<SetCookie>().is_err());
+
+	}
+
+}
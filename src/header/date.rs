@@ -0,0 +1,293 @@
+//! Types related to dates used in http headers.
+
+use super::HeaderValue;
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, Duration, UNIX_EPOCH};
+
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = [
+	"Jan", "Feb", "Mar", "Apr", "May", "Jun",
+	"Jul", "Aug", "Sep", "Oct", "Nov", "Dec"
+];
+
+/// A date as used in http headers like `date`, `last-modified` or `expires`.
+///
+/// The precision is in seconds, dates before the unix epoch are not
+/// supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpDate {
+	// seconds since the unix epoch
+	secs: u64
+}
+
+impl HttpDate {
+	/// Returns the current date.
+	pub fn now() -> Self {
+		SystemTime::now().into()
+	}
+
+	/// Creates a date from the seconds since the unix epoch.
+	pub fn from_unix_secs(secs: u64) -> Self {
+		Self { secs }
+	}
+
+	/// Returns the seconds since the unix epoch.
+	pub fn unix_secs(&self) -> u64 {
+		self.secs
+	}
+
+	/// Returns the date as a `SystemTime`.
+	pub fn to_system_time(&self) -> SystemTime {
+		UNIX_EPOCH + Duration::from_secs(self.secs)
+	}
+
+	/// Formats the date like `10/Oct/2000:13:55:36 +0000` as used in access
+	/// logs.
+	pub(crate) fn log_format(&self) -> String {
+		let days = (self.secs / 86400) as i64;
+		let secs_of_day = self.secs % 86400;
+		let (year, month, day) = civil_from_days(days);
+
+		format!(
+			"{day:02}/{}/{year:04}:{:02}:{:02}:{:02} +0000",
+			MONTHS[month as usize - 1],
+			secs_of_day / 3600,
+			secs_of_day / 60 % 60,
+			secs_of_day % 60
+		)
+	}
+
+	/// Formats the date like `2000-10-10T13:55:36Z` as used in atom feeds.
+	pub(crate) fn rfc3339_format(&self) -> String {
+		let days = (self.secs / 86400) as i64;
+		let secs_of_day = self.secs % 86400;
+		let (year, month, day) = civil_from_days(days);
+
+		format!(
+			"{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+			secs_of_day / 3600,
+			secs_of_day / 60 % 60,
+			secs_of_day % 60
+		)
+	}
+}
+
+impl From<SystemTime> for HttpDate {
+	/// Truncates to seconds, a time before the unix epoch becomes the epoch.
+	fn from(time: SystemTime) -> Self {
+		let secs = time.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or(0);
+
+		Self { secs }
+	}
+}
+
+impl From<HttpDate> for SystemTime {
+	fn from(date: HttpDate) -> Self {
+		date.to_system_time()
+	}
+}
+
+/// Formats the date as an IMF-fixdate `Sun, 06 Nov 1994 08:49:37 GMT`.
+impl fmt::Display for HttpDate {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let days = (self.secs / 86400) as i64;
+		let secs_of_day = self.secs % 86400;
+		let (year, month, day) = civil_from_days(days);
+		// the 1.1.1970 was a thursday
+		let weekday = WEEKDAYS[((days + 3) % 7) as usize];
+
+		write!(
+			f, "{weekday}, {day:02} {} {year:04} {:02}:{:02}:{:02} GMT",
+			MONTHS[month as usize - 1],
+			secs_of_day / 3600,
+			secs_of_day / 60 % 60,
+			secs_of_day % 60
+		)
+	}
+}
+
+/// Parses an IMF-fixdate and the obsolete rfc850 and asctime formats.
+impl FromStr for HttpDate {
+	type Err = InvalidHttpDate;
+
+	fn from_str(s: &str) -> Result<Self, InvalidHttpDate> {
+		parse(s).ok_or(InvalidHttpDate)
+	}
+}
+
+impl From<HttpDate> for HeaderValue {
+	fn from(date: HttpDate) -> Self {
+		// the formatted date only contains valid characters
+		HeaderValue::from_str(&date.to_string()).unwrap()
+	}
+}
+
+/// Returned if a string is not a valid http date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidHttpDate;
+
+impl fmt::Display for InvalidHttpDate {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("invalid http date")
+	}
+}
+
+impl std::error::Error for InvalidHttpDate {}
+
+fn parse(s: &str) -> Option<HttpDate> {
+	let parts: Vec<_> = s.split_ascii_whitespace().collect();
+
+	let (year, month, day, time) = match parts.as_slice() {
+		// Sun, 06 Nov 1994 08:49:37 GMT
+		[_, day, month, year, time, "GMT"] => {
+			(year.parse().ok()?, *month, day.parse().ok()?, *time)
+		},
+		// Sunday, 06-Nov-94 08:49:37 GMT
+		[_, date, time, "GMT"] => {
+			let mut date = date.split('-');
+			let day = date.next()?.parse().ok()?;
+			let month = date.next()?;
+			let year: i64 = date.next()?.parse().ok()?;
+			if !(0..100).contains(&year) {
+				return None
+			}
+			let year = if year < 70 { 2000 + year } else { 1900 + year };
+			(year, month, day, *time)
+		},
+		// Sun Nov  6 08:49:37 1994
+		[_, month, day, time, year] => {
+			(year.parse().ok()?, *month, day.parse().ok()?, *time)
+		},
+		_ => return None
+	};
+
+	// larger years cannot be formatted with four digits and would overflow
+	// the calculation of the seconds
+	if !(1970..=9999).contains(&year) {
+		return None
+	}
+
+	let month = MONTHS.iter().position(|m| *m == month)? as u32 + 1;
+	if !(1..=days_in_month(year, month)).contains(&day) {
+		return None
+	}
+
+	let mut time = time.split(':');
+	let hour: u64 = time.next()?.parse().ok()?;
+	let min: u64 = time.next()?.parse().ok()?;
+	let sec: u64 = time.next()?.parse().ok()?;
+	if time.next().is_some() || hour > 23 || min > 59 || sec > 60 {
+		return None
+	}
+
+	let days = days_from_civil(year, month, day) as u64;
+	Some(HttpDate {
+		secs: days * 86400 + hour * 3600 + min * 60 + sec
+	})
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+	match month {
+		2 if is_leap_year(year) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31
+	}
+}
+
+fn is_leap_year(year: i64) -> bool {
+	year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+// algorithms from http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+	let z = days + 719468;
+	let era = z.div_euclid(146097);
+	let doe = z - era * 146097;
+	let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+	let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+	(year, month, day)
+}
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let yoe = year - era * 400;
+	let mp = if month > 2 { month - 3 } else { month + 9 } as i64;
+	let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+	era * 146097 + doe - 719468
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_formats() {
+
+		let date = HttpDate::from_unix_secs(784111777);
+		assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+
+		for s in [
+			"Sun, 06 Nov 1994 08:49:37 GMT",
+			"Sunday, 06-Nov-94 08:49:37 GMT",
+			"Sun Nov  6 08:49:37 1994"
+		] {
+			assert_eq!(s.parse::<HttpDate>().unwrap(), date);
+		}
+
+		let date = HttpDate::from_unix_secs(1709164800);
+		assert_eq!(date.to_string(), "Thu, 29 Feb 2024 00:00:00 GMT");
+		assert_eq!(date.to_string().parse::<HttpDate>().unwrap(), date);
+		assert_eq!(date.rfc3339_format(), "2024-02-29T00:00:00Z");
+
+		assert!("Sun, 06 Nov 1994 25:49:37 GMT".parse::<HttpDate>().is_err());
+
+	}
+
+	#[test]
+	fn test_invalid_days() {
+
+		for s in [
+			"Fri, 31 Feb 2023 00:00:00 GMT",
+			"Wed, 29 Feb 2023 00:00:00 GMT",
+			"Thu, 29 Feb 2100 00:00:00 GMT",
+			"Mon, 31 Apr 2023 00:00:00 GMT",
+			"Sun, 00 Jan 2023 00:00:00 GMT",
+			"Sunday, 31-Jun-23 00:00:00 GMT",
+			"Sun Sep 31 00:00:00 2023",
+			"Sun, 06 Nov 999999999999 08:49:37 GMT",
+			"Sun, 06 Nov 9223372036854775807 08:49:37 GMT",
+			"Sunday, 06-Nov-9223372036854775807 08:49:37 GMT",
+			"Sunday, 06-Nov--5 08:49:37 GMT",
+			"Sat, 01 Jan 10000 00:00:00 GMT"
+		] {
+			assert!(s.parse::<HttpDate>().is_err(), "{s}");
+		}
+
+		for s in [
+			"Tue, 29 Feb 2000 00:00:00 GMT",
+			"Tue, 31 Dec 2024 00:00:00 GMT",
+			"Sun, 30 Apr 2023 00:00:00 GMT",
+			"Fri, 31 Dec 9999 23:59:59 GMT"
+		] {
+			let date = s.parse::<HttpDate>().unwrap();
+			assert_eq!(date.to_string(), s);
+		}
+
+	}
+
+}
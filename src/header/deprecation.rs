@@ -0,0 +1,134 @@
+//! Types related to the `Deprecation` (RFC 9745) and `Sunset` (RFC 8594)
+//! http headers.
+
+use super::{HeaderValue, HttpDate};
+use super::date::InvalidHttpDate;
+
+use std::fmt;
+use std::str::FromStr;
+
+
+/// Http `Deprecation` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Deprecation {
+	/// The resource is deprecated without a known date, from older drafts.
+	Deprecated,
+	/// The resource is or will be deprecated at the date.
+	Since(HttpDate)
+}
+
+impl Deprecation {
+	/// Returns the date of the deprecation if known.
+	pub fn date(&self) -> Option<HttpDate> {
+		match self {
+			Self::Deprecated => None,
+			Self::Since(date) => Some(*date)
+		}
+	}
+}
+
+/// Formats a date as a structured field date, for example `@1688169599`.
+impl fmt::Display for Deprecation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Deprecated => f.write_str("true"),
+			Self::Since(date) => write!(f, "@{}", date.unix_secs())
+		}
+	}
+}
+
+/// Parses a structured field date and the `true` and http date values of
+/// older drafts.
+impl FromStr for Deprecation {
+	type Err = InvalidDeprecation;
+
+	fn from_str(s: &str) -> Result<Self, InvalidDeprecation> {
+		let s = s.trim();
+		if s == "true" || s == "?1" {
+			return Ok(Self::Deprecated)
+		}
+
+		let date = match s.strip_prefix('@') {
+			Some(secs) => secs.parse().ok().map(HttpDate::from_unix_secs),
+			None => s.parse().ok()
+		};
+
+		date.map(Self::Since).ok_or(InvalidDeprecation)
+	}
+}
+
+impl From<Deprecation> for HeaderValue {
+	fn from(deprecation: Deprecation) -> Self {
+		// only contains valid characters
+		HeaderValue::from_str(&deprecation.to_string()).unwrap()
+	}
+}
+
+/// Returned if a `Deprecation` could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidDeprecation;
+
+impl fmt::Display for InvalidDeprecation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("invalid deprecation")
+	}
+}
+
+impl std::error::Error for InvalidDeprecation {}
+
+/// Http `Sunset` header, the date at which the resource will become
+/// unavailable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sunset(pub HttpDate);
+
+impl fmt::Display for Sunset {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl FromStr for Sunset {
+	type Err = InvalidHttpDate;
+
+	fn from_str(s: &str) -> Result<Self, InvalidHttpDate> {
+		s.trim().parse().map(Self)
+	}
+}
+
+impl From<HttpDate> for Sunset {
+	fn from(date: HttpDate) -> Self {
+		Self(date)
+	}
+}
+
+impl From<Sunset> for HeaderValue {
+	fn from(sunset: Sunset) -> Self {
+		sunset.0.into()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_deprecation() {
+
+		let date = HttpDate::from_unix_secs(1688169599);
+		let dep = Deprecation::Since(date);
+		assert_eq!(dep.to_string(), "@1688169599");
+		assert_eq!("@1688169599".parse::<Deprecation>().unwrap(), dep);
+		assert_eq!(date.to_string().parse::<Deprecation>().unwrap(), dep);
+		assert_eq!(
+			"true".parse::<Deprecation>().unwrap(),
+			Deprecation::Deprecated
+		);
+		assert!("@abc".parse::<Deprecation>().is_err());
+
+		let sunset = Sunset(date);
+		assert_eq!(sunset.to_string().parse::<Sunset>().unwrap(), sunset);
+
+	}
+
+}
@@ -0,0 +1,56 @@
+//! Metadata describing a response body.
+
+use super::{ContentType, ContentEncoding, HeaderValues};
+
+
+/// Bundles the metadata of a body (called entity or representation in the
+/// http specs), so it can be kept together with the body it describes.
+/// 
+/// Apply it with `ResponseBuilder::entity`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Entity {
+	pub content_type: ContentType,
+	/// The encodings in the order they were applied.
+	pub content_encoding: Vec<ContentEncoding>,
+	/// For example `en-US`.
+	pub content_language: Option<String>,
+	pub content_length: Option<u64>,
+	/// The full etag including quotes, for example `"abc"` or `W/"abc"`.
+	pub etag: Option<String>
+}
+
+impl Entity {
+	/// Creates a new `Entity` with the given content type.
+	pub fn new(content_type: impl Into<ContentType>) -> Self {
+		Self {
+			content_type: content_type.into(),
+			..Default::default()
+		}
+	}
+
+	/// Writes all set metadata except the content type into the header
+	/// values.
+	/// 
+	/// ## Panics
+	/// If a value is not a valid `HeaderValue`.
+	pub fn apply_to_values(&self, values: &mut HeaderValues) {
+		if !self.content_encoding.is_empty() {
+			values.insert(
+				"content-encoding",
+				ContentEncoding::format_list(&self.content_encoding)
+			);
+		}
+
+		if let Some(language) = &self.content_language {
+			values.insert("content-language", language.as_str());
+		}
+
+		if let Some(len) = self.content_length {
+			values.insert("content-length", len);
+		}
+
+		if let Some(etag) = &self.etag {
+			values.insert("etag", etag.as_str());
+		}
+	}
+}
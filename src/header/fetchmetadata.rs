@@ -0,0 +1,188 @@
+//! Types related to the fetch metadata request headers `Sec-Fetch-Site`,
+//! `Sec-Fetch-Mode` and `Sec-Fetch-Dest`.
+//!
+//! Browsers send them with every request, which allows to reject cross site
+//! requests before they reach the application.
+
+use super::RequestHeader;
+
+use std::fmt;
+use std::str::FromStr;
+
+
+macro_rules! fetch_enum {
+	(
+		$(#[$meta:meta])*
+		$name:ident {
+			$($(#[$vmeta:meta])* $variant:ident => $value:literal),*
+		}
+	) => (
+		$(#[$meta])*
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+		#[non_exhaustive]
+		pub enum $name {
+			$($(#[$vmeta])* $variant),*
+		}
+
+		impl $name {
+			/// Returns the header value.
+			pub fn as_str(&self) -> &'static str {
+				match self {
+					$(Self::$variant => $value),*
+				}
+			}
+		}
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str(self.as_str())
+			}
+		}
+
+		impl FromStr for $name {
+			type Err = UnknownFetchMetadata;
+
+			fn from_str(s: &str) -> Result<Self, UnknownFetchMetadata> {
+				match s.trim() {
+					$($value => Ok(Self::$variant),)*
+					_ => Err(UnknownFetchMetadata)
+				}
+			}
+		}
+	)
+}
+
+fetch_enum! {
+	/// Http `Sec-Fetch-Site` header, the relation between the origin of the
+	/// request initiator and the requested resource.
+	SecFetchSite {
+		SameOrigin => "same-origin",
+		SameSite => "same-site",
+		CrossSite => "cross-site",
+		/// The request was initiated by the user, for example by typing the
+		/// url.
+		None => "none"
+	}
+}
+
+fetch_enum! {
+	/// Http `Sec-Fetch-Mode` header.
+	SecFetchMode {
+		Cors => "cors",
+		Navigate => "navigate",
+		NoCors => "no-cors",
+		SameOrigin => "same-origin",
+		Websocket => "websocket"
+	}
+}
+
+fetch_enum! {
+	/// Http `Sec-Fetch-Dest` header, how the response will be used.
+	SecFetchDest {
+		Audio => "audio",
+		AudioWorklet => "audioworklet",
+		Document => "document",
+		Embed => "embed",
+		Empty => "empty",
+		Font => "font",
+		Frame => "frame",
+		Iframe => "iframe",
+		Image => "image",
+		Manifest => "manifest",
+		Object => "object",
+		PaintWorklet => "paintworklet",
+		Report => "report",
+		Script => "script",
+		ServiceWorker => "serviceworker",
+		SharedWorker => "sharedworker",
+		Style => "style",
+		Track => "track",
+		Video => "video",
+		WebIdentity => "webidentity",
+		Worker => "worker",
+		Xslt => "xslt"
+	}
+}
+
+/// Returned if a fetch metadata value is not known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownFetchMetadata;
+
+impl fmt::Display for UnknownFetchMetadata {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("unknown fetch metadata value")
+	}
+}
+
+impl std::error::Error for UnknownFetchMetadata {}
+
+pub(super) fn is_cross_site(header: &RequestHeader) -> bool {
+	if let Some(site) = header.value("sec-fetch-site") {
+		return site.trim() == SecFetchSite::CrossSite.as_str()
+	}
+
+	let Some(origin) = header.origin() else {
+		return false
+	};
+
+	// an opaque origin, for example from a sandboxed iframe
+	if origin == "null" {
+		return true
+	}
+
+	let origin_host = origin.split_once("://")
+		.map(|(_, host)| host)
+		.unwrap_or(origin);
+	let host = header.value("host")
+		.or_else(|| header.uri().authority().map(|a| a.as_str()));
+
+	match host {
+		Some(host) => !origin_host.eq_ignore_ascii_case(host.trim()),
+		None => false
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::{test_request_header, Method};
+
+	#[test]
+	fn test_cross_site() {
+
+		let req = test_request_header(Method::POST, "/", &[
+			("sec-fetch-site", "cross-site"),
+			("sec-fetch-mode", "no-cors"),
+			("sec-fetch-dest", "image")
+		]);
+		assert_eq!(req.sec_fetch_site(), Some(SecFetchSite::CrossSite));
+		assert_eq!(req.sec_fetch_mode(), Some(SecFetchMode::NoCors));
+		assert_eq!(req.sec_fetch_dest(), Some(SecFetchDest::Image));
+		assert!(req.is_cross_site());
+
+		let req = test_request_header(
+			Method::POST,
+			"/",
+			&[("sec-fetch-site", "same-site")]
+		);
+		assert!(!req.is_cross_site());
+
+		// fallback to the origin
+		let req = test_request_header(Method::POST, "/", &[
+			("origin", "https://evil.com"),
+			("host", "example.com")
+		]);
+		assert!(req.is_cross_site());
+		let req = test_request_header(Method::POST, "/", &[
+			("origin", "https://example.com"),
+			("host", "example.com")
+		]);
+		assert!(!req.is_cross_site());
+
+		let req = test_request_header(Method::POST, "/", &[]);
+		assert!(!req.is_cross_site());
+
+	}
+
+}
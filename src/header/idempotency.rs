@@ -0,0 +1,85 @@
+//! Types related to the `Idempotency-Key` http header.
+
+use std::fmt;
+
+
+/// The maximum length of an idempotency key.
+pub const MAX_KEY_LEN: usize = 255;
+
+/// Parses and validates an idempotency key.
+///
+/// The key can be sent as a quoted structured field string or unquoted and
+/// needs to be between 1 and `MAX_KEY_LEN` visible ascii characters long.
+pub fn parse_idempotency_key(
+	value: &str
+) -> Result<&str, InvalidIdempotencyKey> {
+	let value = value.trim();
+	let key = value.strip_prefix('"')
+		.and_then(|v| v.strip_suffix('"'))
+		.unwrap_or(value);
+
+	if key.is_empty() {
+		return Err(InvalidIdempotencyKey::Empty)
+	}
+
+	if key.len() > MAX_KEY_LEN {
+		return Err(InvalidIdempotencyKey::TooLong)
+	}
+
+	// escapes are not supported inside quotes
+	if !key.bytes().all(|b| b.is_ascii_graphic() && b != b'"' && b != b'\\') {
+		return Err(InvalidIdempotencyKey::InvalidCharacter)
+	}
+
+	Ok(key)
+}
+
+/// Returned if an `Idempotency-Key` is not valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InvalidIdempotencyKey {
+	Empty,
+	TooLong,
+	InvalidCharacter
+}
+
+impl fmt::Display for InvalidIdempotencyKey {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Empty => f.write_str("idempotency key is empty"),
+			Self::TooLong => f.write_str("idempotency key is too long"),
+			Self::InvalidCharacter => {
+				f.write_str("idempotency key contains an invalid character")
+			}
+		}
+	}
+}
+
+impl std::error::Error for InvalidIdempotencyKey {}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+
+		assert_eq!(parse_idempotency_key("\"abc-123\"").unwrap(), "abc-123");
+		assert_eq!(parse_idempotency_key(" abc ").unwrap(), "abc");
+		assert_eq!(
+			parse_idempotency_key("\"\""),
+			Err(InvalidIdempotencyKey::Empty)
+		);
+		assert_eq!(
+			parse_idempotency_key("a b"),
+			Err(InvalidIdempotencyKey::InvalidCharacter)
+		);
+		assert_eq!(
+			parse_idempotency_key(&"a".repeat(MAX_KEY_LEN + 1)),
+			Err(InvalidIdempotencyKey::TooLong)
+		);
+
+	}
+
+}
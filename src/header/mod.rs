@@ -1,15 +1,88 @@
+use std::fmt;
+use std::sync::Arc;
 use std::net::SocketAddr;
 
-pub use http::{StatusCode, Method, Uri};
+pub use http::{StatusCode, Method, Uri, Version};
+
+pub mod percent;
+
+pub mod date;
+pub use date::HttpDate;
+
+pub mod uri;
+pub use uri::UriExt;
+
+pub mod version;
+pub use version::VersionExt;
+
+pub mod connection;
+pub use connection::ConnectionInfo;
 
 pub mod url;
 pub use url::Url;
 
+pub mod query;
+pub use query::Query;
+
 mod contenttype;
 pub use contenttype::{ContentType, Mime};
 
+pub mod charset;
+pub use charset::Charset;
+
+mod contentencoding;
+pub use contentencoding::ContentEncoding;
+
 pub mod values;
-pub use values::{HeaderValues, HeaderValue};
+pub use values::{
+	HeaderValues, SharedHeaderValues, HeaderValue, ConflictPolicy
+};
+
+pub mod entity;
+pub use entity::Entity;
+
+pub mod priority;
+pub use priority::Priority;
+
+pub mod idempotency;
+
+pub mod allow;
+pub use allow::AllowedMethods;
+
+pub mod prefer;
+pub use prefer::{Prefer, PreferenceApplied};
+
+pub mod deprecation;
+pub use deprecation::{Deprecation, Sunset};
+
+pub mod cookie;
+pub use cookie::{SetCookie, Cookies};
+
+pub mod trace;
+pub use trace::{TraceParent, TraceState};
+
+pub mod propagation;
+
+pub mod conditional;
+pub use conditional::IfRange;
+
+pub mod negotiate;
+
+pub mod useragent;
+pub use useragent::UserAgent;
+
+pub mod reporting;
+pub use reporting::{ReportingEndpoints, ReportTo, Nel};
+
+pub mod clienthints;
+pub use clienthints::{ClientHint, ClientHints};
+
+pub mod fetchmetadata;
+pub use fetchmetadata::{SecFetchSite, SecFetchMode, SecFetchDest};
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde_with;
 
 
 /// RequestHeader received from a client.
@@ -18,7 +91,10 @@ pub struct RequestHeader {
 	pub address: SocketAddr,
 	pub method: Method,
 	pub uri: Uri,
-	pub values: HeaderValues
+	pub values: HeaderValues,
+	/// The connection this request was received on, shared between all
+	/// requests of the connection.
+	pub connection: Option<Arc<ConnectionInfo>>
 }
 
 impl RequestHeader {
@@ -37,6 +113,11 @@ impl RequestHeader {
 		&self.uri
 	}
 
+	/// Returns the connection this request was received on if it is known.
+	pub fn connection(&self) -> Option<&ConnectionInfo> {
+		self.connection.as_deref()
+	}
+
 	pub fn to_url(&self) -> Option<Url> {
 		Url::from_inner(self.uri.clone())
 	}
@@ -54,8 +135,340 @@ impl RequestHeader {
 	where K: values::AsHeaderName {
 		self.values.get_str(key)
 	}
+
+	/// Returns the parsed `priority` header if it exists and is valid.
+	pub fn priority(&self) -> Option<Priority> {
+		self.value("priority")?.parse().ok()
+	}
+
+	/// Returns an iterator over all cookie names and values.
+	pub fn cookies(&self) -> impl Iterator<Item=(&str, &str)> {
+		cookie::parse_cookie_pairs(self.value("cookie").unwrap_or(""))
+	}
+
+	/// Returns the value of the first cookie with the given name.
+	pub fn cookie(&self, name: &str) -> Option<&str> {
+		self.cookies().find(|(n, _)| *n == name).map(|(_, v)| v)
+	}
+
+	/// Adds a cookie to the `cookie` header, keeping existing cookies.
+	/// 
+	/// ## Panics
+	/// If the name or value contains characters which are not allowed in a
+	/// `HeaderValue`.
+	pub fn add_cookie(&mut self, name: &str, value: &str) {
+		let cookie = match self.value("cookie") {
+			Some(prev) if !prev.trim().is_empty() => {
+				format!("{prev}; {name}={value}")
+			},
+			_ => format!("{name}={value}")
+		};
+
+		self.values.insert("cookie", cookie);
+	}
+
+	/// Returns the id of this request, either from the `x-request-id` header
+	/// or the trace id of the `traceparent` header.
+	pub fn request_id(&self) -> Option<&str> {
+		if let Some(id) = self.value("x-request-id") {
+			return Some(id)
+		}
+
+		let traceparent = self.value("traceparent")?.trim();
+		traceparent.parse::<TraceParent>().ok()?;
+		// a valid traceparent always starts with the version and a dash
+		Some(&traceparent[3..35])
+	}
+
+	/// Returns the charset of the `content-type` header if it is set and
+	/// known.
+	pub fn charset(&self) -> Option<Charset> {
+		Charset::from_content_type(self.value("content-type")?)
+	}
+
+	/// Returns the `origin` header if it exists, opaque origins are returned
+	/// as `null`.
+	pub fn origin(&self) -> Option<&str> {
+		self.value("origin").map(str::trim)
+	}
+
+	/// Returns the `sec-fetch-site` header if it exists and is known.
+	pub fn sec_fetch_site(&self) -> Option<SecFetchSite> {
+		self.value("sec-fetch-site")?.parse().ok()
+	}
+
+	/// Returns the `sec-fetch-mode` header if it exists and is known.
+	pub fn sec_fetch_mode(&self) -> Option<SecFetchMode> {
+		self.value("sec-fetch-mode")?.parse().ok()
+	}
+
+	/// Returns the `sec-fetch-dest` header if it exists and is known.
+	pub fn sec_fetch_dest(&self) -> Option<SecFetchDest> {
+		self.value("sec-fetch-dest")?.parse().ok()
+	}
+
+	/// Returns true if the request was initiated by another site.
+	/// 
+	/// Uses `sec-fetch-site` if it exists, else compares the host of the
+	/// `origin` with the `host` header, which also treats other subdomains as
+	/// cross site. Requests without both headers are not cross site, since
+	/// they don't come from a modern browser.
+	pub fn is_cross_site(&self) -> bool {
+		fetchmetadata::is_cross_site(self)
+	}
+
+	/// Returns the client hints sent with the request.
+	pub fn client_hints(&self) -> ClientHints<'_> {
+		ClientHints::new(self)
+	}
+
+	/// Returns the parsed `if-range` header if it exists and is valid.
+	pub fn if_range(&self) -> Option<IfRange> {
+		self.value("if-range")?.parse().ok()
+	}
+
+	/// Returns the parsed `user-agent` header if it exists.
+	pub fn user_agent(&self) -> Option<UserAgent<'_>> {
+		self.value("user-agent").map(UserAgent::parse)
+	}
+
+	/// Returns the parsed `prefer` header, is empty if the header does not
+	/// exist.
+	pub fn prefer(&self) -> Prefer {
+		self.value("prefer")
+			.map(|v| v.parse().unwrap())
+			.unwrap_or_default()
+	}
+
+	/// Returns the `idempotency-key` without quotes if it exists.
+	/// 
+	/// ## Errors
+	/// If the key is empty, too long or contains invalid characters.
+	pub fn idempotency_key(
+		&self
+	) -> Result<Option<&str>, idempotency::InvalidIdempotencyKey> {
+		self.value("idempotency-key")
+			.map(idempotency::parse_idempotency_key)
+			.transpose()
+	}
+
+	/// Returns the parsed `traceparent` header if it exists and is valid.
+	pub fn traceparent(&self) -> Option<TraceParent> {
+		self.value("traceparent")?.parse().ok()
+	}
+
+	/// Returns the parsed `tracestate` header if it exists and is valid.
+	pub fn tracestate(&self) -> Option<TraceState> {
+		self.value("tracestate")?.parse().ok()
+	}
+
+	/// Returns the id of this request (see `request_id`) or generates a new
+	/// uuid v4 storing it in the `x-request-id` header.
+	#[cfg(feature = "uuid")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+	pub fn ensure_request_id(&mut self) -> &str {
+		if self.request_id().is_none() {
+			let id = uuid::Uuid::new_v4().to_string();
+			self.values.insert("x-request-id", id);
+		}
+
+		self.request_id().unwrap()
+	}
+
+	/// Checks that the header values don't exceed the given number of headers
+	/// and the given approximate size in bytes (see `HeaderValues::byte_len`).
+	/// 
+	/// Every value is counted as a header, even if multiple values share a
+	/// key (see `HeaderValues::len`).
+	pub fn enforce_limits(
+		&self,
+		max_headers: usize,
+		max_total_bytes: usize
+	) -> Result<(), HeaderLimitError> {
+		let count = self.values.len();
+		if count > max_headers {
+			return Err(HeaderLimitError::TooManyHeaders {
+				max: max_headers,
+				count
+			})
+		}
+
+		let bytes = self.values.byte_len();
+		if bytes > max_total_bytes {
+			return Err(HeaderLimitError::TooLarge {
+				max: max_total_bytes,
+				bytes
+			})
+		}
+
+		Ok(())
+	}
+
+	/// Checks the `content-length` and `transfer-encoding` headers for
+	/// ambiguities which can lead to request smuggling.
+	/// 
+	/// A server should reject the request if this returns an error.
+	pub fn check_framing(&self) -> Result<(), FramingViolation> {
+		let mut content_length = None;
+		for val in self.values.get_all("content-length") {
+			let val = val.to_str()
+				.map_err(|_| FramingViolation::InvalidContentLength)?;
+
+			for len in val.split(',').map(str::trim) {
+				if len.is_empty() || !len.bytes().all(|b| b.is_ascii_digit()) {
+					return Err(FramingViolation::InvalidContentLength)
+				}
+
+				let len: u64 = len.parse()
+					.map_err(|_| FramingViolation::InvalidContentLength)?;
+				match content_length {
+					Some(prev) if prev != len => {
+						return Err(FramingViolation::ConflictingContentLength)
+					},
+					_ => content_length = Some(len)
+				}
+			}
+		}
+
+		let mut codings = vec![];
+		for val in self.values.get_all("transfer-encoding") {
+			let val = val.to_str()
+				.map_err(|_| FramingViolation::InvalidTransferEncoding)?;
+			codings.extend(val.split(',').map(str::trim));
+		}
+
+		if codings.is_empty() {
+			return Ok(())
+		}
+
+		if content_length.is_some() {
+			return Err(FramingViolation::ContentLengthAndTransferEncoding)
+		}
+
+		// chunked needs to be the last coding else the length is unknown
+		let chunked = |c: &&str| c.eq_ignore_ascii_case("chunked");
+		match codings.iter().position(chunked) {
+			Some(pos) if pos == codings.len() - 1 => Ok(()),
+			_ => Err(FramingViolation::InvalidTransferEncoding)
+		}
+	}
+
+	/// Returns the pseudo header fields `:method`, `:scheme`, `:authority`
+	/// and `:path` followed by all regular fields, with lowercase names as
+	/// needed by http/2 and http/3.
+	/// 
+	/// `:scheme` is only returned if the uri contains it, `:authority` is
+	/// taken from the uri or the `host` header which is then skipped.
+	/// 
+	/// Connection specific fields like `connection` or `transfer-encoding`
+	/// are skipped since they are not allowed in http/2 and http/3.
+	pub fn pseudo_and_fields(&self) -> impl Iterator<Item=(&str, &[u8])> {
+		let authority = self.uri.authority()
+			.map(|a| a.as_str().as_bytes())
+			.or_else(|| self.values.get("host").map(|v| v.as_bytes()));
+		let path = self.uri.path_and_query()
+			.map(|p| p.as_str())
+			.unwrap_or("/");
+
+		let pseudo = [
+			Some((":method", self.method.as_str().as_bytes())),
+			self.uri.scheme_str().map(|s| (":scheme", s.as_bytes())),
+			authority.map(|a| (":authority", a)),
+			Some((":path", path.as_bytes()))
+		];
+
+		let skip_host = authority.is_some();
+		pseudo.into_iter()
+			.flatten()
+			.chain(regular_fields(&self.values).filter(move |(name, _)| {
+				!(skip_host && *name == "host")
+			}))
+	}
+}
+
+/// Returns all fields which are allowed in http/2 and http/3.
+fn regular_fields(
+	values: &HeaderValues
+) -> impl Iterator<Item=(&str, &[u8])> {
+	let mut last = None;
+
+	values.iter()
+		.map(|(name, _)| name)
+		// the iterator returns a name for every value but we need
+		// every name once
+		.filter(move |name| {
+			let new = last != Some(*name);
+			last = Some(*name);
+			new
+		})
+		.filter(|name| !matches!(
+			name.as_str(),
+			"connection" | "keep-alive" | "proxy-connection" |
+			"transfer-encoding" | "upgrade"
+		))
+		.flat_map(move |name| {
+			values.get_all(name).map(|v| (name.as_str(), v.as_bytes()))
+		})
+}
+
+/// Returned from `RequestHeader::check_framing`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FramingViolation {
+	/// Both `content-length` and `transfer-encoding` are present.
+	ContentLengthAndTransferEncoding,
+	/// Multiple `content-length` values which differ.
+	ConflictingContentLength,
+	/// A `content-length` which is not a number.
+	InvalidContentLength,
+	/// A `transfer-encoding` which does not end with a single `chunked`.
+	InvalidTransferEncoding
+}
+
+impl fmt::Display for FramingViolation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match self {
+			Self::ContentLengthAndTransferEncoding => {
+				"content-length and transfer-encoding present"
+			},
+			Self::ConflictingContentLength => "conflicting content-length",
+			Self::InvalidContentLength => "invalid content-length",
+			Self::InvalidTransferEncoding => "invalid transfer-encoding"
+		})
+	}
 }
 
+impl std::error::Error for FramingViolation {}
+
+/// Returned from `RequestHeader::enforce_limits` if a limit was exceeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HeaderLimitError {
+	TooManyHeaders {
+		max: usize,
+		count: usize
+	},
+	TooLarge {
+		max: usize,
+		bytes: usize
+	}
+}
+
+impl fmt::Display for HeaderLimitError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::TooManyHeaders { max, count } => write!(
+				f, "too many headers {count}, only {max} allowed"
+			),
+			Self::TooLarge { max, bytes } => write!(
+				f, "headers to large {bytes} bytes, only {max} allowed"
+			)
+		}
+	}
+}
+
+impl std::error::Error for HeaderLimitError {}
+
 /// ResponseHeader created from a server.
 /// 
 /// To create a ResponseHeader you should probably
@@ -64,10 +477,51 @@ impl RequestHeader {
 pub struct ResponseHeader {
 	pub status_code: StatusCode,
 	pub content_type: ContentType,
-	pub values: HeaderValues
+	/// Can be shared between responses, mutating them only copies the
+	/// values if they are still shared.
+	pub values: SharedHeaderValues
 }
 
 impl ResponseHeader {
+	/// Creates an interim header, for example `100 Continue` or
+	/// `102 Processing`.
+	/// 
+	/// ## Panics
+	/// If the status code is not informational (1xx) or is
+	/// `101 Switching Protocols` which is a final response.
+	pub fn interim(status_code: StatusCode) -> Self {
+		assert!(
+			status_code.is_informational() &&
+			status_code != StatusCode::SWITCHING_PROTOCOLS,
+			"status code {status_code} is not an interim status code"
+		);
+
+		Self {
+			status_code,
+			..Default::default()
+		}
+	}
+
+	/// Creates a `103 Early Hints` header containing the given `link` values,
+	/// for example `</style.css>; rel=preload; as=style`.
+	/// 
+	/// ## Panics
+	/// If a link is not a valid `HeaderValue`.
+	pub fn early_hints<I, L>(links: I) -> Self
+	where
+		I: IntoIterator<Item=L>,
+		L: AsRef<str>
+	{
+		let links = links.into_iter()
+			.map(|l| l.as_ref().to_string())
+			.collect::<Vec<_>>()
+			.join(", ");
+
+		let mut header = Self::interim(StatusCode::from_u16(103).unwrap());
+		header.values.insert("link", links);
+		header
+	}
+
 	/// Returns the used status code.
 	pub fn status_code(&self) -> &StatusCode {
 		&self.status_code
@@ -91,6 +545,47 @@ impl ResponseHeader {
 	where K: values::AsHeaderName {
 		self.values.get_str(key)
 	}
+
+	/// Returns the applied content encodings in the order they were applied.
+	pub fn content_encoding(&self) -> Vec<ContentEncoding> {
+		let encoding = self.value("content-encoding").unwrap_or("");
+		ContentEncoding::parse_list(encoding)
+	}
+
+	/// Returns the parsed `priority` header if it exists and is valid.
+	pub fn priority(&self) -> Option<Priority> {
+		self.value("priority")?.parse().ok()
+	}
+
+	/// Returns all valid `set-cookie` values parsed.
+	pub fn set_cookies(&self) -> Vec<SetCookie> {
+		self.values.get_all("set-cookie")
+			.filter_map(|v| v.to_str().ok()?.parse().ok())
+			.collect()
+	}
+
+	/// Returns the pseudo header field `:status` followed by the content type
+	/// and all regular fields, with lowercase names as needed by http/2 and
+	/// http/3.
+	/// 
+	/// Connection specific fields like `connection` or `transfer-encoding`
+	/// are skipped since they are not allowed in http/2 and http/3.
+	pub fn pseudo_and_fields(&self) -> impl Iterator<Item=(&str, &[u8])> {
+		let content_type = match &self.content_type {
+			ContentType::None => None,
+			ct => Some(("content-type", ct.as_str().as_bytes()))
+		};
+		let has_content_type = content_type.is_some();
+
+		[
+			Some((":status", self.status_code.as_str().as_bytes())),
+			content_type
+		].into_iter()
+			.flatten()
+			.chain(regular_fields(&self.values).filter(move |(name, _)| {
+				!(has_content_type && *name == "content-type")
+			}))
+	}
 }
 
 impl Default for ResponseHeader {
@@ -98,7 +593,74 @@ impl Default for ResponseHeader {
 		Self {
 			status_code: StatusCode::OK,
 			content_type: ContentType::None,
-			values: HeaderValues::new()
+			values: HeaderValues::new().into()
 		}
 	}
-}
\ No newline at end of file
+}
+
+/// Creates a `RequestHeader` from `127.0.0.1:0` for tests, values with the
+/// same key are appended.
+#[cfg(test)]
+pub(crate) fn test_request_header(
+	method: Method,
+	uri: &str,
+	values: &[(&'static str, &str)]
+) -> RequestHeader {
+	let mut header = RequestHeader {
+		address: ([127, 0, 0, 1], 0).into(),
+		method,
+		uri: uri.parse().unwrap(),
+		values: HeaderValues::new(),
+		connection: None
+	};
+	for (k, v) in values {
+		header.values.extend(
+			HeaderValues::from(vec![(*k, v.to_string())]),
+			ConflictPolicy::Append
+		);
+	}
+
+	header
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn request(values: HeaderValues) -> RequestHeader {
+		let mut header = test_request_header(Method::GET, "/", &[]);
+		header.values = values;
+
+		header
+	}
+
+	#[test]
+	fn test_enforce_limits() {
+
+		let mut values = HeaderValues::from(vec![("a", "1"), ("b", "2")]);
+		values.extend(
+			HeaderValues::from(vec![("a", "3")]),
+			ConflictPolicy::Append
+		);
+		assert_eq!(values.len(), 3);
+		assert_eq!(values.keys_len(), 2);
+		// "a: 1\r\n" is 6 bytes
+		assert_eq!(values.byte_len(), 18);
+
+		let req = request(values);
+		assert!(req.enforce_limits(3, 18).is_ok());
+
+		assert_eq!(
+			req.enforce_limits(2, 18).unwrap_err(),
+			HeaderLimitError::TooManyHeaders { max: 2, count: 3 }
+		);
+		assert_eq!(
+			req.enforce_limits(3, 17).unwrap_err(),
+			HeaderLimitError::TooLarge { max: 17, bytes: 18 }
+		);
+
+		assert!(request(HeaderValues::new()).enforce_limits(0, 0).is_ok());
+
+	}
+
+}
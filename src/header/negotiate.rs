@@ -0,0 +1,316 @@
+//! Proactive content negotiation as defined in RFC 9110 section 12.
+//!
+//! All `accept-*` headers are evaluated together so the chosen
+//! representation and the `vary` header always match.
+
+use super::{RequestHeader, Mime, ContentEncoding, StatusCode};
+
+use std::fmt;
+
+
+/// The representations a resource is available in, the first entry of each
+/// list is preferred.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SupportedRepresentations {
+	pub mimes: Vec<Mime>,
+	/// Encodings besides `identity`, which is always supported.
+	pub encodings: Vec<ContentEncoding>,
+	pub languages: Vec<String>,
+	pub charsets: Vec<String>
+}
+
+impl SupportedRepresentations {
+	/// Creates a new `SupportedRepresentations` without any entries.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a supported mime type.
+	pub fn mime(mut self, mime: Mime) -> Self {
+		self.mimes.push(mime);
+		self
+	}
+
+	/// Adds a supported encoding.
+	pub fn encoding(mut self, encoding: ContentEncoding) -> Self {
+		self.encodings.push(encoding);
+		self
+	}
+
+	/// Adds a supported language tag, for example `en-US`.
+	pub fn language(mut self, language: impl Into<String>) -> Self {
+		self.languages.push(language.into());
+		self
+	}
+
+	/// Adds a supported charset, for example `utf-8`.
+	pub fn charset(mut self, charset: impl Into<String>) -> Self {
+		self.charsets.push(charset.into());
+		self
+	}
+
+	/// Returns the header names which influence the negotiation, they
+	/// should be added to the `vary` header.
+	pub fn vary(&self) -> Vec<&'static str> {
+		let mut vary = vec![];
+		if self.mimes.len() > 1 {
+			vary.push("accept");
+		}
+		if !self.encodings.is_empty() {
+			vary.push("accept-encoding");
+		}
+		if self.languages.len() > 1 {
+			vary.push("accept-language");
+		}
+		if self.charsets.len() > 1 {
+			vary.push("accept-charset");
+		}
+
+		vary
+	}
+}
+
+/// The result of a successful negotiation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Negotiated {
+	/// Is `None` if no mime types are supported.
+	pub mime: Option<Mime>,
+	/// Is `None` if the body should not be encoded (`identity`).
+	pub encoding: Option<ContentEncoding>,
+	/// Is `None` if no languages are supported.
+	pub language: Option<String>,
+	/// Is `None` if no charsets are supported.
+	pub charset: Option<String>,
+	/// The header names which should be added to the `vary` header.
+	pub vary: Vec<&'static str>
+}
+
+/// Returned if no supported mime type is acceptable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotAcceptable;
+
+impl NotAcceptable {
+	pub fn status_code(&self) -> StatusCode {
+		StatusCode::NOT_ACCEPTABLE
+	}
+}
+
+impl fmt::Display for NotAcceptable {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("no acceptable representation")
+	}
+}
+
+impl std::error::Error for NotAcceptable {}
+
+/// Chooses a representation based on the `accept`, `accept-encoding`,
+/// `accept-language` and `accept-charset` headers.
+///
+/// A missing header accepts everything and the preferred entry is chosen.
+/// If no language or charset is acceptable the preferred one is used anyway
+/// and if no encoding is acceptable the body is not encoded.
+///
+/// ## Errors
+/// If mime types are supported but none of them is acceptable.
+pub fn negotiate(
+	header: &RequestHeader,
+	supported: &SupportedRepresentations
+) -> Result<Negotiated, NotAcceptable> {
+	let mime = match header.value("accept") {
+		Some(accept) if !supported.mimes.is_empty() => {
+			let ranges = parse_weighted(accept);
+			let mime = best(&supported.mimes, |m| mime_quality(&ranges, m))
+				.ok_or(NotAcceptable)?;
+			Some(*mime)
+		},
+		_ => supported.mimes.first().copied()
+	};
+
+	let encoding = match header.value("accept-encoding") {
+		Some(accept) => {
+			let codings = parse_weighted(accept);
+			negotiate_encoding(&codings, &supported.encodings)
+		},
+		None => None
+	};
+
+	let language = match header.value("accept-language") {
+		Some(accept) => {
+			let ranges = parse_weighted(accept);
+			best(&supported.languages, |l| language_quality(&ranges, l))
+		},
+		None => None
+	}.or_else(|| supported.languages.first()).cloned();
+
+	let charset = match header.value("accept-charset") {
+		Some(accept) => {
+			let charsets = parse_weighted(accept);
+			best(&supported.charsets, |c| exact_quality(&charsets, c))
+		},
+		None => None
+	}.or_else(|| supported.charsets.first()).cloned();
+
+	Ok(Negotiated {
+		mime,
+		encoding,
+		language,
+		charset,
+		vary: supported.vary()
+	})
+}
+
+/// Parses a comma separated list of values with an optional `q` parameter.
+///
+/// Entries with an invalid weight are skipped.
+fn parse_weighted(s: &str) -> Vec<(&str, f32)> {
+	s.split(',')
+		.filter_map(|entry| {
+			let mut parts = entry.split(';').map(str::trim);
+			let value = parts.next().filter(|v| !v.is_empty())?;
+
+			let q = parts
+				.filter_map(|p| p.split_once('='))
+				.find(|(k, _)| k.trim().eq_ignore_ascii_case("q"))
+				.map(|(_, q)| q.trim().parse::<f32>().ok())
+				.unwrap_or(Some(1.0))?;
+
+			Some((value, q.clamp(0.0, 1.0)))
+		})
+		.collect()
+}
+
+/// Returns the supported entry with the highest quality above zero, on a tie
+/// the earlier entry wins.
+fn best<T, F>(supported: &[T], quality: F) -> Option<&T>
+where F: Fn(&T) -> Option<f32> {
+	let mut best: Option<(&T, f32)> = None;
+	for entry in supported {
+		let Some(q) = quality(entry) else { continue };
+		if q > 0.0 && best.map(|(_, b)| q > b).unwrap_or(true) {
+			best = Some((entry, q));
+		}
+	}
+
+	best.map(|(entry, _)| entry)
+}
+
+/// The quality of the most specific matching range.
+fn mime_quality(ranges: &[(&str, f32)], mime: &Mime) -> Option<f32> {
+	let (ty, subty) = mime.as_str().split_once('/').unwrap();
+
+	ranges.iter()
+		.filter_map(|(range, q)| {
+			let (r_ty, r_subty) = range.split_once('/')?;
+			let specificity = match (r_ty, r_subty) {
+				("*", "*") => 0,
+				(r_ty, "*") if r_ty.eq_ignore_ascii_case(ty) => 1,
+				(r_ty, r_subty) if r_ty.eq_ignore_ascii_case(ty) &&
+					r_subty.eq_ignore_ascii_case(subty) => 2,
+				_ => return None
+			};
+
+			Some((specificity, *q))
+		})
+		.max_by_key(|(specificity, _)| *specificity)
+		.map(|(_, q)| q)
+}
+
+/// The quality of the longest matching language range (RFC 4647 basic
+/// filtering).
+fn language_quality(ranges: &[(&str, f32)], tag: &str) -> Option<f32> {
+	ranges.iter()
+		.filter(|(range, _)| {
+			*range == "*" ||
+			tag.eq_ignore_ascii_case(range) ||
+			tag.get(..range.len()).is_some_and(|prefix| {
+				prefix.eq_ignore_ascii_case(range) &&
+				tag.as_bytes()[range.len()] == b'-'
+			})
+		})
+		.max_by_key(|(range, _)| match *range {
+			"*" => 0,
+			range => range.len()
+		})
+		.map(|(_, q)| *q)
+}
+
+fn exact_quality(list: &[(&str, f32)], value: &str) -> Option<f32> {
+	list.iter()
+		.find(|(v, _)| v.eq_ignore_ascii_case(value))
+		.or_else(|| list.iter().find(|(v, _)| *v == "*"))
+		.map(|(_, q)| *q)
+}
+
+fn negotiate_encoding(
+	codings: &[(&str, f32)],
+	supported: &[ContentEncoding]
+) -> Option<ContentEncoding> {
+	let quality = |coding: &str| exact_quality(codings, coding);
+
+	let encoding = best(supported, |e| quality(e.as_str()))?;
+
+	// identity is acceptable if not explicitly excluded
+	let identity = quality("identity").unwrap_or(1.0);
+	let q = quality(encoding.as_str()).unwrap();
+	if identity > q {
+		return None
+	}
+
+	Some(encoding.clone())
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::{test_request_header, Method};
+
+	#[test]
+	fn test_negotiate() {
+
+		let supported = SupportedRepresentations::new()
+			.mime(Mime::HTML)
+			.mime(Mime::JSON)
+			.encoding(ContentEncoding::Br)
+			.encoding(ContentEncoding::Gzip)
+			.language("en")
+			.language("de-CH");
+
+		let req = test_request_header(Method::GET, "/", &[]);
+		let neg = negotiate(&req, &supported).unwrap();
+		assert_eq!(neg.mime, Some(Mime::HTML));
+		assert_eq!(neg.encoding, None);
+		assert_eq!(neg.language.as_deref(), Some("en"));
+		assert_eq!(neg.charset, None);
+		assert_eq!(neg.vary, ["accept", "accept-encoding", "accept-language"]);
+
+		let neg = negotiate(&test_request_header(Method::GET, "/", &[
+			("accept", "text/*;q=0.5, application/json"),
+			("accept-encoding", "gzip, br;q=0.8"),
+			("accept-language", "fr, de;q=0.9, *;q=0.1")
+		]), &supported).unwrap();
+		assert_eq!(neg.mime, Some(Mime::JSON));
+		assert_eq!(neg.encoding, Some(ContentEncoding::Gzip));
+		assert_eq!(neg.language.as_deref(), Some("de-CH"));
+
+		// identity is preferred
+		let neg = negotiate(&test_request_header(Method::GET, "/", &[
+			("accept-encoding", "gzip;q=0.5")
+		]), &supported).unwrap();
+		assert_eq!(neg.encoding, None);
+
+		let neg = negotiate(&test_request_header(Method::GET, "/", &[
+			("accept", "*/*;q=0.1, text/html;q=0")
+		]), &supported).unwrap();
+		assert_eq!(neg.mime, Some(Mime::JSON));
+
+		let req = test_request_header(
+			Method::GET,
+			"/",
+			&[("accept", "image/png")]
+		);
+		assert_eq!(negotiate(&req, &supported), Err(NotAcceptable));
+
+	}
+
+}
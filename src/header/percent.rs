@@ -0,0 +1,147 @@
+//! Percent encoding and decoding for the different parts of a request.
+//!
+//! Each context has it's own set of characters that need to be encoded, using
+//! the wrong set either produces invalid values or values which cannot be
+//! decoded unambiguously.
+
+use super::HeaderValue;
+
+use std::borrow::Cow;
+
+use percent_encoding::{AsciiSet, CONTROLS, percent_decode, utf8_percent_encode};
+
+
+/// Characters encoded in a path segment (the WHATWG path set plus `/` and
+/// `%`).
+pub const PATH_SEGMENT: &AsciiSet = &CONTROLS
+	.add(b' ').add(b'"').add(b'#').add(b'<').add(b'>').add(b'?').add(b'`')
+	.add(b'{').add(b'}').add(b'/').add(b'%');
+
+/// Characters encoded in a query key or value (the WHATWG component set).
+pub const QUERY_COMPONENT: &AsciiSet = &PATH_SEGMENT
+	.add(b':').add(b';').add(b'=').add(b'@').add(b'[').add(b'\\').add(b']')
+	.add(b'^').add(b'|').add(b'$').add(b'&').add(b'+').add(b',');
+
+/// Characters encoded in a header value.
+///
+/// Every non visible ascii character and `%` gets encoded which guarantees
+/// the result is a valid `HeaderValue` and can be decoded again. `"` is
+/// encoded so the value can be placed inside a quoted string.
+pub const HEADER_VALUE: &AsciiSet = &CONTROLS.add(b'%').add(b'"');
+
+/// Characters encoded in an RFC 8187 ext-value (everything except attr-char).
+pub const EXT_VALUE: &AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+	.remove(b'!').remove(b'#').remove(b'$').remove(b'&').remove(b'+')
+	.remove(b'-').remove(b'.').remove(b'^').remove(b'_').remove(b'`')
+	.remove(b'|').remove(b'~');
+
+/// Percent encodes a single path segment.
+pub fn encode_path_segment(s: &str) -> Cow<'_, str> {
+	utf8_percent_encode(s, PATH_SEGMENT).into()
+}
+
+/// Decodes a single path segment, returns `None` if the result is not valid
+/// utf8.
+pub fn decode_path_segment(s: &str) -> Option<Cow<'_, str>> {
+	percent_decode(s.as_bytes()).decode_utf8().ok()
+}
+
+/// Percent encodes a query key or value.
+///
+/// Spaces are encoded as `%20`, `Query` uses the form encoding which writes
+/// them as `+`.
+pub fn encode_query_component(s: &str) -> Cow<'_, str> {
+	utf8_percent_encode(s, QUERY_COMPONENT).into()
+}
+
+/// Decodes a query key or value, treating `+` as a space. Invalid utf8 is
+/// replaced.
+pub fn decode_query_component(s: &str) -> Cow<'_, str> {
+	if s.contains('+') {
+		let s = s.replace('+', " ");
+		return percent_decode(s.as_bytes()).decode_utf8_lossy()
+			.into_owned()
+			.into()
+	}
+
+	percent_decode(s.as_bytes()).decode_utf8_lossy()
+}
+
+/// Percent encodes a value so that it is a valid `HeaderValue`.
+pub fn encode_header_value(s: impl AsRef<[u8]>) -> HeaderValue {
+	let s: String = percent_encoding::percent_encode(
+		s.as_ref(),
+		HEADER_VALUE
+	).collect();
+	// does not allocate again
+	let b: bytes::Bytes = s.into();
+	// HEADER_VALUE encodes every byte which is not allowed
+	HeaderValue::from_maybe_shared(b).unwrap()
+}
+
+/// Decodes a header value, returns `None` if the result is not valid utf8.
+pub fn decode_header_value(val: &HeaderValue) -> Option<Cow<'_, str>> {
+	percent_decode(val.as_bytes()).decode_utf8().ok()
+}
+
+/// Encodes a value as an RFC 8187 ext-value in the form `utf-8''value`.
+///
+/// ## Note
+/// A raw value can look like an ext-value, so whether a value is encoded
+/// needs to be recorded separately, for example with a `name*` key like
+/// `HeaderValues::encode_value_ext` does.
+pub fn encode_ext_value(s: &str) -> HeaderValue {
+	let s = format!("utf-8''{}", utf8_percent_encode(s, EXT_VALUE));
+	// EXT_VALUE only leaves attr-char which are all valid
+	HeaderValue::from_maybe_shared(bytes::Bytes::from(s)).unwrap()
+}
+
+/// Decodes an RFC 8187 ext-value with the charset `utf-8` or `iso-8859-1`.
+///
+/// Returns `None` if the value is not an ext-value, has an unknown charset
+/// or is not valid utf8.
+pub fn decode_ext_value(val: &HeaderValue) -> Option<Cow<'_, str>> {
+	let val = val.to_str().ok()?;
+	let (charset, rest) = val.split_once('\'')?;
+	// skip the language tag
+	let (_, val) = rest.split_once('\'')?;
+
+	let decoded = percent_decode(val.as_bytes());
+	if charset.eq_ignore_ascii_case("utf-8") {
+		decoded.decode_utf8().ok()
+	} else if charset.eq_ignore_ascii_case("iso-8859-1") {
+		Some(decoded.map(char::from).collect::<String>().into())
+	} else {
+		None
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_contexts() {
+
+		assert_eq!(encode_path_segment("a b/c%"), "a%20b%2Fc%25");
+		assert_eq!(decode_path_segment("a%20b%2Fc%25").unwrap(), "a b/c%");
+
+		assert_eq!(encode_query_component("a=b&c+d e"), "a%3Db%26c%2Bd%20e");
+		assert_eq!(decode_query_component("a+b%2B"), "a b+");
+
+		let val = encode_header_value("100% \"\r\n🚀");
+		assert_eq!(val, "100%25 %22%0D%0A%F0%9F%9A%80");
+		assert_eq!(decode_header_value(&val).unwrap(), "100% \"\r\n🚀");
+
+		let val = encode_ext_value("£ rates");
+		assert_eq!(val, "utf-8''%C2%A3%20rates");
+		assert_eq!(decode_ext_value(&val).unwrap(), "£ rates");
+		let val = HeaderValue::from_static("iso-8859-1'en'%A3%20rates");
+		assert_eq!(decode_ext_value(&val).unwrap(), "£ rates");
+		let val = HeaderValue::from_static("%F0 raw");
+		assert!(decode_ext_value(&val).is_none());
+
+	}
+
+}
@@ -0,0 +1,238 @@
+//! Types related to the `Prefer` and `Preference-Applied` http headers
+//! (RFC 7240).
+
+use super::HeaderValue;
+
+use std::fmt;
+use std::str::FromStr;
+use std::convert::Infallible;
+
+
+/// The value of the `return` preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Return {
+	Minimal,
+	Representation
+}
+
+impl Return {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Minimal => "minimal",
+			Self::Representation => "representation"
+		}
+	}
+}
+
+/// Http `Prefer` header.
+///
+/// Parsing never fails, invalid preferences and parameters are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Prefer {
+	/// The lowercase names and the values in the order they were received.
+	preferences: Vec<(String, Option<String>)>
+}
+
+/// Http `Preference-Applied` header, which uses the same syntax as
+/// `Prefer`.
+pub type PreferenceApplied = Prefer;
+
+impl Prefer {
+	/// Creates a new `Prefer` without any preferences.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a preference, replacing a previous one with the same name.
+	pub fn with(
+		mut self,
+		name: impl Into<String>,
+		value: Option<impl Into<String>>
+	) -> Self {
+		let name = name.into().to_ascii_lowercase();
+		self.preferences.retain(|(n, _)| *n != name);
+		self.preferences.push((name, value.map(Into::into)));
+		self
+	}
+
+	/// Adds the `return` preference.
+	pub fn with_return(self, ret: Return) -> Self {
+		self.with("return", Some(ret.as_str()))
+	}
+
+	/// Adds the `wait` preference in seconds.
+	pub fn with_wait(self, secs: u64) -> Self {
+		self.with("wait", Some(secs.to_string()))
+	}
+
+	/// Adds the `respond-async` preference.
+	pub fn with_respond_async(self) -> Self {
+		self.with("respond-async", None::<String>)
+	}
+
+	/// Returns the value of the first preference with the name, the inner
+	/// option is `None` if the preference has no value.
+	pub fn get(&self, name: &str) -> Option<Option<&str>> {
+		self.preferences.iter()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, v)| v.as_deref())
+	}
+
+	/// Returns true if the preference is present.
+	pub fn contains(&self, name: &str) -> bool {
+		self.get(name).is_some()
+	}
+
+	/// Returns the `return` preference if it is valid.
+	pub fn return_pref(&self) -> Option<Return> {
+		match self.get("return")?? {
+			"minimal" => Some(Return::Minimal),
+			"representation" => Some(Return::Representation),
+			_ => None
+		}
+	}
+
+	/// Returns the `wait` preference in seconds if it is valid.
+	pub fn wait(&self) -> Option<u64> {
+		self.get("wait")??.parse().ok()
+	}
+
+	/// Returns true if the `respond-async` preference is present.
+	pub fn respond_async(&self) -> bool {
+		self.contains("respond-async")
+	}
+
+	/// Returns an iterator over all names and values.
+	pub fn iter(&self) -> impl Iterator<Item=(&str, Option<&str>)> {
+		self.preferences.iter()
+			.map(|(n, v)| (n.as_str(), v.as_deref()))
+	}
+
+	pub fn len(&self) -> usize {
+		self.preferences.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.preferences.is_empty()
+	}
+}
+
+impl fmt::Display for Prefer {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, (name, value)) in self.preferences.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			f.write_str(name)?;
+
+			match value {
+				Some(v) if !v.is_empty() && v.bytes().all(is_token_char) => {
+					write!(f, "={v}")?;
+				},
+				Some(v) => {
+					f.write_str("=\"")?;
+					for c in v.chars() {
+						if matches!(c, '"' | '\\') {
+							f.write_str("\\")?;
+						}
+						write!(f, "{c}")?;
+					}
+					f.write_str("\"")?;
+				},
+				None => {}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// A later preference with the same name is ignored.
+impl FromStr for Prefer {
+	type Err = Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Infallible> {
+		let mut prefer = Self::new();
+
+		for pref in s.split(',') {
+			// parameters are ignored
+			let pref = pref.split(';').next().unwrap().trim();
+			let (name, value) = match pref.split_once('=') {
+				Some((n, v)) => (n.trim(), Some(unquote(v.trim()))),
+				None => (pref, None)
+			};
+
+			if name.is_empty() || !name.bytes().all(is_token_char) ||
+				prefer.contains(name)
+			{
+				continue
+			}
+
+			prefer.preferences.push((name.to_ascii_lowercase(), value));
+		}
+
+		Ok(prefer)
+	}
+}
+
+impl TryFrom<Prefer> for HeaderValue {
+	type Error = super::values::InvalidHeaderValue;
+
+	fn try_from(prefer: Prefer) -> Result<Self, Self::Error> {
+		prefer.to_string().try_into()
+	}
+}
+
+fn is_token_char(b: u8) -> bool {
+	b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+fn unquote(s: &str) -> String {
+	let Some(inner) = s.strip_prefix('"').and_then(|s| s.strip_suffix('"'))
+	else { return s.to_string() };
+
+	let mut out = String::with_capacity(inner.len());
+	let mut chars = inner.chars();
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' => out.extend(chars.next()),
+			c => out.push(c)
+		}
+	}
+
+	out
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_prefer() {
+
+		let prefer: Prefer = "Return=minimal; foo=bar, wait=10, \
+			respond-async, handling=\"a \\\"b\\\"\", wait=20"
+			.parse().unwrap();
+		assert_eq!(prefer.return_pref(), Some(Return::Minimal));
+		assert_eq!(prefer.wait(), Some(10));
+		assert!(prefer.respond_async());
+		assert_eq!(prefer.get("handling"), Some(Some("a \"b\"")));
+		assert_eq!(prefer.len(), 4);
+
+		assert_eq!(
+			prefer.to_string(),
+			"return=minimal, wait=10, respond-async, handling=\"a \\\"b\\\"\""
+		);
+
+		let applied = PreferenceApplied::new()
+			.with_return(Return::Representation)
+			.with_respond_async();
+		assert_eq!(
+			applied.to_string(),
+			"return=representation, respond-async"
+		);
+
+	}
+
+}
@@ -0,0 +1,169 @@
+//! Types related to the `Priority` http header (RFC 9218).
+
+use super::HeaderValue;
+
+use std::fmt;
+use std::str::FromStr;
+
+
+/// Http `Priority` header.
+/// 
+/// Servers using HTTP/2 or HTTP/3 can map it to stream priorities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Priority {
+	urgency: u8,
+	incremental: bool
+}
+
+impl Priority {
+	/// The default urgency.
+	pub const DEFAULT_URGENCY: u8 = 3;
+
+	/// Creates a new `Priority` with the default urgency which is not
+	/// incremental.
+	pub fn new() -> Self {
+		Self {
+			urgency: Self::DEFAULT_URGENCY,
+			incremental: false
+		}
+	}
+
+	/// Sets the urgency, 0 is the highest and 7 the lowest.
+	/// 
+	/// ## Panics
+	/// If the urgency is bigger than 7.
+	pub fn urgency(mut self, urgency: u8) -> Self {
+		assert!(urgency <= 7, "urgency needs to be between 0 and 7");
+		self.urgency = urgency;
+		self
+	}
+
+	/// Sets if the response can be processed incrementally.
+	pub fn incremental(mut self, incremental: bool) -> Self {
+		self.incremental = incremental;
+		self
+	}
+
+	/// Returns the urgency, 0 is the highest and 7 the lowest.
+	pub fn get_urgency(&self) -> u8 {
+		self.urgency
+	}
+
+	/// Returns true if the response can be processed incrementally.
+	pub fn is_incremental(&self) -> bool {
+		self.incremental
+	}
+}
+
+impl Default for Priority {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl fmt::Display for Priority {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "u={}", self.urgency)?;
+		if self.incremental {
+			f.write_str(", i")?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Parses the structured field dictionary.
+/// 
+/// Unknown keys and values which are out of range or have an unexpected type
+/// are ignored (RFC 9218), only an invalid key returns an error.
+impl FromStr for Priority {
+	type Err = InvalidPriority;
+
+	fn from_str(s: &str) -> Result<Self, InvalidPriority> {
+		let mut prio = Self::new();
+
+		for member in s.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+			// parameters of a member are ignored
+			let member = member.split(';').next().unwrap();
+			let (key, val) = match member.split_once('=') {
+				Some((k, v)) => (k.trim(), Some(v.trim())),
+				None => (member, None)
+			};
+
+			if !is_key(key) {
+				return Err(InvalidPriority)
+			}
+
+			match (key, val) {
+				("u", Some(v)) => {
+					if let Some(u) = v.parse().ok().filter(|u| *u <= 7) {
+						prio.urgency = u;
+					}
+				},
+				("i", None | Some("?1")) => prio.incremental = true,
+				("i", Some("?0")) => prio.incremental = false,
+				_ => {}
+			}
+		}
+
+		Ok(prio)
+	}
+}
+
+// a structured field key
+fn is_key(key: &str) -> bool {
+	let mut bytes = key.bytes();
+	matches!(bytes.next(), Some(b'a'..=b'z' | b'*')) &&
+		bytes.all(|b| matches!(
+			b,
+			b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'*'
+		))
+}
+
+impl From<Priority> for HeaderValue {
+	fn from(prio: Priority) -> Self {
+		// only contains valid characters
+		HeaderValue::from_str(&prio.to_string()).unwrap()
+	}
+}
+
+/// Returned if a `Priority` could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidPriority;
+
+impl fmt::Display for InvalidPriority {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("invalid priority")
+	}
+}
+
+impl std::error::Error for InvalidPriority {}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+
+		let prio: Priority = "u=1, i".parse().unwrap();
+		assert_eq!(prio, Priority::new().urgency(1).incremental(true));
+		assert_eq!(prio.to_string(), "u=1, i");
+
+		let prio: Priority = "i=?0, foo=bar, u=9".parse().unwrap();
+		assert_eq!(prio, Priority::new());
+
+		// out of range and unexpected types are ignored
+		for s in ["u=8", "u=9", "u=300", "u=-1", "u=high", "u=1.5", "i=5"] {
+			assert_eq!(s.parse::<Priority>().unwrap(), Priority::new(), "{s}");
+		}
+		let prio: Priority = "u=2, u=300".parse().unwrap();
+		assert_eq!(prio.get_urgency(), 2);
+
+		assert!("U=1".parse::<Priority>().is_err());
+		assert!("=1".parse::<Priority>().is_err());
+
+	}
+
+}
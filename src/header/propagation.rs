@@ -0,0 +1,68 @@
+//! Context propagation headers which should be forwarded to downstream
+//! services, like W3C Trace Context, W3C Baggage and B3.
+
+use super::{RequestHeader, HeaderValues, ConflictPolicy};
+
+
+/// All known context propagation headers.
+pub const PROPAGATION_HEADERS: &[&str] = &[
+	"traceparent",
+	"tracestate",
+	"baggage",
+	"b3",
+	"x-b3-traceid",
+	"x-b3-spanid",
+	"x-b3-parentspanid",
+	"x-b3-sampled",
+	"x-b3-flags"
+];
+
+/// Returns true if the header name is a context propagation header, the name
+/// is compared case insensitive.
+pub fn is_propagation_header(name: &str) -> bool {
+	PROPAGATION_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name))
+}
+
+/// Copies all context propagation headers of the incoming request to the
+/// values of an outgoing request.
+///
+/// Values which already exist in `to` are replaced.
+pub fn propagate(from: &RequestHeader, to: &mut HeaderValues) {
+	let mut values = http::HeaderMap::new();
+
+	for name in PROPAGATION_HEADERS {
+		for val in from.values().get_all(*name) {
+			values.append(*name, val.clone());
+		}
+	}
+
+	to.extend(HeaderValues::from_inner(values), ConflictPolicy::Overwrite);
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::{test_request_header, Method};
+
+	#[test]
+	fn test_propagate() {
+
+		let header = test_request_header(Method::GET, "/", &[
+			("traceparent", "00-abc-def-01"),
+			("X-B3-Sampled", "1"),
+			("authorization", "secret")
+		]);
+
+		let mut values = HeaderValues::new();
+		values.insert("traceparent", "old");
+		propagate(&header, &mut values);
+
+		assert_eq!(values.len(), 2);
+		assert_eq!(values.get_str("traceparent"), Some("00-abc-def-01"));
+		assert_eq!(values.get_str("x-b3-sampled"), Some("1"));
+		assert!(is_propagation_header("Baggage"));
+
+	}
+
+}
@@ -0,0 +1,212 @@
+//! Types related to the query string of an uri.
+
+use super::Uri;
+use super::percent::decode_query_component;
+
+use std::fmt;
+
+
+/// Contains the decoded key value pairs of a query string.
+///
+/// The order of the pairs is preserved, a key can have multiple values and
+/// keys without a value (`?debug`) are supported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+	// value is None for bare keys
+	pairs: Vec<(String, Option<String>)>
+}
+
+impl Query {
+	/// Creates a new empty `Query`.
+	pub fn new() -> Self {
+		Self { pairs: vec![] }
+	}
+
+	/// Parses a query string (without the leading `?`).
+	///
+	/// Keys and values are percent decoded and `+` is treated as a space.
+	pub fn parse(query: &str) -> Self {
+		let pairs = query.split('&')
+			.filter(|p| !p.is_empty())
+			.map(|p| match p.split_once('=') {
+				Some((k, v)) => (decode(k), Some(decode(v))),
+				None => (decode(p), None)
+			})
+			.collect();
+
+		Self { pairs }
+	}
+
+	/// Parses the query string of an `Uri`.
+	pub fn from_uri(uri: &Uri) -> Self {
+		Self::parse(uri.query().unwrap_or(""))
+	}
+
+	/// Returns the number of pairs.
+	pub fn len(&self) -> usize {
+		self.pairs.len()
+	}
+
+	/// Returns true if no pair is present.
+	pub fn is_empty(&self) -> bool {
+		self.pairs.is_empty()
+	}
+
+	/// Returns true if the key is present, with or without a value.
+	pub fn contains_key(&self, key: &str) -> bool {
+		self.pairs.iter().any(|(k, _)| k == key)
+	}
+
+	/// Returns the first value of the given key.
+	///
+	/// A key without a value returns an empty string.
+	pub fn get(&self, key: &str) -> Option<&str> {
+		self.pairs.iter()
+			.find(|(k, _)| k == key)
+			.map(|(_, v)| v.as_deref().unwrap_or(""))
+	}
+
+	/// Returns all values of the given key.
+	///
+	/// A key without a value returns an empty string.
+	pub fn get_all<'a>(
+		&'a self,
+		key: &'a str
+	) -> impl Iterator<Item=&'a str> + 'a {
+		self.pairs.iter()
+			.filter(move |(k, _)| k == key)
+			.map(|(_, v)| v.as_deref().unwrap_or(""))
+	}
+
+	/// Appends a new key value pair, keeping existing values of that key.
+	pub fn append(&mut self, key: impl Into<String>, val: impl Into<String>) {
+		self.pairs.push((key.into(), Some(val.into())));
+	}
+
+	/// Appends a key without a value.
+	pub fn append_key(&mut self, key: impl Into<String>) {
+		self.pairs.push((key.into(), None));
+	}
+
+	/// Sets the value of a key, removing all previous values of that key.
+	///
+	/// The first occurrence of the key keeps its position, if the key is not
+	/// present the pair is appended.
+	pub fn set(&mut self, key: impl Into<String>, val: impl Into<String>) {
+		let key = key.into();
+		let Some(pos) = self.pairs.iter().position(|(k, _)| *k == key) else {
+			self.pairs.push((key, Some(val.into())));
+			return
+		};
+
+		self.pairs[pos].1 = Some(val.into());
+
+		// keep only the first occurrence
+		let mut seen = false;
+		self.pairs.retain(|(k, _)| {
+			*k != key || !std::mem::replace(&mut seen, true)
+		});
+	}
+
+	/// Removes all values of the given key, returning true if the key was
+	/// present.
+	pub fn remove(&mut self, key: &str) -> bool {
+		let prev_len = self.pairs.len();
+		self.pairs.retain(|(k, _)| k != key);
+		prev_len != self.pairs.len()
+	}
+
+	/// Returns an iterator over all pairs, a key without a value returns
+	/// `None`.
+	pub fn iter(&self) -> impl Iterator<Item=(&str, Option<&str>)> {
+		self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_deref()))
+	}
+
+	/// Returns the encoded query string (without the leading `?`).
+	pub fn to_query_string(&self) -> String {
+		self.to_string()
+	}
+}
+
+impl fmt::Display for Query {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, (k, v)) in self.pairs.iter().enumerate() {
+			if i > 0 {
+				f.write_str("&")?;
+			}
+
+			encode(f, k)?;
+
+			if let Some(v) = v {
+				f.write_str("=")?;
+				encode(f, v)?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl<K, V> FromIterator<(K, V)> for Query
+where
+	K: Into<String>,
+	V: Into<String>
+{
+	fn from_iter<I>(iter: I) -> Self
+	where I: IntoIterator<Item=(K, V)> {
+		Self {
+			pairs: iter.into_iter()
+				.map(|(k, v)| (k.into(), Some(v.into())))
+				.collect()
+		}
+	}
+}
+
+fn decode(s: &str) -> String {
+	decode_query_component(s).into_owned()
+}
+
+// form encoding, spaces are written as +
+fn encode(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+	for part in form_urlencoded::byte_serialize(s.as_bytes()) {
+		f.write_str(part)?;
+	}
+
+	Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_and_serialize() {
+
+		let mut query = Query::parse("a=1&a=2&debug&name=hello+w%C3%B6rld");
+		assert_eq!(query.get("a").unwrap(), "1");
+		assert_eq!(query.get_all("a").collect::<Vec<_>>(), ["1", "2"]);
+		assert_eq!(query.get("debug").unwrap(), "");
+		assert_eq!(query.get("name").unwrap(), "hello wörld");
+
+		query.set("a", "3");
+		query.remove("name");
+		query.append("q", "a&b c");
+		assert_eq!(query.to_query_string(), "a=3&debug&q=a%26b+c");
+
+	}
+
+	#[test]
+	fn test_set_keeps_order() {
+
+		let mut query = Query::parse("a=1&b=2&a=3&c&a=4");
+		query.set("a", "5");
+		assert_eq!(query.to_query_string(), "a=5&b=2&c");
+
+		query.set("c", "6");
+		query.set("d", "7");
+		assert_eq!(query.to_query_string(), "a=5&b=2&c=6&d=7");
+
+	}
+
+}
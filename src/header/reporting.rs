@@ -0,0 +1,309 @@
+//! Types related to the Reporting API and Network Error Logging headers
+//! `Reporting-Endpoints`, `Report-To` and `NEL`.
+
+use super::HeaderValue;
+
+use std::fmt;
+use std::fmt::Write;
+
+
+/// Http `Reporting-Endpoints` header, maps endpoint names to urls.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportingEndpoints {
+	endpoints: Vec<(String, String)>
+}
+
+impl ReportingEndpoints {
+	/// Creates a new `ReportingEndpoints` without any endpoint.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds an endpoint, the name needs to be a structured field key like
+	/// `default` or `csp-endpoint`.
+	///
+	/// ## Panics
+	/// If the name is not a valid key.
+	pub fn endpoint(
+		mut self,
+		name: impl Into<String>,
+		url: impl Into<String>
+	) -> Self {
+		let name = name.into();
+		assert!(is_key(&name), "invalid endpoint name {name:?}");
+		self.endpoints.push((name, url.into()));
+		self
+	}
+
+	/// Returns the url of an endpoint.
+	pub fn get(&self, name: &str) -> Option<&str> {
+		self.endpoints.iter()
+			.find(|(n, _)| n == name)
+			.map(|(_, url)| url.as_str())
+	}
+}
+
+impl fmt::Display for ReportingEndpoints {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, (name, url)) in self.endpoints.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{name}=\"")?;
+			for c in url.chars() {
+				match c {
+					'"' | '\\' => write!(f, "\\{c}")?,
+					c => f.write_char(c)?
+				}
+			}
+			f.write_char('"')?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Http `Report-To` header (the legacy version of `Reporting-Endpoints`)
+/// describing one endpoint group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportTo {
+	group: Option<String>,
+	max_age: u64,
+	endpoints: Vec<String>,
+	include_subdomains: bool
+}
+
+impl ReportTo {
+	/// Creates a new `ReportTo` which is valid for `max_age` seconds.
+	pub fn new(max_age: u64) -> Self {
+		Self {
+			group: None,
+			max_age,
+			endpoints: vec![],
+			include_subdomains: false
+		}
+	}
+
+	/// Sets the group name, without a name the group is called `default`.
+	pub fn group(mut self, group: impl Into<String>) -> Self {
+		self.group = Some(group.into());
+		self
+	}
+
+	/// Adds an endpoint url.
+	pub fn endpoint(mut self, url: impl Into<String>) -> Self {
+		self.endpoints.push(url.into());
+		self
+	}
+
+	/// Sets if the group applies to all subdomains.
+	pub fn include_subdomains(mut self, include: bool) -> Self {
+		self.include_subdomains = include;
+		self
+	}
+}
+
+impl fmt::Display for ReportTo {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_char('{')?;
+		if let Some(group) = &self.group {
+			f.write_str("\"group\":")?;
+			write_json_str(f, group)?;
+			f.write_char(',')?;
+		}
+
+		write!(f, "\"max_age\":{},\"endpoints\":[", self.max_age)?;
+		for (i, url) in self.endpoints.iter().enumerate() {
+			if i > 0 {
+				f.write_char(',')?;
+			}
+			f.write_str("{\"url\":")?;
+			write_json_str(f, url)?;
+			f.write_char('}')?;
+		}
+		f.write_char(']')?;
+
+		if self.include_subdomains {
+			f.write_str(",\"include_subdomains\":true")?;
+		}
+
+		f.write_char('}')
+	}
+}
+
+/// Http `NEL` header which enables network error logging.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nel {
+	report_to: String,
+	max_age: u64,
+	include_subdomains: bool,
+	success_fraction: Option<f64>,
+	failure_fraction: Option<f64>
+}
+
+impl Nel {
+	/// Creates a new `Nel` which sends reports to the given group or
+	/// endpoint name and is valid for `max_age` seconds.
+	///
+	/// A `max_age` of 0 removes the policy.
+	pub fn new(report_to: impl Into<String>, max_age: u64) -> Self {
+		Self {
+			report_to: report_to.into(),
+			max_age,
+			include_subdomains: false,
+			success_fraction: None,
+			failure_fraction: None
+		}
+	}
+
+	/// Sets if the policy applies to all subdomains.
+	pub fn include_subdomains(mut self, include: bool) -> Self {
+		self.include_subdomains = include;
+		self
+	}
+
+	/// Sets the fraction of successful requests which should be reported,
+	/// defaults to 0.
+	///
+	/// ## Panics
+	/// If the fraction is not between 0 and 1.
+	pub fn success_fraction(mut self, fraction: f64) -> Self {
+		assert_fraction(fraction);
+		self.success_fraction = Some(fraction);
+		self
+	}
+
+	/// Sets the fraction of failed requests which should be reported,
+	/// defaults to 1.
+	///
+	/// ## Panics
+	/// If the fraction is not between 0 and 1.
+	pub fn failure_fraction(mut self, fraction: f64) -> Self {
+		assert_fraction(fraction);
+		self.failure_fraction = Some(fraction);
+		self
+	}
+}
+
+impl fmt::Display for Nel {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("{\"report_to\":")?;
+		write_json_str(f, &self.report_to)?;
+		write!(f, ",\"max_age\":{}", self.max_age)?;
+
+		if self.include_subdomains {
+			f.write_str(",\"include_subdomains\":true")?;
+		}
+
+		if let Some(fraction) = self.success_fraction {
+			write!(f, ",\"success_fraction\":{fraction}")?;
+		}
+
+		if let Some(fraction) = self.failure_fraction {
+			write!(f, ",\"failure_fraction\":{fraction}")?;
+		}
+
+		f.write_char('}')
+	}
+}
+
+macro_rules! impl_into_header_value {
+	($($ty:ty),*) => ($(
+		/// Non ascii characters are escaped, so the value is always valid.
+		impl From<$ty> for HeaderValue {
+			fn from(v: $ty) -> Self {
+				HeaderValue::from_str(&v.to_string()).unwrap()
+			}
+		}
+	)*)
+}
+
+impl_into_header_value!(ReportTo, Nel);
+
+/// Non ascii characters in urls need to be percent encoded.
+impl TryFrom<ReportingEndpoints> for HeaderValue {
+	type Error = super::values::InvalidHeaderValue;
+
+	fn try_from(e: ReportingEndpoints) -> Result<Self, Self::Error> {
+		e.to_string().try_into()
+	}
+}
+
+fn assert_fraction(fraction: f64) {
+	assert!(
+		(0.0..=1.0).contains(&fraction),
+		"fraction needs to be between 0 and 1"
+	);
+}
+
+/// Writes a json string escaping everything which is not printable ascii.
+fn write_json_str(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+	f.write_char('"')?;
+	for c in s.chars() {
+		match c {
+			'"' | '\\' => write!(f, "\\{c}")?,
+			' '..='~' => f.write_char(c)?,
+			c => {
+				let mut buf = [0; 2];
+				for unit in c.encode_utf16(&mut buf) {
+					write!(f, "\\u{unit:04x}")?;
+				}
+			}
+		}
+	}
+	f.write_char('"')
+}
+
+/// Returns true if the string is a structured field key.
+fn is_key(s: &str) -> bool {
+	s.starts_with(|c: char| c.is_ascii_lowercase() || c == '*') &&
+		s.bytes().all(|b| {
+			b.is_ascii_lowercase() || b.is_ascii_digit() ||
+				b"_-.*".contains(&b)
+		})
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_headers() {
+
+		let endpoints = ReportingEndpoints::new()
+			.endpoint("default", "https://example.com/reports")
+			.endpoint("csp-endpoint", "https://example.com/csp");
+		assert_eq!(
+			endpoints.to_string(),
+			"default=\"https://example.com/reports\", \
+			csp-endpoint=\"https://example.com/csp\""
+		);
+		assert_eq!(
+			endpoints.get("default"),
+			Some("https://example.com/reports")
+		);
+
+		let report_to = ReportTo::new(10886400)
+			.group("network-errors")
+			.endpoint("https://example.com/ü")
+			.include_subdomains(true);
+		assert_eq!(
+			report_to.to_string(),
+			"{\"group\":\"network-errors\",\"max_age\":10886400,\
+			\"endpoints\":[{\"url\":\"https://example.com/\\u00fc\"}],\
+			\"include_subdomains\":true}"
+		);
+		let _ = HeaderValue::from(report_to);
+
+		let nel = Nel::new("network-errors", 2592000)
+			.failure_fraction(0.5);
+		assert_eq!(
+			nel.to_string(),
+			"{\"report_to\":\"network-errors\",\"max_age\":2592000,\
+			\"failure_fraction\":0.5}"
+		);
+
+	}
+
+}
@@ -0,0 +1,74 @@
+//! Serde helpers for `StatusCode` and `Method`.
+//!
+//! Use them on a field with
+//! `#[serde(with = "fire_http_representation::header::serde_with::method")]`.
+
+/// Serializes a `StatusCode` as an integer like `404`.
+pub mod status_code {
+	use crate::header::StatusCode;
+
+	use serde::{Serializer, Deserializer, Deserialize};
+	use serde::de::Error;
+
+	pub fn serialize<S>(code: &StatusCode, s: S) -> Result<S::Ok, S::Error>
+	where S: Serializer {
+		s.serialize_u16(code.as_u16())
+	}
+
+	pub fn deserialize<'de, D>(d: D) -> Result<StatusCode, D::Error>
+	where D: Deserializer<'de> {
+		let code = u16::deserialize(d)?;
+		StatusCode::from_u16(code).map_err(D::Error::custom)
+	}
+}
+
+/// Serializes a `Method` as a string like `"GET"`.
+pub mod method {
+	use crate::header::Method;
+
+	use serde::{Serializer, Deserializer, Deserialize};
+	use serde::de::Error;
+
+	pub fn serialize<S>(method: &Method, s: S) -> Result<S::Ok, S::Error>
+	where S: Serializer {
+		s.serialize_str(method.as_str())
+	}
+
+	pub fn deserialize<'de, D>(d: D) -> Result<Method, D::Error>
+	where D: Deserializer<'de> {
+		let method = String::deserialize(d)?;
+		Method::from_bytes(method.as_bytes()).map_err(D::Error::custom)
+	}
+}
+
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+	use crate::header::{StatusCode, Method};
+
+	use serde::{Serialize, Deserialize};
+
+	#[test]
+	fn test_roundtrip() {
+
+		#[derive(Debug, PartialEq, Serialize, Deserialize)]
+		struct Config {
+			#[serde(with = "super::status_code")]
+			status: StatusCode,
+			#[serde(with = "super::method")]
+			method: Method
+		}
+
+		let json = r#"{"status":404,"method":"PATCH"}"#;
+		let config: Config = serde_json::from_str(json).unwrap();
+		assert_eq!(config.status, StatusCode::NOT_FOUND);
+		assert_eq!(config.method, Method::PATCH);
+		assert_eq!(serde_json::to_string(&config).unwrap(), json);
+
+		assert!(serde_json::from_str::<Config>(
+			r#"{"status":1000,"method":"GET"}"#
+		).is_err());
+
+	}
+
+}
@@ -0,0 +1,354 @@
+//! Types related to the W3C Trace Context headers `traceparent` and
+//! `tracestate`.
+
+use super::HeaderValue;
+
+use std::fmt;
+use std::str::FromStr;
+
+
+/// The maximum number of entries in a `tracestate` header.
+pub const MAX_TRACE_STATE_ENTRIES: usize = 32;
+
+/// Http `traceparent` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceParent {
+	pub version: u8,
+	pub trace_id: [u8; 16],
+	pub parent_id: [u8; 8],
+	pub flags: u8
+}
+
+impl TraceParent {
+	/// The sampled flag.
+	pub const SAMPLED: u8 = 0x01;
+
+	/// Creates a new version 0 `TraceParent`.
+	///
+	/// Returns `None` if the trace id or the parent id are all zeros.
+	pub fn new(
+		trace_id: [u8; 16],
+		parent_id: [u8; 8],
+		flags: u8
+	) -> Option<Self> {
+		let me = Self { version: 0, trace_id, parent_id, flags };
+		me.is_valid().then_some(me)
+	}
+
+	fn is_valid(&self) -> bool {
+		self.version != 0xff &&
+		self.trace_id != [0; 16] &&
+		self.parent_id != [0; 8]
+	}
+
+	/// Returns true if the sampled flag is set.
+	pub fn is_sampled(&self) -> bool {
+		self.flags & Self::SAMPLED != 0
+	}
+
+	/// Returns the trace id as lowercase hex.
+	pub fn trace_id_hex(&self) -> String {
+		to_hex(&self.trace_id)
+	}
+
+	/// Returns the parent id as lowercase hex.
+	pub fn parent_id_hex(&self) -> String {
+		to_hex(&self.parent_id)
+	}
+
+	/// Returns a `TraceParent` with the same trace but a new parent id, to
+	/// be sent to downstream services.
+	///
+	/// Returns `None` if the parent id is all zeros.
+	pub fn with_parent_id(&self, parent_id: [u8; 8]) -> Option<Self> {
+		Self::new(self.trace_id, parent_id, self.flags)
+	}
+}
+
+impl fmt::Display for TraceParent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f, "{:02x}-{}-{}-{:02x}",
+			self.version,
+			self.trace_id_hex(),
+			self.parent_id_hex(),
+			self.flags
+		)
+	}
+}
+
+impl FromStr for TraceParent {
+	type Err = InvalidTraceContext;
+
+	fn from_str(s: &str) -> Result<Self, InvalidTraceContext> {
+		let s = s.trim();
+		let mut parts = s.split('-');
+
+		let mut version = [0u8; 1];
+		let mut trace_id = [0u8; 16];
+		let mut parent_id = [0u8; 8];
+		let mut flags = [0u8; 1];
+
+		for buf in [
+			&mut version[..],
+			&mut trace_id[..],
+			&mut parent_id[..],
+			&mut flags[..]
+		] {
+			let part = parts.next().ok_or(InvalidTraceContext)?;
+			from_hex(part, buf).ok_or(InvalidTraceContext)?;
+		}
+
+		let me = Self {
+			version: version[0],
+			trace_id,
+			parent_id,
+			flags: flags[0]
+		};
+
+		// version 0 does not allow additional fields, future versions might
+		// add them
+		let valid_len = match me.version {
+			0 => s.len() == 55,
+			_ => s.len() == 55 || s.as_bytes().get(55) == Some(&b'-')
+		};
+
+		if !valid_len || !me.is_valid() {
+			return Err(InvalidTraceContext)
+		}
+
+		Ok(me)
+	}
+}
+
+impl From<TraceParent> for HeaderValue {
+	fn from(tp: TraceParent) -> Self {
+		// only contains hex and dashes
+		HeaderValue::from_str(&tp.to_string()).unwrap()
+	}
+}
+
+/// Http `tracestate` header.
+///
+/// Contains vendor specific key value pairs, the most recently updated entry
+/// comes first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceState {
+	entries: Vec<(String, String)>
+}
+
+impl TraceState {
+	/// Creates a new empty `TraceState`.
+	pub fn new() -> Self {
+		Self { entries: vec![] }
+	}
+
+	/// Returns the value of the given key.
+	pub fn get(&self, key: &str) -> Option<&str> {
+		self.entries.iter()
+			.find(|(k, _)| k == key)
+			.map(|(_, v)| v.as_str())
+	}
+
+	/// Inserts or updates a key, moving it to the front.
+	///
+	/// If there are more than 32 entries the last one is dropped.
+	///
+	/// Returns an error if the key or the value are not valid.
+	pub fn insert(
+		&mut self,
+		key: impl Into<String>,
+		val: impl Into<String>
+	) -> Result<(), InvalidTraceContext> {
+		let key = key.into();
+		let val = val.into();
+		if !valid_key(&key) || !valid_value(&val) {
+			return Err(InvalidTraceContext)
+		}
+
+		self.remove(&key);
+		self.entries.insert(0, (key, val));
+		self.entries.truncate(MAX_TRACE_STATE_ENTRIES);
+
+		Ok(())
+	}
+
+	/// Removes a key returning it's value.
+	pub fn remove(&mut self, key: &str) -> Option<String> {
+		let pos = self.entries.iter().position(|(k, _)| k == key)?;
+		Some(self.entries.remove(pos).1)
+	}
+
+	/// Returns the number of entries.
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Returns true if there are no entries.
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Returns an iterator over all keys and values.
+	pub fn iter(&self) -> impl Iterator<Item=(&str, &str)> {
+		self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+	}
+}
+
+impl fmt::Display for TraceState {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, (k, v)) in self.entries.iter().enumerate() {
+			if i > 0 {
+				f.write_str(",")?;
+			}
+
+			write!(f, "{k}={v}")?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Parses a `tracestate` value, empty list members are ignored.
+impl FromStr for TraceState {
+	type Err = InvalidTraceContext;
+
+	fn from_str(s: &str) -> Result<Self, InvalidTraceContext> {
+		let mut entries: Vec<(String, String)> = vec![];
+
+		for member in s.split(',').map(str::trim).filter(|m| !m.is_empty()) {
+			let (key, val) = member.split_once('=')
+				.ok_or(InvalidTraceContext)?;
+
+			if !valid_key(key) || !valid_value(val) ||
+				entries.iter().any(|(k, _)| k == key)
+			{
+				return Err(InvalidTraceContext)
+			}
+
+			entries.push((key.to_string(), val.to_string()));
+		}
+
+		if entries.len() > MAX_TRACE_STATE_ENTRIES {
+			return Err(InvalidTraceContext)
+		}
+
+		Ok(Self { entries })
+	}
+}
+
+impl TryFrom<TraceState> for HeaderValue {
+	type Error = super::values::InvalidHeaderValue;
+
+	fn try_from(ts: TraceState) -> Result<Self, Self::Error> {
+		ts.to_string().try_into()
+	}
+}
+
+/// Returned if a `traceparent` or `tracestate` value is not valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTraceContext;
+
+impl fmt::Display for InvalidTraceContext {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("invalid trace context")
+	}
+}
+
+impl std::error::Error for InvalidTraceContext {}
+
+fn valid_key(key: &str) -> bool {
+	let simple = |s: &str, max: usize, first_digit: bool| {
+		let mut chars = s.chars();
+		let first_ok = chars.next().map(|c| {
+			c.is_ascii_lowercase() || (first_digit && c.is_ascii_digit())
+		}).unwrap_or(false);
+
+		first_ok && s.len() <= max && chars.all(|c| {
+			c.is_ascii_lowercase() || c.is_ascii_digit() ||
+			matches!(c, '_' | '-' | '*' | '/')
+		})
+	};
+
+	match key.split_once('@') {
+		Some((tenant, system)) => {
+			simple(tenant, 241, true) && simple(system, 14, false)
+		},
+		None => simple(key, 256, false)
+	}
+}
+
+fn valid_value(val: &str) -> bool {
+	!val.is_empty() &&
+	val.len() <= 256 &&
+	!val.ends_with(' ') &&
+	val.chars().all(|c| {
+		matches!(c, ' '..='~') && c != ',' && c != '='
+	})
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str, buf: &mut [u8]) -> Option<()> {
+	// only lowercase is allowed
+	let valid = s.len() == buf.len() * 2 && s.bytes().all(|c| {
+		c.is_ascii_digit() || matches!(c, b'a'..=b'f')
+	});
+	if !valid {
+		return None
+	}
+
+	for (i, b) in buf.iter_mut().enumerate() {
+		*b = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+	}
+
+	Some(())
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_trace_parent() {
+
+		let s = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+		let tp: TraceParent = s.parse().unwrap();
+		assert_eq!(tp.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+		assert!(tp.is_sampled());
+		assert_eq!(tp.to_string(), s);
+
+		for invalid in [
+			"00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+			"00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+			"00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+			"ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+			"00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-ab"
+		] {
+			assert!(invalid.parse::<TraceParent>().is_err(), "{invalid}");
+		}
+
+		let future = "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+		assert!(format!("{future}-ab").parse::<TraceParent>().is_ok());
+
+	}
+
+	#[test]
+	fn test_trace_state() {
+
+		let mut ts: TraceState = "rojo=00f067aa0ba902b7, congo=t61rcWkgMzE"
+			.parse().unwrap();
+		assert_eq!(ts.get("congo").unwrap(), "t61rcWkgMzE");
+
+		ts.insert("congo", "abc").unwrap();
+		assert_eq!(ts.to_string(), "congo=abc,rojo=00f067aa0ba902b7");
+
+		assert!(ts.insert("Upper", "abc").is_err());
+		assert!("a=1,a=2".parse::<TraceState>().is_err());
+
+	}
+
+}
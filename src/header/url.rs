@@ -1,4 +1,5 @@
 use super::Uri;
+use super::uri::{match_path, PathParams};
 
 use http::uri::{Scheme, Authority, PathAndQuery};
 
@@ -68,6 +69,13 @@ impl Url {
 		path.split('/')
 	}
 
+	/// Matches the path against a pattern like `/users/{id}/posts/{post}`.
+	/// 
+	/// See `UriExt::match_pattern`.
+	pub fn match_pattern(&self, pattern: &str) -> Option<PathParams> {
+		match_path(pattern, self.path())
+	}
+
 	/// Returns the query string.
 	pub fn query(&self) -> Option<&str> {
 		self.path_and_query.query()
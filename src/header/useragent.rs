@@ -0,0 +1,194 @@
+//! Types related to the `User-Agent` http header.
+//!
+//! This is not a browser detection library, only the structure of the header
+//! (product tokens and comments) is parsed.
+
+use std::fmt;
+
+
+/// A product token like `curl/8.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Product<'a> {
+	pub name: &'a str,
+	pub version: Option<&'a str>
+}
+
+impl fmt::Display for Product<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.version {
+			Some(version) => write!(f, "{}/{version}", self.name),
+			None => f.write_str(self.name)
+		}
+	}
+}
+
+/// A parsed `User-Agent` header.
+///
+/// Parsing is lenient, anything which is not a valid product or comment is
+/// skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAgent<'a> {
+	raw: &'a str,
+	products: Vec<Product<'a>>,
+	comments: Vec<&'a str>
+}
+
+impl<'a> UserAgent<'a> {
+	/// Parses a `User-Agent` value.
+	pub fn parse(raw: &'a str) -> Self {
+		let mut products = vec![];
+		let mut comments = vec![];
+
+		let mut rest = raw.trim();
+		while !rest.is_empty() {
+			if rest.starts_with('(') {
+				let (comment, end) = match comment_end(rest) {
+					Some(end) => (&rest[1..end - 1], end),
+					// unclosed comment
+					None => (&rest[1..], rest.len())
+				};
+				comments.push(comment.trim());
+				rest = &rest[end..];
+			} else {
+				let end = rest.find(|c: char| c.is_whitespace() || c == '(')
+					.unwrap_or(rest.len());
+				if let Some(product) = parse_product(&rest[..end]) {
+					products.push(product);
+				}
+				rest = &rest[end..];
+			}
+
+			rest = rest.trim_start();
+		}
+
+		Self { raw, products, comments }
+	}
+
+	/// Returns the raw header value.
+	pub fn as_str(&self) -> &'a str {
+		self.raw
+	}
+
+	/// Returns the first product, which is the most significant one.
+	pub fn product(&self) -> Option<&Product<'a>> {
+		self.products.first()
+	}
+
+	/// Returns all products in the order they appear.
+	pub fn products(&self) -> impl Iterator<Item=&Product<'a>> {
+		self.products.iter()
+	}
+
+	/// Returns the content of all comments without the parentheses.
+	pub fn comments(&self) -> impl Iterator<Item=&'a str> + '_ {
+		self.comments.iter().copied()
+	}
+
+	/// Returns the version of the product with the given name, ignoring the
+	/// case.
+	pub fn version_of(&self, name: &str) -> Option<&'a str> {
+		self.products()
+			.find(|p| p.name.eq_ignore_ascii_case(name))
+			.and_then(|p| p.version)
+	}
+
+	/// Returns true if a product has the given name or a comment contains
+	/// it, ignoring the case.
+	pub fn contains(&self, name: &str) -> bool {
+		let name = name.to_ascii_lowercase();
+
+		self.products().any(|p| p.name.eq_ignore_ascii_case(&name)) ||
+			self.comments()
+				.any(|c| c.to_ascii_lowercase().contains(&name))
+	}
+}
+
+impl fmt::Display for UserAgent<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.raw)
+	}
+}
+
+fn parse_product(s: &str) -> Option<Product<'_>> {
+	let (name, version) = match s.split_once('/') {
+		Some((name, version)) => (name, Some(version)),
+		None => (s, None)
+	};
+
+	let valid = |t: &str| !t.is_empty() && t.bytes().all(is_token_byte);
+	if !valid(name) || !version.map(valid).unwrap_or(true) {
+		return None
+	}
+
+	Some(Product { name, version })
+}
+
+/// Returns the index after the closing parenthesis of a comment, comments
+/// can be nested and quoted pairs are skipped. Returns None if it is not
+/// closed.
+fn comment_end(s: &str) -> Option<usize> {
+	let mut depth = 0;
+	let mut escaped = false;
+
+	for (i, b) in s.bytes().enumerate() {
+		match b {
+			_ if escaped => escaped = false,
+			b'\\' => escaped = true,
+			b'(' => depth += 1,
+			b')' => {
+				depth -= 1;
+				if depth == 0 {
+					return Some(i + 1)
+				}
+			},
+			_ => {}
+		}
+	}
+
+	None
+}
+
+fn is_token_byte(b: u8) -> bool {
+	b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse() {
+
+		let ua = UserAgent::parse(
+			"Mozilla/5.0 (X11; Linux x86_64; rv:120.0 (nested)) \
+			Gecko/20100101 Firefox/120.0"
+		);
+		assert_eq!(
+			ua.product(),
+			Some(&Product { name: "Mozilla", version: Some("5.0") })
+		);
+		assert_eq!(ua.products().count(), 3);
+		assert_eq!(ua.version_of("firefox"), Some("120.0"));
+		assert_eq!(
+			ua.comments().collect::<Vec<_>>(),
+			["X11; Linux x86_64; rv:120.0 (nested)"]
+		);
+		assert!(ua.contains("Linux"));
+		assert!(!ua.contains("curl"));
+
+		let ua = UserAgent::parse("curl/8.4.0");
+		assert!(ua.contains("curl"));
+		assert_eq!(ua.product().unwrap().to_string(), "curl/8.4.0");
+
+		// invalid products are skipped
+		let ua = UserAgent::parse("bad/ver/sion Googlebot (unclosed");
+		assert_eq!(
+			ua.products().collect::<Vec<_>>(),
+			[&Product { name: "Googlebot", version: None }]
+		);
+		assert_eq!(ua.comments().collect::<Vec<_>>(), ["unclosed"]);
+
+	}
+
+}
@@ -1,9 +1,21 @@
 use std::fmt;
+use std::sync::Arc;
 use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+use std::collections::HashMap;
+use std::time::{SystemTime, Duration};
 
 pub use http::header::{
 	HeaderValue, HeaderName, AsHeaderName, IntoHeaderName, InvalidHeaderValue
 };
+use http::header::{Entry, ValueIter};
+
+use super::date::HttpDate;
+use super::contenttype::Mime;
+use super::percent::{
+	encode_header_value, decode_header_value, encode_ext_value,
+	decode_ext_value
+};
 
 #[cfg(feature = "json")]
 pub use serde_json::Error as JsonError;
@@ -12,20 +24,138 @@ pub use serde_json::Error as JsonError;
 /// Contains all http header values.
 /// 
 /// This is really similar to `http::header::HeaderMap` except
-/// that is uses IntoHeaderValue for inserting. And it does not allow
-/// multiples values for a given key.
+/// that is uses IntoHeaderValue for inserting. `insert` replaces all values
+/// of a key, multiple values can be added with `extend` and
+/// `ConflictPolicy::Append` and read with `get_all`.
+/// 
+/// ## Preserving case
+/// Header names are always stored lowercase, if you need to forward headers
+/// with their original casing (for example in a proxy) enable
+/// `set_preserve_case` and insert them with `insert_cased`.
+/// 
+/// ## Preserving order
+/// If the iteration order needs to match the insertion order (for example
+/// for deterministic serialization) enable `set_preserve_order`.
 #[derive(Debug, Clone)]
-pub struct HeaderValues(http::HeaderMap<HeaderValue>);
+pub struct HeaderValues {
+	inner: http::HeaderMap<HeaderValue>,
+	// only Some if preserve case is enabled
+	casing: Option<HashMap<HeaderName, Box<str>>>,
+	// only Some if preserve order is enabled
+	order: Option<Vec<HeaderName>>
+}
 
 impl HeaderValues {
 	/// Creates a new empty `HeaderValues`.
 	pub fn new() -> Self {
-		Self(http::HeaderMap::new())
+		Self::from_inner(http::HeaderMap::new())
+	}
+
+	/// Creates a new empty `HeaderValues` with space for at least
+	/// `capacity` values.
+	/// 
+	/// ## Note
+	/// `new` does not allocate, the first insert allocates space for a few
+	/// values which might grow afterwards.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self::from_inner(http::HeaderMap::with_capacity(capacity))
 	}
 
 	/// Creates a new `HeaderValues` from it's inner type.
 	pub fn from_inner(inner: http::HeaderMap<HeaderValue>) -> Self {
-		Self(inner)
+		Self { inner, casing: None, order: None }
+	}
+
+	/// Enables or disables recording the original casing of header names
+	/// inserted with `insert_cased`.
+	/// 
+	/// Disabling it removes all recorded casings.
+	pub fn set_preserve_case(&mut self, preserve: bool) {
+		match (preserve, &self.casing) {
+			(true, None) => self.casing = Some(HashMap::new()),
+			(false, _) => self.casing = None,
+			_ => {}
+		}
+	}
+
+	/// Returns true if the original casing of header names gets recorded.
+	pub fn preserves_case(&self) -> bool {
+		self.casing.is_some()
+	}
+
+	/// Enables or disables tracking the insertion order of keys, which is then
+	/// used by `iter`.
+	/// 
+	/// When enabling, the keys which are already present keep their current
+	/// iteration order.
+	pub fn set_preserve_order(&mut self, preserve: bool) {
+		match (preserve, &self.order) {
+			(true, None) => {
+				self.order = Some(self.inner.keys().cloned().collect())
+			},
+			(false, _) => self.order = None,
+			_ => {}
+		}
+	}
+
+	/// Returns true if the insertion order of keys gets tracked.
+	pub fn preserves_order(&self) -> bool {
+		self.order.is_some()
+	}
+
+	/// Returns the number of values which can be stored without
+	/// reallocating.
+	pub fn capacity(&self) -> usize {
+		self.inner.capacity()
+	}
+
+	/// Reserves space for at least `additional` more values.
+	/// 
+	/// ## Panics
+	/// If the new capacity overflows.
+	pub fn reserve(&mut self, additional: usize) {
+		self.inner.reserve(additional);
+		if let Some(order) = &mut self.order {
+			order.reserve(additional);
+		}
+	}
+
+	/// Removes all values but keeps the allocated memory, so the
+	/// `HeaderValues` can be reused for another response.
+	/// 
+	/// The preserve case and preserve order settings are kept.
+	pub fn clear(&mut self) {
+		self.inner.clear();
+		if let Some(casing) = &mut self.casing {
+			casing.clear();
+		}
+		if let Some(order) = &mut self.order {
+			order.clear();
+		}
+	}
+
+	// overwriting a value also removes the recorded casing of the key
+	fn insert_value<K>(
+		&mut self,
+		key: K,
+		val: HeaderValue
+	) -> Option<HeaderValue>
+	where K: IntoHeaderName {
+		match self.inner.entry(key) {
+			Entry::Occupied(mut e) => {
+				if let Some(casing) = &mut self.casing {
+					casing.remove(e.key());
+				}
+				Some(e.insert(val))
+			},
+			Entry::Vacant(e) => {
+				if let Some(order) = &mut self.order {
+					order.push(e.key().clone());
+				}
+				e.insert(val);
+				None
+			}
+		}
 	}
 
 	/// Insert a new key and value into the header.
@@ -42,7 +172,35 @@ impl HeaderValues {
 		V::Error: fmt::Debug
 	{
 		let val = val.try_into().expect("invalid HeaderValue");
-		self.0.insert(key, val)
+		self.insert_value(key, val)
+	}
+
+	/// Insert a new key and value into the header, recording the casing of
+	/// the key if `set_preserve_case` is enabled.
+	/// 
+	/// If a value to this key is already present
+	/// that value is dropped.
+	/// 
+	/// ## Panics
+	/// If the key is not a valid HeaderName or the value is not a valid
+	/// HeaderValue.
+	pub fn insert_cased<V>(&mut self, key: &str, val: V) -> Option<HeaderValue>
+	where
+		V: TryInto<HeaderValue>,
+		V::Error: fmt::Debug
+	{
+		let name = HeaderName::from_bytes(key.as_bytes())
+			.expect("invalid HeaderName");
+		let val = val.try_into().expect("invalid HeaderValue");
+
+		let prev = self.insert_value(name.clone(), val);
+		if let Some(casing) = &mut self.casing {
+			if name.as_str() != key {
+				casing.insert(name, key.into());
+			}
+		}
+
+		prev
 	}
 
 	/// Insert a new key and value into the header. Returning
@@ -59,11 +217,34 @@ impl HeaderValues {
 		K: IntoHeaderName,
 		V: TryInto<HeaderValue, Error=InvalidHeaderValue>
 	{
-		Ok(self.0.insert(key, val.try_into()?))
+		Ok(self.insert_value(key, val.try_into()?))
+	}
+
+	/// Insert a new key and a value which might come from an untrusted source
+	/// (for example a redirect target or a filename) into the header.
+	/// 
+	/// All control characters including `\r` and `\n` are removed, which
+	/// makes it impossible to inject additional headers.
+	/// 
+	/// If a value to this key is already present
+	/// that value is dropped.
+	pub fn insert_untrusted<K>(
+		&mut self,
+		key: K,
+		val: &str
+	) -> Option<HeaderValue>
+	where K: IntoHeaderName {
+		let val: String = val.chars().filter(|c| !c.is_control()).collect();
+		// only control characters are invalid in from_bytes
+		let val = HeaderValue::from_bytes(val.as_bytes()).unwrap();
+		self.insert_value(key, val)
 	}
 
 	/// Insert a new key and value into the header. Percent encoding
 	/// the value if necessary.
+	/// 
+	/// Every control character including `\r` and `\n` gets encoded, so the
+	/// value can never split the header.
 	pub fn encode_value<K, V>(
 		&mut self,
 		key: K,
@@ -74,7 +255,26 @@ impl HeaderValues {
 		V: IntoEncodedHeaderValue
 	{
 		let val = val.into_encoded_header_value();
-		self.0.insert(key, val)
+		self.insert_value(key, val)
+	}
+
+	/// Insert a new key and value into the header. Encoding the value as an
+	/// RFC 8187 ext-value (`utf-8''value`) stored under the key `key*`.
+	/// 
+	/// In contrast to `encode_value` the encoding is recorded in the key,
+	/// which allows `decode_value_ext` to return raw values unchanged. A raw
+	/// value stored under `key` gets removed.
+	/// 
+	/// ## Panics
+	/// If `key*` is not a valid HeaderName.
+	pub fn encode_value_ext(
+		&mut self,
+		key: &str,
+		val: &str
+	) -> Option<HeaderValue> {
+		let name = ext_name(key).expect("invalid HeaderName");
+		self.remove(key);
+		self.insert_value(name, encode_ext_value(val))
 	}
 
 	/// Insert a new key and a serializeable value. The value will be serialized
@@ -99,13 +299,39 @@ impl HeaderValues {
 	/// Returns the value if it exists.
 	pub fn get<K>(&self, key: K) -> Option<&HeaderValue>
 	where K: AsHeaderName {
-		self.0.get(key)
+		self.inner.get(key)
+	}
+
+	/// Returns all values of a key.
+	/// 
+	/// `HeaderValues` only stores one value per key when inserting, but a
+	/// map created with `from_inner` may contain multiple values, for example
+	/// for `set-cookie`.
+	pub fn get_all<K>(&self, key: K) -> impl Iterator<Item=&HeaderValue>
+	where K: AsHeaderName {
+		self.inner.get_all(key).into_iter()
 	}
 
 	/// Returns the value mutably if it exists.
 	pub fn get_mut<K>(&mut self, key: K) -> Option<&mut HeaderValue>
 	where K: AsHeaderName {
-		self.0.get_mut(key)
+		self.inner.get_mut(key)
+	}
+
+	/// Removes all values of the key returning the first one.
+	pub fn remove<K>(&mut self, key: K) -> Option<HeaderValue>
+	where K: AsHeaderName {
+		let val = self.inner.remove(key)?;
+
+		let inner = &self.inner;
+		if let Some(order) = &mut self.order {
+			order.retain(|name| inner.contains_key(name));
+		}
+		if let Some(casing) = &mut self.casing {
+			casing.retain(|name, _| inner.contains_key(name));
+		}
+
+		Some(val)
 	}
 
 	/// Returns the value as a string if it exists and is valid.
@@ -114,15 +340,62 @@ impl HeaderValues {
 		self.get(key).and_then(|v| v.to_str().ok())
 	}
 
+	/// Returns the value as a string or an error if it does not exist or is
+	/// not valid.
+	fn get_str_or_err<K>(&self, key: K) -> Result<&str, ParseValueError>
+	where K: AsHeaderName {
+		self.get(key)
+			.ok_or(ParseValueError::Missing)?
+			.to_str()
+			.map_err(|_| ParseValueError::NotVisibleAscii)
+	}
+
+	/// Returns the value parsed as an `u64`.
+	pub fn get_u64<K>(&self, key: K) -> Result<u64, ParseValueError>
+	where K: AsHeaderName {
+		let s = self.get_str_or_err(key)?;
+		s.trim().parse()
+			.map_err(|_| ParseValueError::invalid(s, "an unsigned integer"))
+	}
+
+	/// Returns the value parsed as an `HttpDate`.
+	pub fn get_date<K>(&self, key: K) -> Result<HttpDate, ParseValueError>
+	where K: AsHeaderName {
+		let s = self.get_str_or_err(key)?;
+		s.parse().map_err(|_| ParseValueError::invalid(s, "an http date"))
+	}
+
+	/// Returns the `content-length` value.
+	pub fn get_content_length(&self) -> Result<u64, ParseValueError> {
+		self.get_u64(http::header::CONTENT_LENGTH)
+	}
+
+	/// Returns the value parsed as a known `Mime` type, parameters like the
+	/// charset are ignored.
+	pub fn get_mime<K>(&self, key: K) -> Result<Mime, ParseValueError>
+	where K: AsHeaderName {
+		let s = self.get_str_or_err(key)?;
+		let essence = s.split(';').next().unwrap_or("").trim();
+		essence.parse()
+			.map_err(|_| ParseValueError::invalid(s, "a known mime type"))
+	}
+
 	/// Returns the value percent decoded as a string if it exists and is valid.
 	pub fn decode_value<K>(&self, key: K) -> Option<Cow<'_, str>>
 	where K: AsHeaderName {
-		self.get(key)
-			.and_then(|v| {
-				percent_encoding::percent_decode(v.as_bytes())
-					.decode_utf8()
-					.ok()
-			})
+		self.get(key).and_then(decode_header_value)
+	}
+
+	/// Returns the value of `key*` decoded if it exists (see
+	/// `encode_value_ext`) or else the raw value of `key` unchanged.
+	/// 
+	/// Returns `None` if no value exists or the value is not valid.
+	pub fn decode_value_ext(&self, key: &str) -> Option<Cow<'_, str>> {
+		if let Some(val) = ext_name(key).and_then(|name| self.get(name)) {
+			return decode_ext_value(val)
+		}
+
+		self.get_str(key).map(Into::into)
 	}
 
 	/// Deserializes a given value. Returning `None` if the value
@@ -138,28 +411,333 @@ impl HeaderValues {
 		serde_json::from_str(v.as_ref()).ok()
 	}
 
+	/// Returns the header name with it's original casing if it was recorded,
+	/// else the lowercase name.
+	pub fn cased_name<'a>(&'a self, name: &'a HeaderName) -> &'a str {
+		self.casing.as_ref()
+			.and_then(|c| c.get(name))
+			.map(|c| c.as_ref())
+			.unwrap_or_else(|| name.as_str())
+	}
+
+	/// Returns the number of values, a key with multiple values is counted
+	/// once for each value.
+	pub fn len(&self) -> usize {
+		self.inner.len()
+	}
+
+	/// Returns the number of keys.
+	pub fn keys_len(&self) -> usize {
+		self.inner.keys_len()
+	}
+
+	/// Returns true if no key is present.
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
+
+	/// Returns the approximate size in bytes of all headers serialized as
+	/// `name: value\r\n` lines.
+	pub fn byte_len(&self) -> usize {
+		self.inner.iter()
+			.map(|(k, v)| k.as_str().len() + v.len() + 4)
+			.sum()
+	}
+
+	/// Returns an iterator over all header names and their values.
+	/// 
+	/// If `set_preserve_order` is enabled the keys are returned in the order
+	/// they were first inserted, all values of a key are returned together.
+	pub fn iter(&self) -> Iter<'_> {
+		let inner = match &self.order {
+			Some(order) => IterInner::Ordered {
+				order: order.iter(),
+				map: &self.inner,
+				values: None
+			},
+			None => IterInner::Map(self.inner.iter())
+		};
+
+		Iter { inner }
+	}
+
+	/// Returns an iterator over all header names (with the original casing if
+	/// it was recorded) and their values.
+	pub fn iter_cased(&self) -> impl Iterator<Item=(&str, &HeaderValue)> {
+		self.iter().map(|(name, val)| (self.cased_name(name), val))
+	}
+
+	/// Returns a displayable object which only prints the given headers,
+	/// for example `user-agent: curl/8.0, content-length: 5`.
+	/// 
+	/// Headers which are not present are skipped, values which are not
+	/// valid utf8 are printed as `<binary>`.
+	pub fn display_filtered<'a>(
+		&'a self,
+		keys: &'a [&'a str]
+	) -> FilteredHeaderValues<'a> {
+		FilteredHeaderValues { values: self, keys }
+	}
+
 	/// Returns the inner `HeaderMap`.
+	/// 
+	/// ## Note
+	/// Recorded casings are lost.
 	pub fn into_inner(self) -> http::HeaderMap<HeaderValue> {
-		self.0
+		self.inner
+	}
+
+	/// Inserts all keys and values from `other`, `policy` decides what happens
+	/// with keys which are already present.
+	/// 
+	/// Recorded casings of `other` are kept if `set_preserve_case` is enabled.
+	pub fn extend(&mut self, other: HeaderValues, policy: ConflictPolicy) {
+		let keys: Vec<HeaderName> = match &other.order {
+			Some(order) => order.clone(),
+			None => other.inner.keys().cloned().collect()
+		};
+
+		for key in keys {
+			let exists = self.inner.contains_key(&key);
+			if exists && policy == ConflictPolicy::Skip {
+				continue
+			}
+
+			if let Some(casing) = &mut self.casing {
+				let cased = other.cased_name(&key);
+				if cased != key.as_str() {
+					casing.insert(key.clone(), cased.into());
+				}
+			}
+
+			let mut values = other.inner.get_all(&key).iter().cloned();
+			if !exists || policy == ConflictPolicy::Overwrite {
+				let Some(first) = values.next() else { continue };
+				self.insert_value(key.clone(), first);
+			}
+
+			for val in values {
+				self.inner.append(key.clone(), val);
+			}
+		}
+	}
+}
+
+/// Decides how `HeaderValues::extend` handles keys which are already
+/// present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+	/// Keeps the existing values.
+	Skip,
+	/// Replaces the existing values.
+	Overwrite,
+	/// Adds the new values after the existing ones, use this for headers
+	/// which allow multiple values like `set-cookie`.
+	Append
+}
+
+/// ## Panics
+/// If a value is not a valid HeaderValue.
+impl<K, V> From<Vec<(K, V)>> for HeaderValues
+where
+	K: IntoHeaderName,
+	V: TryInto<HeaderValue>,
+	V::Error: fmt::Debug
+{
+	fn from(pairs: Vec<(K, V)>) -> Self {
+		let mut values = Self::new();
+		for (key, val) in pairs {
+			values.insert(key, val);
+		}
+
+		values
+	}
+}
+
+/// Returns the name `key*` which holds the ext-value of `key`.
+fn ext_name(key: &str) -> Option<HeaderName> {
+	HeaderName::from_bytes(format!("{key}*").as_bytes()).ok()
+}
+
+/// `HeaderValues` which can be shared between many responses.
+/// 
+/// Cloning only increments a reference count, the values are copied the
+/// first time they are mutated while being shared (copy on write).
+/// 
+/// Derefs to `HeaderValues`.
+#[derive(Debug, Clone)]
+pub struct SharedHeaderValues(Arc<HeaderValues>);
+
+impl SharedHeaderValues {
+	/// Creates a new `SharedHeaderValues`.
+	pub fn new(values: HeaderValues) -> Self {
+		Self(Arc::new(values))
+	}
+
+	/// Returns true if both share the same values.
+	pub fn ptr_eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+
+	/// Returns the values, cloning them if they are still shared.
+	pub fn into_inner(self) -> HeaderValues {
+		Arc::try_unwrap(self.0).unwrap_or_else(|arc| (*arc).clone())
+	}
+}
+
+impl Deref for SharedHeaderValues {
+	type Target = HeaderValues;
+
+	fn deref(&self) -> &HeaderValues {
+		&self.0
 	}
 }
 
+impl DerefMut for SharedHeaderValues {
+	fn deref_mut(&mut self) -> &mut HeaderValues {
+		Arc::make_mut(&mut self.0)
+	}
+}
+
+impl From<HeaderValues> for SharedHeaderValues {
+	fn from(values: HeaderValues) -> Self {
+		Self::new(values)
+	}
+}
+
+impl From<Arc<HeaderValues>> for SharedHeaderValues {
+	fn from(values: Arc<HeaderValues>) -> Self {
+		Self(values)
+	}
+}
+
+/// Returned from the typed getters of `HeaderValues` like `get_u64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseValueError {
+	/// The key does not exist.
+	Missing,
+	/// The value contains characters which are not visible ascii.
+	NotVisibleAscii,
+	/// The value could not be parsed.
+	Invalid {
+		value: String,
+		expected: &'static str
+	}
+}
+
+impl ParseValueError {
+	fn invalid(value: &str, expected: &'static str) -> Self {
+		Self::Invalid {
+			value: value.to_string(),
+			expected
+		}
+	}
+}
+
+impl fmt::Display for ParseValueError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Missing => f.write_str("header value missing"),
+			Self::NotVisibleAscii => {
+				f.write_str("header value contains non visible ascii")
+			},
+			Self::Invalid { value, expected } => write!(
+				f, "header value {value:?} is not {expected}"
+			)
+		}
+	}
+}
+
+impl std::error::Error for ParseValueError {}
+
+impl<'a> IntoIterator for &'a HeaderValues {
+	type Item = (&'a HeaderName, &'a HeaderValue);
+	type IntoIter = Iter<'a>;
+
+	fn into_iter(self) -> Iter<'a> {
+		self.iter()
+	}
+}
+
+/// An iterator over the keys and values of `HeaderValues`.
+#[derive(Debug)]
+pub struct Iter<'a> {
+	inner: IterInner<'a>
+}
+
+#[derive(Debug)]
+enum IterInner<'a> {
+	Map(http::header::Iter<'a, HeaderValue>),
+	Ordered {
+		order: std::slice::Iter<'a, HeaderName>,
+		map: &'a http::HeaderMap<HeaderValue>,
+		// the values of the key which is currently returned
+		values: Option<(&'a HeaderName, ValueIter<'a, HeaderValue>)>
+	}
+}
+
+impl<'a> Iterator for Iter<'a> {
+	type Item = (&'a HeaderName, &'a HeaderValue);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match &mut self.inner {
+			IterInner::Map(iter) => iter.next(),
+			IterInner::Ordered { order, map, values } => loop {
+				if let Some((name, iter)) = values {
+					if let Some(val) = iter.next() {
+						return Some((name, val))
+					}
+				}
+
+				let name = order.next()?;
+				*values = Some((name, map.get_all(name).iter()));
+			}
+		}
+	}
+}
+
+/// Displays only some headers, see `HeaderValues::display_filtered`.
+#[derive(Clone, Copy)]
+pub struct FilteredHeaderValues<'a> {
+	values: &'a HeaderValues,
+	keys: &'a [&'a str]
+}
+
+impl<'a> FilteredHeaderValues<'a> {
+	fn iter(&self) -> impl Iterator<Item=(&'a str, &'a str)> + '_ {
+		let values = self.values;
+		self.keys.iter().flat_map(move |key| {
+			values.get_all(*key)
+				.map(move |v| (*key, v.to_str().unwrap_or("<binary>")))
+		})
+	}
+}
+
+impl fmt::Display for FilteredHeaderValues<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, (key, value)) in self.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{key}: {value}")?;
+		}
+
+		Ok(())
+	}
+}
 
-fn encode_to_header_value(s: impl AsRef<[u8]>) -> HeaderValue {
-	let s: String = percent_encoding::percent_encode(
-		s.as_ref(),
-		percent_encoding::CONTROLS
-	).collect();
-	// does not allocate again
-	let b: bytes::Bytes = s.into();
-	// now lets make a header value
-	// TODO probably can be changed to
-	// from maybe shared unchecked
-	// but need to check first control covers all cases
-	HeaderValue::from_maybe_shared(b).unwrap()
+impl fmt::Debug for FilteredHeaderValues<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
 }
 
+
 /// Converts a value into a `HeaderValue` and encodes it if necessary.
+/// 
+/// Types which don't implement `TryInto<HeaderValue>`, like `Duration` or
+/// `SystemTime`, can be inserted with `HeaderValues::encode_value`.
 pub trait IntoEncodedHeaderValue {
 	fn into_encoded_header_value(self) -> HeaderValue;
 }
@@ -194,14 +772,64 @@ impl_into_header_value!{
 	u32, self => self.into(),
 	u64, self => self.into(),
 	usize, self => self.into(),
-	String, self => encode_to_header_value(self),
-	Vec<u8>, self => encode_to_header_value(self)
+	String, self => encode_header_value(self),
+	Vec<u8>, self => encode_header_value(self),
+	HttpDate, self => self.into()
+}
+
+/// Encodes the time as an http date.
+/// 
+/// Insert it with `values.encode_value("last-modified", time)`, `insert`
+/// does not accept a `SystemTime`.
+impl IntoEncodedHeaderValue for SystemTime {
+	#[inline]
+	fn into_encoded_header_value(self) -> HeaderValue {
+		HttpDate::from(self).into()
+	}
+}
+
+/// Encodes the duration as whole seconds, for headers like `retry-after` or
+/// `age`.
+/// 
+/// Insert it with `values.encode_value("retry-after", dur)`, `insert` does
+/// not accept a `Duration`.
+impl IntoEncodedHeaderValue for Duration {
+	#[inline]
+	fn into_encoded_header_value(self) -> HeaderValue {
+		self.as_secs().into()
+	}
+}
+
+/// Encodes the time as an http date.
+/// 
+/// Insert it with `values.encode_value("last-modified", time)`, `insert`
+/// does not accept an `OffsetDateTime`.
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl IntoEncodedHeaderValue for time::OffsetDateTime {
+	#[inline]
+	fn into_encoded_header_value(self) -> HeaderValue {
+		SystemTime::from(self).into_encoded_header_value()
+	}
+}
+
+/// Encodes the time as an http date.
+/// 
+/// Insert it with `values.encode_value("last-modified", time)`, `insert`
+/// does not accept a `DateTime`.
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl<Tz: chrono::TimeZone> IntoEncodedHeaderValue for chrono::DateTime<Tz> {
+	#[inline]
+	fn into_encoded_header_value(self) -> HeaderValue {
+		SystemTime::from(self).into_encoded_header_value()
+	}
 }
 
 impl_into_header_value!{ REF,
 	HeaderValue, self => self.clone(),
-	[u8], self => encode_to_header_value(self),
-	str, self => encode_to_header_value(self)
+	[u8], self => encode_header_value(self),
+	str, self => encode_header_value(self)
 }
 
 
@@ -225,6 +853,259 @@ mod tests {
 
 	}
 
+	#[test]
+	fn test_encdec_ext() {
+
+		let mut values = HeaderValues::new();
+		values.insert("Raw", "%F0%9F%9A%80");
+		values.encode_value_ext("Encoded", "🚀 %F0");
+
+		assert_eq!(values.decode_value_ext("Raw").unwrap(), "%F0%9F%9A%80");
+		assert_eq!(values.decode_value_ext("Encoded").unwrap(), "🚀 %F0");
+		assert_eq!(
+			values.get_str("encoded*").unwrap(),
+			"utf-8''%F0%9F%9A%80%20%25F0"
+		);
+		assert!(values.get("encoded").is_none());
+
+		// a raw value which looks like an ext-value is not decoded
+		values.insert("Looks", "utf-8''%41");
+		assert_eq!(values.decode_value_ext("Looks").unwrap(), "utf-8''%41");
+
+		// encoding replaces the raw value
+		values.encode_value_ext("Looks", "B");
+		assert!(values.get("looks").is_none());
+		assert_eq!(values.decode_value_ext("Looks").unwrap(), "B");
+
+		assert!(values.decode_value_ext("Missing").is_none());
+
+	}
+
+	#[test]
+	fn test_encode_time() {
+
+		let mut values = HeaderValues::new();
+		values.encode_value("retry-after", Duration::from_secs(120));
+		assert_eq!(values.get_str("retry-after").unwrap(), "120");
+
+		// fractions of a second are dropped
+		values.encode_value("age", Duration::from_millis(1999));
+		assert_eq!(values.get_str("age").unwrap(), "1");
+
+		let time = SystemTime::UNIX_EPOCH + Duration::from_secs(784111777);
+		values.encode_value("last-modified", time);
+		assert_eq!(
+			values.get_str("last-modified").unwrap(),
+			"Sun, 06 Nov 1994 08:49:37 GMT"
+		);
+
+	}
+
+	#[test]
+	fn test_typed_getters() {
+
+		let mut values = HeaderValues::new();
+		values.insert("content-length", "42");
+		values.insert("content-type", "application/json; charset=utf-8");
+		values.insert("last-modified", "Sun, 06 Nov 1994 08:49:37 GMT");
+		values.insert("age", "-1");
+
+		assert_eq!(values.get_content_length().unwrap(), 42);
+		assert_eq!(values.get_mime("content-type").unwrap(), Mime::JSON);
+		assert_eq!(
+			values.get_date("last-modified").unwrap().unix_secs(),
+			784111777
+		);
+		assert!(matches!(
+			values.get_u64("age"),
+			Err(ParseValueError::Invalid { .. })
+		));
+		assert_eq!(values.get_u64("expires"), Err(ParseValueError::Missing));
+
+	}
+
+	#[test]
+	fn test_display_filtered() {
+
+		let mut values = HeaderValues::new();
+		values.insert("user-agent", "curl/8.0");
+		values.insert("authorization", "Bearer secret");
+		values.insert("content-length", 5);
+
+		let filtered = values.display_filtered(
+			&["content-length", "User-Agent", "content-type"]
+		);
+		assert_eq!(
+			filtered.to_string(),
+			"content-length: 5, User-Agent: curl/8.0"
+		);
+		assert_eq!(
+			format!("{filtered:?}"),
+			r#"{"content-length": "5", "User-Agent": "curl/8.0"}"#
+		);
+
+	}
+
+	#[test]
+	fn test_preserve_case() {
+
+		let mut values = HeaderValues::new();
+		values.set_preserve_case(true);
+		values.insert_cased("X-Legacy-Header", "1");
+		values.insert_cased("content-type", "text/plain");
+
+		assert_eq!(values.get_str("x-legacy-header").unwrap(), "1");
+		let name = HeaderName::from_static("x-legacy-header");
+		assert_eq!(values.cased_name(&name), "X-Legacy-Header");
+		let cased: Vec<_> = values.iter_cased().map(|(k, _)| k).collect();
+		assert!(cased.contains(&"X-Legacy-Header"));
+		assert!(cased.contains(&"content-type"));
+
+		values.set_preserve_case(false);
+		assert_eq!(values.cased_name(&name), "x-legacy-header");
+
+	}
+
+	#[test]
+	fn test_overwrite_clears_case() {
+
+		let name = HeaderName::from_static("x-legacy");
+		let mut values = HeaderValues::new();
+		values.set_preserve_case(true);
+
+		values.insert_cased("X-Legacy", "1");
+		values.insert("x-legacy", "2");
+		assert_eq!(values.cased_name(&name), "x-legacy");
+
+		values.insert_cased("X-Legacy", "1");
+		values.encode_value("x-legacy", "3");
+		assert_eq!(values.cased_name(&name), "x-legacy");
+
+		values.insert_cased("X-Legacy", "1");
+		values.insert_cased("x-legacy", "4");
+		assert_eq!(values.cased_name(&name), "x-legacy");
+
+		values.insert_cased("X-Legacy", "1");
+		let other = HeaderValues::from(vec![("x-legacy", "5")]);
+		values.extend(other.clone(), ConflictPolicy::Skip);
+		assert_eq!(values.cased_name(&name), "X-Legacy");
+		values.extend(other, ConflictPolicy::Overwrite);
+		assert_eq!(values.cased_name(&name), "x-legacy");
+		assert_eq!(values.get_str("x-legacy").unwrap(), "5");
+
+	}
+
+	#[test]
+	fn test_preserve_order() {
+
+		let mut values = HeaderValues::new();
+		values.set_preserve_order(true);
+		for key in ["x-c", "x-a", "x-d", "x-b"] {
+			values.insert(key, "1");
+		}
+		values.insert("x-a", "2");
+
+		let keys: Vec<_> = values.iter().map(|(k, _)| k.as_str()).collect();
+		assert_eq!(keys, ["x-c", "x-a", "x-d", "x-b"]);
+		assert_eq!(values.get_str("x-a").unwrap(), "2");
+
+		let mut cookies = http::HeaderMap::new();
+		cookies.append("set-cookie", HeaderValue::from_static("a=1"));
+		cookies.append("set-cookie", HeaderValue::from_static("b=2"));
+
+		let mut values = HeaderValues::new();
+		values.set_preserve_order(true);
+		values.insert("x-b", "1");
+		let cookies = HeaderValues::from_inner(cookies);
+		values.extend(cookies, ConflictPolicy::Append);
+		values.insert("x-a", "1");
+		values.extend(
+			HeaderValues::from(vec![("set-cookie", "c=3")]),
+			ConflictPolicy::Append
+		);
+
+		let pairs: Vec<_> = values.iter()
+			.map(|(k, v)| (k.as_str(), v.to_str().unwrap()))
+			.collect();
+		assert_eq!(pairs, [
+			("x-b", "1"),
+			("set-cookie", "a=1"),
+			("set-cookie", "b=2"),
+			("set-cookie", "c=3"),
+			("x-a", "1")
+		]);
+
+	}
+
+	#[test]
+	fn test_insert_untrusted() {
+
+		let mut values = HeaderValues::new();
+		values.insert_untrusted("location", "/a\r\nset-cookie: x=1\0");
+		assert_eq!(values.get_str("location").unwrap(), "/aset-cookie: x=1");
+
+		values.insert_untrusted("x-file", "bücher.txt");
+		let val = values.get("x-file").unwrap();
+		assert_eq!(val.as_bytes(), "bücher.txt".as_bytes());
+
+	}
+
+	#[test]
+	fn test_extend() {
+
+		let defaults = HeaderValues::from(vec![
+			("server", "fire"),
+			("x-frame-options", "DENY")
+		]);
+
+		let mut values = HeaderValues::from(vec![("server", "custom")]);
+		values.extend(defaults.clone(), ConflictPolicy::Skip);
+		assert_eq!(values.get_str("server").unwrap(), "custom");
+		assert_eq!(values.get_str("x-frame-options").unwrap(), "DENY");
+
+		values.extend(defaults.clone(), ConflictPolicy::Overwrite);
+		assert_eq!(values.get_str("server").unwrap(), "fire");
+
+		values.extend(defaults, ConflictPolicy::Append);
+		assert_eq!(values.get_all("server").count(), 2);
+
+	}
+
+	#[test]
+	fn test_shared() {
+
+		let shared = SharedHeaderValues::new(
+			HeaderValues::from(vec![("server", "fire")])
+		);
+
+		let mut a = shared.clone();
+		assert!(a.ptr_eq(&shared));
+		assert_eq!(a.get_str("server").unwrap(), "fire");
+
+		a.insert("x-a", "1");
+		assert!(!a.ptr_eq(&shared));
+		assert!(shared.get("x-a").is_none());
+
+	}
+
+	#[test]
+	fn test_clear() {
+
+		let mut values = HeaderValues::with_capacity(8);
+		let capacity = values.capacity();
+		assert!(capacity >= 8);
+
+		values.set_preserve_order(true);
+		values.insert("x-a", "1");
+		values.clear();
+		assert!(values.is_empty());
+		assert_eq!(values.capacity(), capacity);
+
+		values.insert("x-b", "1");
+		assert_eq!(values.iter().count(), 1);
+
+	}
+
 	#[cfg(feature="json")]
 	#[test]
 	fn test_serde() {
@@ -243,7 +1124,10 @@ mod tests {
 		values.serialize_value("Value", &val).unwrap();
 
 		let s = values.get_str("Value").unwrap();
-		assert_eq!(s, "{\"text\":\"%F0%9F%9A%80 Rocket\",\"number\":42}");
+		assert_eq!(
+			s,
+			"{%22text%22:%22%F0%9F%9A%80 Rocket%22,%22number%22:42}"
+		);
 
 		let n_val: Value = values.deserialize_value("Value").unwrap();
 		assert_eq!(n_val, val);
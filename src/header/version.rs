@@ -0,0 +1,60 @@
+//! Helpers for working with a `Version`.
+
+use super::Version;
+
+
+/// Adds helper methods to `Version`.
+pub trait VersionExt {
+	/// Returns the version negotiated with the given ALPN protocol id, for
+	/// example `h2`, `http/1.1` or `h3`.
+	fn from_alpn(alpn: &[u8]) -> Option<Version>
+	where Self: Sized;
+
+	/// Returns the ALPN protocol id of this version, versions before
+	/// http/1.1 don't have one.
+	fn as_alpn(&self) -> Option<&'static [u8]>;
+}
+
+impl VersionExt for Version {
+	fn from_alpn(alpn: &[u8]) -> Option<Version> {
+		match alpn {
+			b"http/1.0" => Some(Version::HTTP_10),
+			b"http/1.1" => Some(Version::HTTP_11),
+			b"h2" => Some(Version::HTTP_2),
+			b"h3" => Some(Version::HTTP_3),
+			_ => None
+		}
+	}
+
+	fn as_alpn(&self) -> Option<&'static [u8]> {
+		match *self {
+			Version::HTTP_10 => Some(b"http/1.0"),
+			Version::HTTP_11 => Some(b"http/1.1"),
+			Version::HTTP_2 => Some(b"h2"),
+			Version::HTTP_3 => Some(b"h3"),
+			_ => None
+		}
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_alpn() {
+
+		for v in [Version::HTTP_10, Version::HTTP_11, Version::HTTP_2] {
+			assert_eq!(Version::from_alpn(v.as_alpn().unwrap()), Some(v));
+		}
+
+		assert_eq!(Version::from_alpn(b"h3"), Some(Version::HTTP_3));
+		assert_eq!(Version::from_alpn(b"spdy/1"), None);
+		// h2c is not a valid tls alpn id (RFC 9113 section 3.1)
+		assert_eq!(Version::from_alpn(b"h2c"), None);
+		assert_eq!(Version::HTTP_09.as_alpn(), None);
+
+	}
+
+}
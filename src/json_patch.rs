@@ -0,0 +1,295 @@
+//! JSON Patch (RFC 6902) and JSON Merge Patch (RFC 7396).
+//!
+//! A JSON Patch is sent with the content type `application/json-patch+json`
+//! and a merge patch with `application/merge-patch+json`.
+
+use crate::Body;
+
+use std::{io, fmt};
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+
+/// A single JSON Patch operation.
+///
+/// Paths are JSON Pointers (RFC 6901) like `/users/0/name`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+	Add { path: String, value: Value },
+	Remove { path: String },
+	Replace { path: String, value: Value },
+	Move { from: String, path: String },
+	Copy { from: String, path: String },
+	Test { path: String, value: Value }
+}
+
+/// A list of JSON Patch operations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+impl JsonPatch {
+	/// Applies all operations to the target.
+	///
+	/// If an operation fails the target is left unchanged.
+	pub fn apply(&self, target: &mut Value) -> Result<(), PatchError> {
+		let mut patched = target.clone();
+		for op in &self.0 {
+			apply_op(&mut patched, op)?;
+		}
+
+		*target = patched;
+		Ok(())
+	}
+}
+
+/// Applies a JSON Merge Patch to the target.
+///
+/// `null` values in the patch remove the member, objects are merged
+/// recursively and every other value replaces the target.
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+	let Value::Object(patch) = patch else {
+		*target = patch.clone();
+		return
+	};
+
+	if !target.is_object() {
+		*target = Value::Object(Default::default());
+	}
+	let target = target.as_object_mut().unwrap();
+
+	for (key, value) in patch {
+		if value.is_null() {
+			target.remove(key);
+		} else {
+			merge_patch(target.entry(key).or_insert(Value::Null), value);
+		}
+	}
+}
+
+/// An error returned by `JsonPatch::apply`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PatchError {
+	/// The path is not a valid JSON Pointer.
+	InvalidPointer(String),
+	/// The path does not exist in the target.
+	PathNotFound(String),
+	/// A `test` operation did not match.
+	TestFailed(String),
+	/// A value cannot be moved into one of its children.
+	MoveIntoChild(String)
+}
+
+impl fmt::Display for PatchError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::InvalidPointer(p) => write!(f, "invalid json pointer {p:?}"),
+			Self::PathNotFound(p) => write!(f, "path {p:?} not found"),
+			Self::TestFailed(p) => write!(f, "test at {p:?} failed"),
+			Self::MoveIntoChild(p) => {
+				write!(f, "cannot move {p:?} into one of its children")
+			}
+		}
+	}
+}
+
+impl std::error::Error for PatchError {}
+
+impl Body {
+	/// Deserializes the body as a JSON Patch.
+	#[cfg_attr(docsrs, doc(cfg(feature = "json-patch")))]
+	pub async fn deserialize_json_patch(self) -> io::Result<JsonPatch> {
+		self.deserialize().await
+	}
+}
+
+fn apply_op(target: &mut Value, op: &PatchOperation) -> Result<(), PatchError> {
+	match op {
+		PatchOperation::Add { path, value } => add(target, path, value.clone()),
+		PatchOperation::Remove { path } => remove(target, path).map(|_| ()),
+		PatchOperation::Replace { path, value } => {
+			*pointer_mut(target, path)? = value.clone();
+			Ok(())
+		},
+		PatchOperation::Move { from, path } => {
+			if from == path {
+				return pointer_mut(target, from).map(|_| ())
+			}
+
+			if path.starts_with(&format!("{from}/")) {
+				return Err(PatchError::MoveIntoChild(from.clone()))
+			}
+
+			let value = remove(target, from)?;
+			add(target, path, value)
+		},
+		PatchOperation::Copy { from, path } => {
+			let value = pointer_mut(target, from)?.clone();
+			add(target, path, value)
+		},
+		PatchOperation::Test { path, value } => {
+			if *pointer_mut(target, path)? == *value {
+				Ok(())
+			} else {
+				Err(PatchError::TestFailed(path.clone()))
+			}
+		}
+	}
+}
+
+/// Splits a pointer into the parent and the unescaped last token.
+fn split_pointer(path: &str) -> Result<(&str, String), PatchError> {
+	let Some(idx) = path.rfind('/') else {
+		return Err(PatchError::InvalidPointer(path.into()))
+	};
+
+	Ok((&path[..idx], unescape(&path[idx + 1..])))
+}
+
+fn unescape(token: &str) -> String {
+	token.replace("~1", "/").replace("~0", "~")
+}
+
+fn pointer_mut<'a>(
+	target: &'a mut Value,
+	path: &str
+) -> Result<&'a mut Value, PatchError> {
+	if path.is_empty() {
+		return Ok(target)
+	}
+
+	if !path.starts_with('/') {
+		return Err(PatchError::InvalidPointer(path.into()))
+	}
+
+	// serde_json uses the same pointer syntax
+	target.pointer_mut(path)
+		.ok_or_else(|| PatchError::PathNotFound(path.into()))
+}
+
+fn array_index(
+	token: &str,
+	len: usize,
+	path: &str
+) -> Result<usize, PatchError> {
+	let valid = !token.is_empty() &&
+		token.bytes().all(|b| b.is_ascii_digit()) &&
+		(token == "0" || !token.starts_with('0'));
+	if !valid {
+		return Err(PatchError::InvalidPointer(path.into()))
+	}
+
+	token.parse().ok()
+		.filter(|i| *i < len)
+		.ok_or_else(|| PatchError::PathNotFound(path.into()))
+}
+
+fn add(target: &mut Value, path: &str, value: Value) -> Result<(), PatchError> {
+	if path.is_empty() {
+		*target = value;
+		return Ok(())
+	}
+
+	let (parent, token) = split_pointer(path)?;
+	match pointer_mut(target, parent)? {
+		Value::Object(map) => {
+			map.insert(token, value);
+		},
+		Value::Array(list) if token == "-" => list.push(value),
+		Value::Array(list) => {
+			// inserting at the end is allowed
+			let idx = array_index(&token, list.len() + 1, path)?;
+			list.insert(idx, value);
+		},
+		_ => return Err(PatchError::PathNotFound(path.into()))
+	}
+
+	Ok(())
+}
+
+fn remove(target: &mut Value, path: &str) -> Result<Value, PatchError> {
+	let (parent, token) = split_pointer(path)?;
+	match pointer_mut(target, parent)? {
+		Value::Object(map) => map.remove(&token)
+			.ok_or_else(|| PatchError::PathNotFound(path.into())),
+		Value::Array(list) => {
+			let idx = array_index(&token, list.len(), path)?;
+			Ok(list.remove(idx))
+		},
+		_ => Err(PatchError::PathNotFound(path.into()))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_json_patch() {
+
+		let patch: JsonPatch = serde_json::from_value(json!([
+			{ "op": "test", "path": "/a~1b", "value": 1 },
+			{ "op": "add", "path": "/list/1", "value": 2 },
+			{ "op": "add", "path": "/list/-", "value": 4 },
+			{ "op": "remove", "path": "/old" },
+			{ "op": "replace", "path": "/name", "value": "new" },
+			{ "op": "move", "from": "/a~1b", "path": "/moved" },
+			{ "op": "copy", "from": "/list/0", "path": "/first" }
+		])).unwrap();
+
+		let mut target = json!({
+			"a/b": 1,
+			"list": [1, 3],
+			"old": true,
+			"name": "old"
+		});
+		patch.apply(&mut target).unwrap();
+		assert_eq!(target, json!({
+			"list": [1, 2, 3, 4],
+			"name": "new",
+			"moved": 1,
+			"first": 1
+		}));
+
+		let failing = JsonPatch(vec![
+			PatchOperation::Remove { path: "/name".into() },
+			PatchOperation::Test { path: "/first".into(), value: json!(2) }
+		]);
+		assert_eq!(
+			failing.apply(&mut target).unwrap_err(),
+			PatchError::TestFailed("/first".into())
+		);
+		// unchanged
+		assert_eq!(target["name"], "new");
+
+	}
+
+	#[test]
+	fn test_merge_patch() {
+
+		let mut target = json!({
+			"title": "Goodbye!",
+			"author": { "givenName": "John", "familyName": "Doe" },
+			"tags": ["example", "sample"]
+		});
+		merge_patch(&mut target, &json!({
+			"title": "Hello!",
+			"author": { "familyName": null },
+			"tags": ["example"],
+			"phoneNumber": "+01-123-456-7890"
+		}));
+		assert_eq!(target, json!({
+			"title": "Hello!",
+			"author": { "givenName": "John" },
+			"tags": ["example"],
+			"phoneNumber": "+01-123-456-7890"
+		}));
+
+	}
+
+}
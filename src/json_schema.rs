@@ -0,0 +1,341 @@
+//! JSON Schema validation of request bodies.
+//!
+//! Schemas are compiled and validated with the `jsonschema` crate, the
+//! draft is detected from `$schema` and defaults to 2020-12. `format` is
+//! asserted and not only annotated.
+//!
+//! ## Note
+//! Remote `$ref`s are not resolved, a schema referencing one is rejected by
+//! `JsonSchema::new`.
+
+use crate::Body;
+
+use std::{io, fmt};
+use std::sync::Arc;
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+
+/// A compiled JSON Schema.
+#[derive(Clone)]
+pub struct JsonSchema {
+	schema: Value,
+	validator: Arc<jsonschema::Validator>
+}
+
+impl JsonSchema {
+	/// Compiles a schema.
+	///
+	/// ## Errors
+	/// If the schema is not valid or references a remote schema.
+	pub fn new(schema: Value) -> Result<Self, SchemaError> {
+		let validator = jsonschema::options()
+			.should_validate_formats(true)
+			.build(&schema)
+			.map_err(|e| SchemaError { message: e.to_string() })?;
+
+		Ok(Self { schema, validator: Arc::new(validator) })
+	}
+
+	/// Returns the schema.
+	pub fn as_value(&self) -> &Value {
+		&self.schema
+	}
+
+	/// Returns all violations, an empty list means the value is valid.
+	pub fn validate(&self, value: &Value) -> Vec<Violation> {
+		self.validator.iter_errors(value)
+			.map(|e| {
+				let keyword = e.schema_path.as_str()
+					.rsplit('/')
+					.next()
+					.filter(|k| !k.is_empty())
+					.unwrap_or("false");
+
+				Violation {
+					instance_path: e.instance_path.as_str().into(),
+					keyword: keyword.into(),
+					message: e.to_string()
+				}
+			})
+			.collect()
+	}
+
+	/// Returns true if the value is valid.
+	pub fn is_valid(&self, value: &Value) -> bool {
+		self.validator.is_valid(value)
+	}
+}
+
+impl fmt::Debug for JsonSchema {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("JsonSchema")
+			.field("schema", &self.schema)
+			.finish()
+	}
+}
+
+impl PartialEq for JsonSchema {
+	fn eq(&self, other: &Self) -> bool {
+		self.schema == other.schema
+	}
+}
+
+/// Returned by `JsonSchema::new` if a schema cannot be compiled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+	message: String
+}
+
+impl fmt::Display for SchemaError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid json schema {}", self.message)
+	}
+}
+
+impl std::error::Error for SchemaError {}
+
+/// A value which does not match a schema keyword.
+///
+/// Serializes as `{"instancePath":"/users/0","keyword":"required",...}`
+/// which can be sent as the body of a `422 Unprocessable Entity` response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Violation {
+	/// A JSON Pointer to the invalid value, empty for the root.
+	pub instance_path: String,
+	pub keyword: String,
+	pub message: String
+}
+
+impl fmt::Display for Violation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let path = match self.instance_path.as_str() {
+			"" => "/",
+			p => p
+		};
+		write!(f, "{path}: {}", self.message)
+	}
+}
+
+/// Returned by `Body::deserialize_validated`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ValidationError {
+	/// The body could not be read, is not valid json or could not be
+	/// deserialized into the type.
+	Reading(io::Error),
+	/// The body does not match the schema.
+	Invalid(Vec<Violation>)
+}
+
+impl ValidationError {
+	/// Returns the violations if the body does not match the schema.
+	pub fn violations(&self) -> Option<&[Violation]> {
+		match self {
+			Self::Invalid(v) => Some(v),
+			_ => None
+		}
+	}
+}
+
+impl fmt::Display for ValidationError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Reading(e) => write!(f, "failed to read body {e}"),
+			Self::Invalid(violations) => {
+				f.write_str("body does not match the schema")?;
+				for (i, v) in violations.iter().enumerate() {
+					let sep = if i == 0 { ": " } else { ", " };
+					write!(f, "{sep}{v}")?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<ValidationError> for io::Error {
+	fn from(e: ValidationError) -> Self {
+		match e {
+			ValidationError::Reading(e) => e,
+			e => io::Error::new(io::ErrorKind::InvalidData, e)
+		}
+	}
+}
+
+impl Body {
+	/// Validates the body against the schema and then deserializes it.
+	///
+	/// The body is first parsed into a `serde_json::Value`.
+	#[cfg_attr(docsrs, doc(cfg(feature = "jsonschema")))]
+	pub async fn deserialize_validated<D>(
+		self,
+		schema: &JsonSchema
+	) -> Result<D, ValidationError>
+	where D: serde::de::DeserializeOwned + Send + 'static {
+		let value: Value = self.deserialize().await
+			.map_err(ValidationError::Reading)?;
+
+		let violations = schema.validate(&value);
+		if !violations.is_empty() {
+			return Err(ValidationError::Invalid(violations))
+		}
+
+		serde_json::from_value(value)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+			.map_err(ValidationError::Reading)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn schema() -> JsonSchema {
+		JsonSchema::new(json!({
+			"type": "object",
+			"required": ["name", "tags"],
+			"additionalProperties": false,
+			"properties": {
+				"name": { "type": "string", "minLength": 1 },
+				"age": { "type": "integer", "minimum": 0 },
+				"tags": {
+					"type": "array",
+					"uniqueItems": true,
+					"items": { "$ref": "#/$defs/tag" }
+				}
+			},
+			"$defs": {
+				"tag": { "enum": ["a", "b"] }
+			}
+		})).unwrap()
+	}
+
+	#[test]
+	fn test_validate() {
+
+		let schema = schema();
+		assert!(schema.is_valid(&json!({ "name": "x", "tags": ["a", "b"] })));
+
+		let violations = schema.validate(&json!({
+			"name": "",
+			"age": 1.5,
+			"tags": ["a", "c", "a"],
+			"other": 1
+		}));
+		let mut found: Vec<_> = violations.iter()
+			.map(|v| (v.instance_path.as_str(), v.keyword.as_str()))
+			.collect();
+		found.sort();
+		assert_eq!(found, [
+			("", "additionalProperties"),
+			("/age", "type"),
+			("/name", "minLength"),
+			("/tags", "uniqueItems"),
+			("/tags/1", "enum")
+		]);
+
+		let violations = schema.validate(&json!({ "name": "x" }));
+		assert_eq!(violations.len(), 1);
+		let violation = serde_json::to_value(&violations[0]).unwrap();
+		assert_eq!(violation["instancePath"], "");
+		assert_eq!(violation["keyword"], "required");
+
+	}
+
+	#[test]
+	fn test_combinators() {
+
+		let schema = JsonSchema::new(json!({
+			"oneOf": [{ "type": "integer" }, { "minimum": 2 }],
+			"not": { "const": 5 }
+		})).unwrap();
+		assert!(schema.is_valid(&json!(1)));
+		assert!(!schema.is_valid(&json!(3)));
+		assert!(!schema.is_valid(&json!(5)));
+		assert!(schema.is_valid(&json!(2.5)));
+
+	}
+
+	#[test]
+	fn test_keywords() {
+
+		let schema = JsonSchema::new(json!({
+			"type": "object",
+			"properties": {
+				"code": { "type": "string", "pattern": "^[a-z]+$" },
+				"email": { "type": "string", "format": "email" },
+				"point": {
+					"prefixItems": [{ "type": "integer" }, { "type": "integer" }]
+				}
+			},
+			"patternProperties": {
+				"^x-": { "type": "string" }
+			},
+			"dependentRequired": {
+				"email": ["code"]
+			},
+			"if": { "required": ["point"] },
+			"then": { "required": ["code"] }
+		})).unwrap();
+
+		assert!(schema.is_valid(&json!({
+			"code": "abc",
+			"email": "a@b.ch",
+			"point": [1, 2],
+			"x-tag": "a"
+		})));
+
+		let invalid = [
+			(json!({ "code": "ABC" }), "pattern"),
+			(json!({ "code": "a", "email": "no" }), "format"),
+			(json!({ "code": "a", "point": [1, "a"] }), "type"),
+			(json!({ "x-tag": 1 }), "type"),
+			(json!({ "email": "a@b.ch" }), "dependentRequired"),
+			(json!({ "point": [1, 2] }), "required")
+		];
+		for (value, keyword) in invalid {
+			let violations = schema.validate(&value);
+			assert_eq!(violations.len(), 1, "{value}");
+			assert_eq!(violations[0].keyword, keyword, "{value}");
+		}
+
+	}
+
+	#[test]
+	fn test_invalid_schema() {
+
+		assert!(JsonSchema::new(json!({ "type": "nope" })).is_err());
+		assert!(JsonSchema::new(json!({ "minLength": -1 })).is_err());
+		assert!(JsonSchema::new(json!({
+			"$ref": "https://example.com/schema.json"
+		})).is_err());
+
+	}
+
+	#[tokio::test]
+	async fn test_deserialize_validated() {
+
+		#[derive(Debug, Deserialize)]
+		struct User {
+			name: String
+		}
+
+		let body = Body::from(r#"{"name": "x", "tags": ["a"]}"#);
+		let user: User = body.deserialize_validated(&schema()).await.unwrap();
+		assert_eq!(user.name, "x");
+
+		let body = Body::from(r#"{"name": "x"}"#);
+		let e = body.deserialize_validated::<User>(&schema()).await
+			.unwrap_err();
+		assert_eq!(e.violations().unwrap().len(), 1);
+
+	}
+
+}
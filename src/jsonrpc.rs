@@ -0,0 +1,311 @@
+//! JSON-RPC 2.0 request and response envelopes.
+
+use crate::Body;
+
+use std::{io, fmt};
+
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde::de::{self, DeserializeOwned};
+use serde_json::Value;
+
+
+/// The `jsonrpc` member which is always `"2.0"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Version;
+
+impl Serialize for Version {
+	fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+		s.serialize_str("2.0")
+	}
+}
+
+impl<'de> Deserialize<'de> for Version {
+	fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+		let s: String = Deserialize::deserialize(d)?;
+		match s.as_str() {
+			"2.0" => Ok(Self),
+			_ => Err(de::Error::custom("expected jsonrpc version 2.0"))
+		}
+	}
+}
+
+/// The id of a request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JsonRpcId {
+	Number(i64),
+	String(String),
+	/// Only used in responses if the id of the request could not be
+	/// determined.
+	Null
+}
+
+/// A JSON-RPC request, without an id it is a notification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcRequest {
+	pub jsonrpc: Version,
+	pub method: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub params: Option<Value>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub id: Option<JsonRpcId>
+}
+
+impl JsonRpcRequest {
+	/// Creates a new request.
+	pub fn new(
+		id: JsonRpcId,
+		method: impl Into<String>,
+		params: Option<Value>
+	) -> Self {
+		Self {
+			jsonrpc: Version,
+			method: method.into(),
+			params,
+			id: Some(id)
+		}
+	}
+
+	/// Creates a new notification, which does not expect a response.
+	pub fn notification(
+		method: impl Into<String>,
+		params: Option<Value>
+	) -> Self {
+		Self {
+			jsonrpc: Version,
+			method: method.into(),
+			params,
+			id: None
+		}
+	}
+
+	/// Returns true if this request does not expect a response.
+	pub fn is_notification(&self) -> bool {
+		self.id.is_none()
+	}
+
+	/// Deserializes the params, missing params are treated as `null`.
+	///
+	/// ## Errors
+	/// Returns an invalid params error.
+	pub fn params<D>(&self) -> Result<D, JsonRpcError>
+	where D: DeserializeOwned {
+		D::deserialize(self.params.as_ref().unwrap_or(&Value::Null))
+			.map_err(|e| JsonRpcError::invalid_params(e.to_string()))
+	}
+}
+
+/// A JSON-RPC error object.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcError {
+	pub code: i64,
+	pub message: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub data: Option<Value>
+}
+
+impl JsonRpcError {
+	pub const PARSE_ERROR: i64 = -32700;
+	pub const INVALID_REQUEST: i64 = -32600;
+	pub const METHOD_NOT_FOUND: i64 = -32601;
+	pub const INVALID_PARAMS: i64 = -32602;
+	pub const INTERNAL_ERROR: i64 = -32603;
+
+	/// Creates a new error without data.
+	pub fn new(code: i64, message: impl Into<String>) -> Self {
+		Self { code, message: message.into(), data: None }
+	}
+
+	pub fn parse_error() -> Self {
+		Self::new(Self::PARSE_ERROR, "Parse error")
+	}
+
+	pub fn invalid_request() -> Self {
+		Self::new(Self::INVALID_REQUEST, "Invalid Request")
+	}
+
+	pub fn method_not_found() -> Self {
+		Self::new(Self::METHOD_NOT_FOUND, "Method not found")
+	}
+
+	/// The reason is added as data.
+	pub fn invalid_params(reason: impl Into<String>) -> Self {
+		Self {
+			data: Some(Value::String(reason.into())),
+			..Self::new(Self::INVALID_PARAMS, "Invalid params")
+		}
+	}
+
+	pub fn internal_error() -> Self {
+		Self::new(Self::INTERNAL_ERROR, "Internal error")
+	}
+}
+
+impl fmt::Display for JsonRpcError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} ({})", self.message, self.code)
+	}
+}
+
+impl std::error::Error for JsonRpcError {}
+
+/// A JSON-RPC response, contains either a result or an error.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRpcResponse {
+	pub jsonrpc: Version,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub result: Option<Value>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<JsonRpcError>,
+	pub id: JsonRpcId
+}
+
+impl JsonRpcResponse {
+	/// Creates a successful response.
+	pub fn success(id: JsonRpcId, result: Value) -> Self {
+		Self { jsonrpc: Version, result: Some(result), error: None, id }
+	}
+
+	/// Creates an error response.
+	pub fn error(id: JsonRpcId, error: JsonRpcError) -> Self {
+		Self { jsonrpc: Version, result: None, error: Some(error), id }
+	}
+
+	/// Converts the response into a `Result`.
+	pub fn into_result(self) -> Result<Value, JsonRpcError> {
+		match (self.result, self.error) {
+			(_, Some(e)) => Err(e),
+			(Some(r), None) => Ok(r),
+			(None, None) => Ok(Value::Null)
+		}
+	}
+}
+
+/// A single message or a batch of messages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JsonRpcMessage<T> {
+	Single(T),
+	Batch(Vec<T>)
+}
+
+impl<T> JsonRpcMessage<T> {
+	/// Returns true if this is a batch.
+	pub fn is_batch(&self) -> bool {
+		matches!(self, Self::Batch(_))
+	}
+
+	/// Returns all messages, a single message is returned as a batch with
+	/// one entry.
+	pub fn into_vec(self) -> Vec<T> {
+		match self {
+			Self::Single(m) => vec![m],
+			Self::Batch(v) => v
+		}
+	}
+}
+
+impl JsonRpcMessage<JsonRpcRequest> {
+	/// Reads requests from the body, the size limit and timeout of the
+	/// body are respected.
+	///
+	/// ## Errors
+	/// Distinguishes between invalid json, an invalid request or an empty
+	/// batch and an error while reading the body.
+	pub async fn from_body(body: Body) -> Result<Self, JsonRpcBodyError> {
+		let bytes = body.into_bytes().await
+			.map_err(JsonRpcBodyError::Reading)?;
+
+		let value: Value = serde_json::from_slice(&bytes)
+			.map_err(|_| JsonRpcBodyError::Parse)?;
+
+		let msg: Self = serde_json::from_value(value)
+			.map_err(|_| JsonRpcBodyError::InvalidRequest)?;
+
+		match &msg {
+			Self::Batch(v) if v.is_empty() => {
+				Err(JsonRpcBodyError::InvalidRequest)
+			},
+			_ => Ok(msg)
+		}
+	}
+}
+
+impl<T: Serialize> JsonRpcMessage<T> {
+	/// Serializes the messages into a json body.
+	pub fn to_body(&self) -> io::Result<Body> {
+		Body::serialize(self)
+	}
+}
+
+/// Returned if JSON-RPC requests could not be read from a body.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum JsonRpcBodyError {
+	/// The body is not valid json.
+	Parse,
+	/// The json is not a valid request or an empty batch.
+	InvalidRequest,
+	Reading(io::Error)
+}
+
+impl JsonRpcBodyError {
+	/// Returns the response which should be sent to the client, is `None`
+	/// if the body could not be read.
+	pub fn to_response(&self) -> Option<JsonRpcResponse> {
+		let error = match self {
+			Self::Parse => JsonRpcError::parse_error(),
+			Self::InvalidRequest => JsonRpcError::invalid_request(),
+			Self::Reading(_) => return None
+		};
+
+		Some(JsonRpcResponse::error(JsonRpcId::Null, error))
+	}
+}
+
+impl fmt::Display for JsonRpcBodyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Parse => f.write_str("invalid json"),
+			Self::InvalidRequest => f.write_str("invalid jsonrpc request"),
+			Self::Reading(e) => write!(f, "failed to read body {e}")
+		}
+	}
+}
+
+impl std::error::Error for JsonRpcBodyError {}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_serde() {
+
+		let req: JsonRpcMessage<JsonRpcRequest> = serde_json::from_str(
+			r#"[{"jsonrpc":"2.0","method":"sum","params":[1,2],"id":"1"},
+			{"jsonrpc":"2.0","method":"notify"}]"#
+		).unwrap();
+		let reqs = req.into_vec();
+		assert_eq!(reqs[0].id, Some(JsonRpcId::String("1".into())));
+		assert_eq!(reqs[0].params::<Vec<u32>>().unwrap(), [1, 2]);
+		assert!(reqs[1].is_notification());
+
+		assert!(serde_json::from_str::<JsonRpcRequest>(
+			r#"{"jsonrpc":"1.0","method":"sum"}"#
+		).is_err());
+
+		let resp = JsonRpcResponse::error(
+			JsonRpcId::Null,
+			JsonRpcError::method_not_found()
+		);
+		assert_eq!(
+			serde_json::to_string(&resp).unwrap(),
+			r#"{"jsonrpc":"2.0","error":{"code":-32601,"#.to_string() +
+			r#""message":"Method not found"},"id":null}"#
+		);
+
+	}
+
+}
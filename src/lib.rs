@@ -8,21 +8,88 @@
 //!
 //! ## Features
 //!
+//! Without default features only the core types are compiled.
+//!
 //! ### hyper_body
-//! Adds support for the `hyper::Body` type in `Body`.
+//! Adds support for the `hyper::body::Incoming` type in `Body` and allows to
+//! convert a `Body` into a `hyper::body::Body`. Enabled by default.
+//!
+//! ### compression
+//! Adds `Body::compress` and `Body::decompress`, the codecs are enabled
+//! with their own features.
+//!
+//! ### stream-adapters
+//! Adds `Body::lines`, `Body::read_exact_chunks`, `Body::chunked_max` and
+//! `Body::throttle`. Enabled by default.
 //!
 //! ### json
 //! Adds json serialization and deserialization support for
 //! the `Body` type and for `HeaderValues`.
 //!
-//! ### timeout
-//! Adds the `BodyTimeout` type, allowing to set a timeout
-//! for reading from the body.
+//! ### simd-json
+//! Uses simd-json instead of serde_json in `Body::serialize` and
+//! `Body::deserialize`, enables `json`.
+//!
+//! ### serde
+//! Adds serde helpers for `StatusCode` and `Method` in `header::serde_with`.
+//! Enabled by `json`.
+//!
+//! ### time
+//! Allows `time::OffsetDateTime` to be used as a header value.
+//!
+//! ### chrono
+//! Allows `chrono::DateTime` to be used as a header value.
+//!
+//! ### uuid
+//! Allows to generate a request id with `RequestHeader::ensure_request_id`.
+//!
+//! ### gzip
+//! Adds gzip support to `Body::compress` and `Body::decompress`.
+//!
+//! ### deflate
+//! Adds deflate support to `Body::compress` and `Body::decompress`.
+//!
+//! ### brotli
+//! Adds brotli (`br`) support to `Body::compress` and `Body::decompress`.
+//!
+//! ### zstd
+//! Adds zstd support to `Body::compress` and `Body::decompress`.
+//!
+//! ### idna
+//! Adds unicode host support to `UriExt`.
+//!
+//! ### csv
+//! Adds csv serialization and deserialization support for the `Body` type.
+//!
+//! ### xml
+//! Adds xml serialization and deserialization support for the `Body`,
+//! `Request` and `ResponseBuilder` types.
+//!
+//! ### graphql
+//! Adds graphql request and response types in `graphql`.
+//!
+//! ### jsonrpc
+//! Adds JSON-RPC 2.0 request and response types in `jsonrpc`.
+//!
+//! ### json-patch
+//! Adds JSON Patch and JSON Merge Patch support in `json_patch`.
+//!
+//! ### jsonschema
+//! Adds JSON Schema validation in `json_schema`.
+//!
+//! ### webhook
+//! Adds webhook signature verification in `webhook`.
+//!
+//! ### testing
+//! Adds an in-memory transport in `testing`.
 //!
 
 /// Reexport the http crate
 pub use http;
 
+pub mod error;
+pub use error::Error;
+
 pub mod header;
 pub mod body;
 pub use body::Body;
@@ -31,4 +98,49 @@ pub mod request;
 pub use request::Request;
 
 pub mod response;
-pub use response::Response;
\ No newline at end of file
+pub use response::Response;
+
+pub mod extract;
+
+pub mod wire;
+
+pub mod otel;
+
+pub mod logging;
+
+pub mod csrf;
+
+pub mod fingerprint;
+
+#[cfg(feature = "graphql")]
+#[cfg_attr(docsrs, doc(cfg(feature = "graphql")))]
+pub mod graphql;
+
+#[cfg(feature = "jsonrpc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonrpc")))]
+pub mod jsonrpc;
+
+#[cfg(feature = "json-patch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json-patch")))]
+pub mod json_patch;
+
+#[cfg(feature = "jsonschema")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonschema")))]
+pub mod json_schema;
+
+#[cfg(feature = "webhook")]
+#[cfg_attr(docsrs, doc(cfg(feature = "webhook")))]
+pub mod webhook;
+
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+// Body, Request and Response can be shared between threads
+const _: () = {
+	const fn assert_send_sync<T: Send + Sync>() {}
+
+	assert_send_sync::<Body>();
+	assert_send_sync::<Request>();
+	assert_send_sync::<Response>();
+};
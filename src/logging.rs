@@ -0,0 +1,127 @@
+//! Access log formatting.
+
+use crate::header::{RequestHeader, ResponseHeader, HttpDate};
+
+use std::fmt::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+
+/// Formats an access log line in the Common Log Format.
+///
+/// `body_bytes` is the size of the response body. The protocol version is
+/// not known and therefore not part of the request line.
+pub fn format_common_line(
+	req: &RequestHeader,
+	resp: &ResponseHeader,
+	body_bytes: u64,
+	duration: Duration
+) -> String {
+	let date = start_date(duration);
+	let bytes = match body_bytes {
+		0 => "-".to_string(),
+		b => b.to_string()
+	};
+
+	format!(
+		"{} - - [{}] \"{}\" {} {bytes}",
+		req.address.ip(),
+		date.log_format(),
+		escape(&format!("{} {}", req.method, req.uri)),
+		resp.status_code.as_u16()
+	)
+}
+
+/// Formats an access log line in the Combined Log Format followed by the
+/// duration in microseconds.
+///
+/// See `format_common_line`.
+pub fn format_access_line(
+	req: &RequestHeader,
+	resp: &ResponseHeader,
+	body_bytes: u64,
+	duration: Duration
+) -> String {
+	let mut line = format_common_line(req, resp, body_bytes, duration);
+	let referer = req.value("referer").unwrap_or("-");
+	let user_agent = req.value("user-agent").unwrap_or("-");
+
+	write!(
+		line, " \"{}\" \"{}\" {}",
+		escape(referer),
+		escape(user_agent),
+		duration.as_micros()
+	).unwrap();
+
+	line
+}
+
+/// Formats an access log record as a json object.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub fn format_access_json(
+	req: &RequestHeader,
+	resp: &ResponseHeader,
+	body_bytes: u64,
+	duration: Duration
+) -> String {
+	let date = start_date(duration);
+
+	serde_json::json!({
+		"time": date.to_string(),
+		"client": req.address.ip().to_string(),
+		"method": req.method.as_str(),
+		"uri": req.uri.to_string(),
+		"status": resp.status_code.as_u16(),
+		"bytes": body_bytes,
+		"referer": req.value("referer"),
+		"user_agent": req.value("user-agent"),
+		"duration_us": duration.as_micros() as u64
+	}).to_string()
+}
+
+/// Returns the date when the request was received.
+fn start_date(duration: Duration) -> HttpDate {
+	SystemTime::now().checked_sub(duration).unwrap_or(UNIX_EPOCH).into()
+}
+
+/// Escapes `"`, `\` and non printable characters like apache does.
+fn escape(s: &str) -> String {
+	let mut out = String::with_capacity(s.len());
+	for b in s.bytes() {
+		match b {
+			b'"' | b'\\' => {
+				out.push('\\');
+				out.push(b as char);
+			},
+			b' '..=b'~' => out.push(b as char),
+			b => write!(out, "\\x{b:02x}").unwrap()
+		}
+	}
+
+	out
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::{test_request_header, Method};
+
+	#[test]
+	fn test_access_line() {
+
+		let req = test_request_header(Method::GET, "/a?b=1", &[
+			("user-agent", "curl/8.0 \"x\"")
+		]);
+		let resp = ResponseHeader::default();
+
+		let line = format_access_line(
+			&req, &resp, 0, Duration::from_millis(2)
+		);
+		assert!(line.starts_with("127.0.0.1 - - ["), "{line}");
+		let expected = r#"] "GET /a?b=1" 200 - "-" "curl/8.0 \"x\"" 2000"#;
+		assert!(line.ends_with(expected), "{line}");
+
+	}
+
+}
@@ -0,0 +1,87 @@
+//! Helpers to create OpenTelemetry semantic convention attributes.
+
+use crate::header::{RequestHeader, ResponseHeader};
+
+
+/// Returns the OpenTelemetry http attributes of a request and it's response.
+///
+/// Contains `http.request.method`, `url.path`, `url.query` (if present),
+/// `client.address`, `http.response.status_code` and
+/// `user_agent.original` (if present).
+pub fn attributes(
+	req: &RequestHeader,
+	resp: &ResponseHeader
+) -> Vec<(&'static str, String)> {
+	let mut attrs = request_attributes(req);
+	attrs.push((
+		"http.response.status_code",
+		resp.status_code.as_u16().to_string()
+	));
+
+	attrs
+}
+
+/// Returns the OpenTelemetry http attributes which are known before the
+/// response exists, see `attributes`.
+pub fn request_attributes(req: &RequestHeader) -> Vec<(&'static str, String)> {
+	let mut attrs = vec![
+		("http.request.method", req.method.as_str().to_string()),
+		("url.path", req.uri.path().to_string())
+	];
+
+	if let Some(query) = req.uri.query() {
+		attrs.push(("url.query", query.to_string()));
+	}
+
+	attrs.push(("client.address", req.address.ip().to_string()));
+
+	if let Some(ua) = req.value("user-agent") {
+		attrs.push(("user_agent.original", ua.to_string()));
+	}
+
+	attrs
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::header::{test_request_header, Method, StatusCode};
+
+
+	#[test]
+	fn test_attributes() {
+
+		let req = test_request_header(
+			Method::GET,
+			"/search?q=rust",
+			&[("user-agent", "curl/8.0")]
+		);
+		let resp = ResponseHeader {
+			status_code: StatusCode::NOT_FOUND,
+			..Default::default()
+		};
+		assert_eq!(attributes(&req, &resp), [
+			("http.request.method", "GET".to_string()),
+			("url.path", "/search".to_string()),
+			("url.query", "q=rust".to_string()),
+			("client.address", "127.0.0.1".to_string()),
+			("user_agent.original", "curl/8.0".to_string()),
+			("http.response.status_code", "404".to_string())
+		]);
+
+	}
+
+	#[test]
+	fn test_request_attributes() {
+
+		let req = test_request_header(Method::POST, "/upload", &[]);
+		assert_eq!(request_attributes(&req), [
+			("http.request.method", "POST".to_string()),
+			("url.path", "/upload".to_string()),
+			("client.address", "127.0.0.1".to_string())
+		]);
+
+	}
+}
@@ -1,6 +1,17 @@
-use crate::header::RequestHeader;
+use crate::header::{
+	RequestHeader, Method, Uri, HeaderValues, HeaderValue,
+	values::{IntoHeaderName, AsHeaderName}
+};
+use crate::header::negotiate::{
+	negotiate, SupportedRepresentations, Negotiated, NotAcceptable
+};
 use crate::body::Body;
+use crate::wire::{WireReader, ReadLimits, Framing, WireBody};
+use crate::error::Error;
+use crate::fingerprint::FingerprintConfig;
 
+use std::fmt;
+use std::ops::Deref;
 use std::time::Duration;
 
 
@@ -17,16 +28,96 @@ impl Request {
 		Self { header, body }
 	}
 
+	/// Reads the header from the reader and returns a `Request` with a body
+	/// which streams the remaining chunks of the reader.
+	/// 
+	/// The body is only read when it gets consumed, a `content-length` body
+	/// which ends early or contains too much data results in an error.
+	/// 
+	/// ## Errors
+	/// - `Error::InvalidHeader` if the framing headers are not valid, see
+	///   `RequestHeader::check_framing`.
+	/// - `Error::SizeLimit` if the `content-length` exceeds the size limit.
+	/// - If the header could not be read.
+	pub async fn read_from<R>(
+		mut reader: R,
+		limits: ReadLimits
+	) -> Result<Self, Error>
+	where R: WireReader + Send + 'static {
+		let header = reader.read_head().await?;
+		let framing = Framing::from_request_header(
+			&header,
+			reader.body_until_eof()
+		)?;
+
+		let expected = match framing {
+			Framing::None | Framing::ContentLength(0) => {
+				return Ok(Self::new(header, Body::new()))
+			},
+			Framing::ContentLength(len) => Some(len),
+			Framing::Chunked => None
+		};
+
+		if let (Some(len), Some(max)) = (expected, limits.size_limit) {
+			if len > max as u64 {
+				return Err(Error::SizeLimit)
+			}
+		}
+
+		let mut body = Body::from_async_bytes_streamer(
+			WireBody::new(reader, expected)
+		);
+		body.set_size_limit(limits.size_limit);
+		body.set_timeout(limits.timeout);
+
+		Ok(Self::new(header, body))
+	}
+
 	/// Takes the body replacing it with an empty one.
 	pub fn take_body(&mut self) -> Body {
 		self.body.take()
 	}
 
+	/// Returns the header and the body.
+	pub fn into_parts(self) -> (RequestHeader, Body) {
+		(self.header, self.body)
+	}
+
+	/// Replaces the body with the result of the closure, for example to wrap
+	/// it with another body.
+	pub fn map_body<F>(mut self, f: F) -> Self
+	where F: FnOnce(Body) -> Body {
+		self.body = f(self.body);
+		self
+	}
+
+	/// Returns a builder containing a copy of the header and the body,
+	/// which can be used to create a modified request, for example to retry
+	/// it with another header, without changing this one.
+	/// 
+	/// Returns None if the body is streaming, see `Body::try_clone`.
+	pub fn to_builder(&self) -> Option<RequestBuilder> {
+		Some(RequestBuilder {
+			header: self.header.clone(),
+			body: self.body.try_clone()?,
+			body_changed: false
+		})
+	}
+
 	/// Get the request header by reference.
 	pub fn header(&self) -> &RequestHeader {
 		&self.header
 	}
 
+	/// Returns a borrowed view of the request which does not give access to
+	/// the body.
+	pub fn parts(&self) -> RequestParts<'_> {
+		RequestParts {
+			header: &self.header,
+			body_len: self.body.len()
+		}
+	}
+
 	/// Sets a read size limit.
 	pub fn set_size_limit(&mut self, size: Option<usize>) {
 		self.body.set_size_limit(size)
@@ -37,6 +128,26 @@ impl Request {
 		self.body.set_timeout(timeout)
 	}
 
+	/// Returns a hash over the method, the normalized uri and the parts
+	/// selected by the config, which can be used to find identical requests.
+	/// 
+	/// The hash is stable between restarts but not cryptographically secure.
+	/// 
+	/// Returns None if the body should be included but is not buffered, call
+	/// `Body::into_bytes` first.
+	pub fn fingerprint(&self, config: &FingerprintConfig) -> Option<u64> {
+		config.fingerprint(&self.header, &self.body)
+	}
+
+	/// Chooses a representation based on the `accept-*` headers, see
+	/// `header::negotiate::negotiate`.
+	pub fn negotiate(
+		&self,
+		supported: &SupportedRepresentations
+	) -> Result<Negotiated, NotAcceptable> {
+		negotiate(&self.header, supported)
+	}
+
 	/// Tries to deserialize the request body.
 	/// 
 	/// ## Errors
@@ -45,6 +156,49 @@ impl Request {
 	#[cfg(feature = "json")]
 	pub async fn deserialize<D>(&mut self) -> Result<D, DeserializeError>
 	where D: serde::de::DeserializeOwned + Send + 'static {
+		self.expect_mime(crate::header::Mime::JSON)?;
+
+		// now parse body
+		self.body.take().deserialize().await
+			.map_err(DeserializeError::Reading)
+	}
+
+	/// Tries to deserialize the request body as a JSON Patch.
+	/// 
+	/// ## Errors
+	/// - If the header `content-type` is not `application/json-patch+json`.
+	/// - If the body does not contain a valid JSON Patch.
+	#[cfg(feature = "json-patch")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "json-patch")))]
+	pub async fn deserialize_json_patch(
+		&mut self
+	) -> Result<crate::json_patch::JsonPatch, DeserializeError> {
+		self.expect_mime(crate::header::Mime::JSON_PATCH)?;
+
+		self.body.take().deserialize_json_patch().await
+			.map_err(DeserializeError::Reading)
+	}
+
+	/// Tries to deserialize the request body as xml.
+	/// 
+	/// ## Errors
+	/// - If the header `content-type` does not contain `application/xml`.
+	/// - If the body does not contain a valid xml or some data is missing.
+	#[cfg(feature = "xml")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+	pub async fn deserialize_xml<D>(&mut self) -> Result<D, DeserializeError>
+	where D: serde::de::DeserializeOwned + Send + 'static {
+		self.expect_mime(crate::header::Mime::XML)?;
+
+		self.body.take().deserialize_xml().await
+			.map_err(DeserializeError::Reading)
+	}
+
+	#[cfg(any(feature = "json", feature = "xml"))]
+	fn expect_mime(
+		&self,
+		expected: crate::header::Mime
+	) -> Result<(), DeserializeError> {
 		use crate::header::Mime;
 
 		// try to read mime
@@ -58,17 +212,116 @@ impl Request {
 				raw_content_type.to_string()
 			))?;
 
-		if mime != Mime::JSON {
+		if mime != expected {
 			return Err(DeserializeError::WrongMimeType(mime))
 		}
 
-		// now parse body
-		self.body.take().deserialize().await
-			.map_err(|e| DeserializeError::Reading(e))
+		Ok(())
+	}
+}
+
+/// A borrowed view of a `Request` without access to the body.
+/// 
+/// Allows read-only middleware (logging, auth checks) to inspect a request
+/// while the body stays untouched in the owner.
+/// 
+/// Derefs to `RequestHeader`.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestParts<'a> {
+	header: &'a RequestHeader,
+	body_len: Option<usize>
+}
+
+impl<'a> RequestParts<'a> {
+	/// Get the request header.
+	pub fn header(&self) -> &'a RequestHeader {
+		self.header
+	}
+
+	/// Returns the length of the body if it is known.
+	pub fn body_len(&self) -> Option<usize> {
+		self.body_len
 	}
 }
 
-#[cfg(feature = "json")]
+impl Deref for RequestParts<'_> {
+	type Target = RequestHeader;
+
+	fn deref(&self) -> &RequestHeader {
+		self.header
+	}
+}
+
+/// A builder to create a modified copy of a `Request`, see
+/// `Request::to_builder`.
+#[derive(Debug)]
+pub struct RequestBuilder {
+	header: RequestHeader,
+	body: Body,
+	body_changed: bool
+}
+
+impl RequestBuilder {
+	/// Sets the method.
+	pub fn method(mut self, method: Method) -> Self {
+		self.header.method = method;
+		self
+	}
+
+	/// Sets the uri.
+	pub fn uri(mut self, uri: Uri) -> Self {
+		self.header.uri = uri;
+		self
+	}
+
+	/// Sets a header value.
+	/// 
+	/// ## Panics
+	/// If the value is not a valid `HeaderValue`.
+	pub fn header<K, V>(mut self, key: K, val: V) -> Self
+	where
+		K: IntoHeaderName,
+		V: TryInto<HeaderValue>,
+		V::Error: fmt::Debug
+	{
+		self.values_mut().insert(key, val);
+		self
+	}
+
+	/// Removes all values of a header.
+	pub fn remove_header<K>(mut self, key: K) -> Self
+	where K: AsHeaderName {
+		self.values_mut().remove(key);
+		self
+	}
+
+	/// Returns `HeaderValues` mutably.
+	pub fn values_mut(&mut self) -> &mut HeaderValues {
+		&mut self.header.values
+	}
+
+	/// Sets the body dropping the previous one.
+	pub fn body(mut self, body: impl Into<Body>) -> Self {
+		self.body = body.into();
+		self.body_changed = true;
+		self
+	}
+
+	/// Builds the `Request`. If the body was changed the `content-length`
+	/// header is updated.
+	pub fn build(mut self) -> Request {
+		if self.body_changed {
+			match self.body.len() {
+				Some(len) => self.values_mut().insert("content-length", len),
+				None => self.values_mut().remove("content-length")
+			};
+		}
+
+		Request::new(self.header, self.body)
+	}
+}
+
+#[cfg(any(feature = "json", feature = "xml"))]
 mod deserialize_error {
 	use crate::header::Mime;
 
@@ -93,5 +346,53 @@ mod deserialize_error {
 	impl std::error::Error for DeserializeError {}
 }
 
-#[cfg(feature = "json")]
-pub use deserialize_error::*;
\ No newline at end of file
+#[cfg(any(feature = "json", feature = "xml"))]
+pub use deserialize_error::*;
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::header::test_request_header;
+
+	fn request(body: Body) -> Request {
+		let mut header = test_request_header(Method::POST, "/", &[]);
+		if let Some(len) = body.len() {
+			header.values.insert("content-length", len);
+		}
+
+		Request::new(header, body)
+	}
+
+	#[tokio::test]
+	async fn test_to_builder() {
+
+		let req = request(Body::from("abc"));
+		let retry = req.to_builder().unwrap()
+			.header("x-retry", "1")
+			.build();
+		assert_eq!(retry.header.value("x-retry"), Some("1"));
+		assert_eq!(retry.header.value("content-length"), Some("3"));
+		assert!(req.header.value("x-retry").is_none());
+		assert_eq!(retry.body.into_string().await.unwrap(), "abc");
+
+		let changed = req.to_builder().unwrap()
+			.method(Method::PUT)
+			.body("abcd")
+			.build();
+		assert_eq!(changed.header.method, Method::PUT);
+		assert_eq!(changed.header.value("content-length"), Some("4"));
+
+		let streaming = Body::from_async_reader(tokio::io::empty());
+		let changed = request(Body::from("abc")).to_builder().unwrap()
+			.body(streaming)
+			.build();
+		assert!(changed.header.value("content-length").is_none());
+
+		// a streaming body cannot be copied
+		let streaming = Body::from_async_reader(&b"abc"[..]);
+		assert!(request(streaming).to_builder().is_none());
+
+	}
+
+}
\ No newline at end of file
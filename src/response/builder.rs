@@ -1,18 +1,25 @@
 use super::Response;
 use crate::body::Body;
 use crate::header::{
-	ResponseHeader, StatusCode, ContentType, HeaderValues, HeaderValue,
+	RequestHeader, ResponseHeader, StatusCode, ContentType, HeaderValues,
+	SharedHeaderValues, HeaderValue, Entity, Priority, ConflictPolicy,
+	Deprecation, Sunset, PreferenceApplied, ClientHint,
 	values::IntoHeaderName
 };
 
 use std::fmt;
+#[cfg(any(feature = "json", feature = "xml"))]
+use std::io;
 
 
 /// A builder to create a `Response`.
 #[derive(Debug)]
 pub struct ResponseBuilder {
 	header: ResponseHeader,
-	body: Body
+	body: Body,
+	interim_responses: Vec<ResponseHeader>,
+	#[cfg(feature = "json")]
+	json_pretty: bool
 }
 
 impl ResponseBuilder {
@@ -20,10 +27,25 @@ impl ResponseBuilder {
 	pub fn new() -> Self {
 		Self {
 			header: ResponseHeader::default(),
-			body: Body::new()
+			body: Body::new(),
+			interim_responses: vec![],
+			#[cfg(feature = "json")]
+			json_pretty: false
 		}
 	}
 
+	/// Creates a builder containing a copy of the response, see
+	/// `Response::to_builder`.
+	pub(super) fn from_response(resp: &Response) -> Option<Self> {
+		Some(Self {
+			header: resp.header.clone(),
+			body: resp.body.try_clone()?,
+			interim_responses: resp.interim_responses.clone(),
+			#[cfg(feature = "json")]
+			json_pretty: false
+		})
+	}
+
 	/// Sets the status code.
 	pub fn status_code(mut self, status_code: StatusCode) -> Self {
 		self.header.status_code = status_code;
@@ -57,6 +79,153 @@ impl ResponseBuilder {
 		self
 	}
 
+	/// Sets a header value if it is `Some`.
+	/// 
+	/// ## Panics
+	/// If the value is not a valid `HeaderValue`.
+	pub fn header_opt<K, V>(self, key: K, val: Option<V>) -> Self
+	where
+		K: IntoHeaderName,
+		V: TryInto<HeaderValue>,
+		V::Error: fmt::Debug
+	{
+		match val {
+			Some(val) => self.header(key, val),
+			None => self
+		}
+	}
+
+	/// Sets a header value if `cond` is true.
+	/// 
+	/// ## Panics
+	/// If the value is not a valid `HeaderValue`.
+	pub fn header_if<K, V>(self, cond: bool, key: K, val: V) -> Self
+	where
+		K: IntoHeaderName,
+		V: TryInto<HeaderValue>,
+		V::Error: fmt::Debug
+	{
+		self.header_opt(key, cond.then_some(val))
+	}
+
+	/// Adds all `defaults` which are not already set, for example a `server`
+	/// header or security headers which every response should contain.
+	/// 
+	/// Headers set later overwrite the defaults.
+	pub fn with_defaults(mut self, defaults: &HeaderValues) -> Self {
+		self.values_mut().extend(defaults.clone(), ConflictPolicy::Skip);
+		self
+	}
+
+	/// Replaces all header values with values which can be shared between
+	/// responses.
+	/// 
+	/// ## Note
+	/// Every mutation copies the shared values once, this includes the
+	/// `content-length` header inserted by `build` if the body length is
+	/// known.
+	pub fn shared_values(mut self, values: SharedHeaderValues) -> Self {
+		self.header.values = values;
+		self
+	}
+
+	/// Sets the content type and all other metadata of the entity.
+	/// 
+	/// ## Panics
+	/// If a value of the entity is not a valid `HeaderValue`.
+	pub fn entity(mut self, entity: &Entity) -> Self {
+		self.header.content_type = entity.content_type.clone();
+		entity.apply_to_values(self.values_mut());
+		self
+	}
+
+	/// Sets the `priority` header.
+	pub fn priority(mut self, priority: Priority) -> Self {
+		self.values_mut().insert("priority", priority);
+		self
+	}
+
+	/// Sets the `accept-ch` header requesting the client hints in following
+	/// requests.
+	/// 
+	/// If the response depends on a hint add it to the `vary` header as well.
+	pub fn accept_ch(mut self, hints: &[ClientHint]) -> Self {
+		self.values_mut().insert("accept-ch", ClientHint::format_list(hints));
+		self
+	}
+
+	/// Marks the endpoint as deprecated by setting the `deprecation` header
+	/// and if a link is given adds `<link>; rel="deprecation"` to the `link`
+	/// header.
+	/// 
+	/// ## Panics
+	/// If the link is not a valid `HeaderValue`.
+	pub fn deprecated(
+		mut self,
+		deprecation: Deprecation,
+		link: Option<&str>
+	) -> Self {
+		let values = self.values_mut();
+		values.insert("deprecation", deprecation);
+
+		if let Some(link) = link {
+			let link = format!("<{link}>; rel=\"deprecation\"");
+			let link = match values.get_str("link") {
+				Some(existing) => format!("{existing}, {link}"),
+				None => link
+			};
+			values.insert("link", link);
+		}
+
+		self
+	}
+
+	/// Sets the `sunset` header.
+	pub fn sunset(mut self, sunset: impl Into<Sunset>) -> Self {
+		self.values_mut().insert("sunset", sunset.into());
+		self
+	}
+
+	/// Sets the `preference-applied` header, does nothing if no preferences
+	/// were applied.
+	/// 
+	/// ## Panics
+	/// If a preference value is not a valid `HeaderValue`.
+	pub fn preference_applied(mut self, applied: PreferenceApplied) -> Self {
+		if !applied.is_empty() {
+			let value = HeaderValue::try_from(applied)
+				.expect("invalid preference value");
+			self.values_mut().insert("preference-applied", value);
+		}
+
+		self
+	}
+
+	/// Marks the response as a replay of the response to an earlier request
+	/// with the same idempotency key by setting the `idempotency-key` and
+	/// `idempotent-replayed: true` headers.
+	/// 
+	/// ## Panics
+	/// If the key is not a valid `HeaderValue`, use
+	/// `RequestHeader::idempotency_key` to get a valid key.
+	pub fn idempotent_replayed(mut self, key: &str) -> Self {
+		let values = self.values_mut();
+		values.insert("idempotency-key", format!("\"{key}\""));
+		values.insert("idempotent-replayed", "true");
+		self
+	}
+
+	/// Sets the `x-request-id` header to the id of the request if it has one.
+	/// 
+	/// See `RequestHeader::request_id`.
+	pub fn propagate_request_id(mut self, req: &RequestHeader) -> Self {
+		if let Some(id) = req.request_id() {
+			self.values_mut().insert("x-request-id", id);
+		}
+
+		self
+	}
+
 	/// Returns `HeaderValues` mutably.
 	pub fn values_mut(&mut self) -> &mut HeaderValues {
 		&mut self.header.values
@@ -68,6 +237,75 @@ impl ResponseBuilder {
 		self
 	}
 
+	/// Sets if `json` should format the body with indentation, defaults to
+	/// false.
+	#[cfg(feature = "json")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+	pub fn json_pretty(mut self, pretty: bool) -> Self {
+		self.json_pretty = pretty;
+		self
+	}
+
+	/// Serializes the value as the body and sets the content type to
+	/// `application/json`.
+	/// 
+	/// If `json_pretty` was enabled `Body::serialize_pretty` is used.
+	/// 
+	/// ## Errors
+	/// If the value could not be serialized.
+	#[cfg(feature = "json")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+	pub fn json<S>(mut self, value: &S) -> io::Result<Self>
+	where S: serde::Serialize + ?Sized {
+		self.body = if self.json_pretty {
+			Body::serialize_pretty(value)?
+		} else {
+			Body::serialize(value)?
+		};
+		self.header.content_type = crate::header::Mime::JSON.into();
+
+		Ok(self)
+	}
+
+	/// Serializes the value as xml into the body and sets the content type
+	/// to xml, see `Body::serialize_xml`.
+	/// 
+	/// ## Errors
+	/// If the value could not be serialized.
+	#[cfg(feature = "xml")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+	pub fn xml<S>(mut self, value: &S) -> io::Result<Self>
+	where S: serde::Serialize + ?Sized {
+		self.body = Body::serialize_xml(value)?;
+		self.header.content_type = crate::header::Mime::XML.into();
+
+		Ok(self)
+	}
+
+	/// Sets a weak `etag` computed from the body if the body is buffered and
+	/// no `etag` is set yet, see `Body::etag`.
+	/// 
+	/// Call this after setting the body.
+	pub fn auto_etag(mut self) -> Self {
+		if self.header.values.get("etag").is_none() {
+			if let Some(etag) = self.body.etag() {
+				self.values_mut().insert("etag", etag);
+			}
+		}
+
+		self
+	}
+
+	/// Adds an interim response (for example `100 Continue`, `102 Processing`
+	/// or `103 Early Hints`) which should be sent before the final response.
+	/// 
+	/// Create it with `ResponseHeader::interim` or
+	/// `ResponseHeader::early_hints`.
+	pub fn interim(mut self, header: ResponseHeader) -> Self {
+		self.interim_responses.push(header);
+		self
+	}
+
 	/// Builds a `Response`. Adding the `content-length` header
 	/// if the len of the body is known.
 	pub fn build(mut self) -> Response {
@@ -77,7 +315,9 @@ impl ResponseBuilder {
 			self.values_mut().insert("content-length", len);
 		}
 
-		Response::new(self.header, self.body)
+		let mut resp = Response::new(self.header, self.body);
+		resp.interim_responses = self.interim_responses;
+		resp
 	}
 
 }
\ No newline at end of file
@@ -0,0 +1,318 @@
+//! Build rss and atom feed responses.
+
+use super::Response;
+use crate::header::{HttpDate, Mime, StatusCode};
+use crate::body::{Body, ChunkWriter};
+
+use std::io;
+use std::fmt::Write;
+
+
+/// The format of a feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeedFormat {
+	/// `application/rss+xml`
+	Rss,
+	/// `application/atom+xml`
+	Atom
+}
+
+impl FeedFormat {
+	/// Returns the mime type which is used as the content type of the
+	/// response.
+	pub fn mime(&self) -> Mime {
+		match self {
+			Self::Rss => Mime::RSS,
+			Self::Atom => Mime::ATOM
+		}
+	}
+}
+
+/// An entry of a feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+	/// A plain text title, which gets escaped.
+	pub title: String,
+	/// An absolute url, also used as the id of the entry.
+	pub link: String,
+	/// When the entry was published or last updated, written as rfc 822
+	/// date for rss and as rfc 3339 date for atom.
+	pub date: HttpDate,
+	/// Html content, which gets escaped.
+	pub content: String
+}
+
+impl FeedEntry {
+	/// Creates a new `FeedEntry`.
+	pub fn new(
+		title: impl Into<String>,
+		link: impl Into<String>,
+		date: HttpDate,
+		content: impl Into<String>
+	) -> Self {
+		Self {
+			title: title.into(),
+			link: link.into(),
+			date,
+			content: content.into()
+		}
+	}
+}
+
+/// A builder to create rss or atom feed responses.
+///
+/// The xml is only generated while the body is read.
+#[derive(Debug, Clone)]
+pub struct FeedBuilder {
+	format: FeedFormat,
+	title: String,
+	link: String,
+	description: String,
+	entries: Vec<FeedEntry>
+}
+
+impl FeedBuilder {
+	/// Creates a new `FeedBuilder`, the link should point to the website of
+	/// the feed.
+	pub fn new(
+		format: FeedFormat,
+		title: impl Into<String>,
+		link: impl Into<String>
+	) -> Self {
+		Self {
+			format,
+			title: title.into(),
+			link: link.into(),
+			description: String::new(),
+			entries: vec![]
+		}
+	}
+
+	/// Sets the description of the feed.
+	pub fn description(mut self, description: impl Into<String>) -> Self {
+		self.description = description.into();
+		self
+	}
+
+	/// Adds an entry.
+	pub fn entry(mut self, entry: FeedEntry) -> Self {
+		self.entries.push(entry);
+		self
+	}
+
+	/// Adds multiple entries.
+	pub fn entries<I>(mut self, entries: I) -> Self
+	where I: IntoIterator<Item=FeedEntry> {
+		self.entries.extend(entries);
+		self
+	}
+
+	/// Builds a `200 OK` `Response` with the content type of the format.
+	pub fn build(self) -> Response {
+		let mime = self.format.mime();
+
+		Response::builder()
+			.status_code(StatusCode::OK)
+			.content_type(mime)
+			.body(Body::from_writer(|mut writer| async move {
+				self.write(&mut writer).await
+			}))
+			.build()
+	}
+
+	async fn write(self, writer: &mut ChunkWriter) -> io::Result<()> {
+		let mut s = String::new();
+		s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+
+		// the feed is updated with the newest entry
+		let updated = self.entries.iter()
+			.map(|e| e.date)
+			.max()
+			.unwrap_or_else(HttpDate::now);
+
+		let title = escape(&self.title);
+		let link = escape(&self.link);
+		let description = escape(&self.description);
+
+		match self.format {
+			FeedFormat::Rss => {
+				let _ = write!(
+					s,
+					"<rss version=\"2.0\"><channel>\
+					<title>{title}</title>\
+					<link>{link}</link>\
+					<description>{description}</description>\
+					<lastBuildDate>{updated}</lastBuildDate>"
+				);
+			},
+			FeedFormat::Atom => {
+				let _ = write!(
+					s,
+					"<feed xmlns=\"http://www.w3.org/2005/Atom\">\
+					<title>{title}</title>\
+					<link href=\"{link}\"/>\
+					<id>{link}</id>\
+					<updated>{}</updated>",
+					updated.rfc3339_format()
+				);
+
+				if !description.is_empty() {
+					let _ = write!(s, "<subtitle>{description}</subtitle>");
+				}
+			}
+		}
+
+		for entry in &self.entries {
+			writer.write(&s).await?;
+			s.clear();
+
+			let title = escape(&entry.title);
+			let link = escape(&entry.link);
+			let content = escape(&entry.content);
+
+			let _ = match self.format {
+				FeedFormat::Rss => write!(
+					s,
+					"<item>\
+					<title>{title}</title>\
+					<link>{link}</link>\
+					<guid>{link}</guid>\
+					<pubDate>{}</pubDate>\
+					<description>{content}</description>\
+					</item>",
+					entry.date
+				),
+				FeedFormat::Atom => write!(
+					s,
+					"<entry>\
+					<title>{title}</title>\
+					<link href=\"{link}\"/>\
+					<id>{link}</id>\
+					<updated>{}</updated>\
+					<content type=\"html\">{content}</content>\
+					</entry>",
+					entry.date.rfc3339_format()
+				)
+			};
+		}
+
+		s.push_str(match self.format {
+			FeedFormat::Rss => "</channel></rss>",
+			FeedFormat::Atom => "</feed>"
+		});
+		writer.write(&s).await
+	}
+}
+
+fn escape(s: &str) -> String {
+	let mut out = String::with_capacity(s.len());
+	for c in s.chars() {
+		match c {
+			'&' => out.push_str("&amp;"),
+			'<' => out.push_str("&lt;"),
+			'>' => out.push_str("&gt;"),
+			'"' => out.push_str("&quot;"),
+			'\'' => out.push_str("&apos;"),
+			c => out.push(c)
+		}
+	}
+
+	out
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+
+	fn feed(format: FeedFormat) -> FeedBuilder {
+		FeedBuilder::new(format, "News & Notes", "https://example.com/")
+			.description("All the <news>")
+			.entries([
+				FeedEntry::new(
+					"First",
+					"https://example.com/1?a=1&b=2",
+					HttpDate::from_unix_secs(784111777),
+					"<p>Hello</p>"
+				),
+				FeedEntry::new(
+					"Second \"post\"",
+					"https://example.com/2",
+					HttpDate::from_unix_secs(784198177),
+					"it's done"
+				)
+			])
+	}
+
+	#[tokio::test]
+	async fn test_rss() {
+
+		let resp = feed(FeedFormat::Rss).build();
+		assert_eq!(
+			resp.header.content_type.as_str(),
+			"application/rss+xml; charset=utf-8"
+		);
+
+		let xml = resp.body.into_string().await.unwrap();
+		assert_eq!(xml, "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+			<rss version=\"2.0\"><channel>\
+			<title>News &amp; Notes</title>\
+			<link>https://example.com/</link>\
+			<description>All the &lt;news&gt;</description>\
+			<lastBuildDate>Mon, 07 Nov 1994 08:49:37 GMT</lastBuildDate>\
+			<item>\
+			<title>First</title>\
+			<link>https://example.com/1?a=1&amp;b=2</link>\
+			<guid>https://example.com/1?a=1&amp;b=2</guid>\
+			<pubDate>Sun, 06 Nov 1994 08:49:37 GMT</pubDate>\
+			<description>&lt;p&gt;Hello&lt;/p&gt;</description>\
+			</item>\
+			<item>\
+			<title>Second &quot;post&quot;</title>\
+			<link>https://example.com/2</link>\
+			<guid>https://example.com/2</guid>\
+			<pubDate>Mon, 07 Nov 1994 08:49:37 GMT</pubDate>\
+			<description>it&apos;s done</description>\
+			</item>\
+			</channel></rss>"
+		);
+
+	}
+
+	#[tokio::test]
+	async fn test_atom() {
+
+		let resp = feed(FeedFormat::Atom).build();
+		assert_eq!(
+			resp.header.content_type.as_str(),
+			"application/atom+xml; charset=utf-8"
+		);
+
+		let xml = resp.body.into_string().await.unwrap();
+		assert_eq!(xml, "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+			<feed xmlns=\"http://www.w3.org/2005/Atom\">\
+			<title>News &amp; Notes</title>\
+			<link href=\"https://example.com/\"/>\
+			<id>https://example.com/</id>\
+			<updated>1994-11-07T08:49:37Z</updated>\
+			<subtitle>All the &lt;news&gt;</subtitle>\
+			<entry>\
+			<title>First</title>\
+			<link href=\"https://example.com/1?a=1&amp;b=2\"/>\
+			<id>https://example.com/1?a=1&amp;b=2</id>\
+			<updated>1994-11-06T08:49:37Z</updated>\
+			<content type=\"html\">&lt;p&gt;Hello&lt;/p&gt;</content>\
+			</entry>\
+			<entry>\
+			<title>Second &quot;post&quot;</title>\
+			<link href=\"https://example.com/2\"/>\
+			<id>https://example.com/2</id>\
+			<updated>1994-11-07T08:49:37Z</updated>\
+			<content type=\"html\">it&apos;s done</content>\
+			</entry>\
+			</feed>"
+		);
+
+	}
+}
\ No newline at end of file
@@ -1,8 +1,21 @@
 mod builder;
 pub use builder::ResponseBuilder;
 
-use crate::header::{ResponseHeader, StatusCode};
+mod feed;
+pub use feed::{FeedBuilder, FeedEntry, FeedFormat};
+
+use crate::header::{
+	ResponseHeader, StatusCode, AllowedMethods, HeaderValues, ConflictPolicy
+};
 use crate::body::Body;
+use crate::error::Error;
+use crate::wire::{WireWriter, Framing};
+
+use std::io;
+use std::ops::Deref;
+use std::pin::pin;
+
+use tokio_stream::StreamExt;
 
 /// The response created from a server.
 #[derive(Debug)]
@@ -10,14 +23,149 @@ pub struct Response {
 	pub header: ResponseHeader,
 	// if you overide the body
 	// you should pobably reset the content-length
-	pub body: Body
+	pub body: Body,
+	// interim responses (for example 103 Early Hints) which should be sent
+	// in order before this response
+	interim_responses: Vec<ResponseHeader>
 }
 
 impl Response {
 
 	/// Creates a new `Response`.
 	pub fn new(header: ResponseHeader, body: Body) -> Self {
-		Self { header, body }
+		Self { header, body, interim_responses: vec![] }
+	}
+
+	/// Creates a `103 Early Hints` response containing the given `link`
+	/// values.
+	/// 
+	/// To send it before a final response use `add_early_hints`.
+	/// 
+	/// ## Panics
+	/// If a link is not a valid `HeaderValue`.
+	pub fn early_hints<I, L>(links: I) -> Self
+	where
+		I: IntoIterator<Item=L>,
+		L: AsRef<str>
+	{
+		Self::new(ResponseHeader::early_hints(links), Body::new())
+	}
+
+	/// Creates a `405 Method Not Allowed` response with the `allow` header.
+	pub fn method_not_allowed(allowed: &AllowedMethods) -> Self {
+		Self::builder()
+			.status_code(StatusCode::METHOD_NOT_ALLOWED)
+			.header("allow", allowed)
+			.build()
+	}
+
+	/// Creates a `204 No Content` response to an `OPTIONS` request for a
+	/// resource or for the whole server (`OPTIONS *`).
+	/// 
+	/// The `allow` header is set to the allowed methods and the `extra`
+	/// values are added afterwards. If `extra` contains an
+	/// `access-control-allow-origin` header the allowed methods are also
+	/// merged into `access-control-allow-methods`.
+	/// 
+	/// No `content-length` is set since a `204` response never has a body.
+	pub fn options_capabilities(
+		allowed: &AllowedMethods,
+		extra: &HeaderValues
+	) -> Self {
+		let mut header = ResponseHeader {
+			status_code: StatusCode::NO_CONTENT,
+			..Default::default()
+		};
+
+		let values = &mut *header.values;
+		values.insert("allow", allowed);
+		values.extend(extra.clone(), ConflictPolicy::Overwrite);
+		if values.get("access-control-allow-origin").is_some() {
+			allowed.merge_cors(values);
+		}
+
+		Self::new(header, Body::new())
+	}
+
+	/// Creates a response without a body with the status code suggested by
+	/// the error, see `StatusCode::from(&Error)`.
+	/// 
+	/// A `408 Request Timeout` response also contains `connection: close`
+	/// since the connection should not be reused.
+	pub fn from_error(error: &Error) -> Self {
+		let status_code = StatusCode::from(error);
+
+		Self::builder()
+			.status_code(status_code)
+			.header_if(
+				status_code == StatusCode::REQUEST_TIMEOUT,
+				"connection",
+				"close"
+			)
+			.build()
+	}
+
+	/// Returns a copy of a prebuilt response, for example of a static file,
+	/// which can be sent for another request.
+	/// 
+	/// The header values are shared and the body is cloned without copying
+	/// the data, so precomputed headers like `content-length` and `etag` stay
+	/// intact. Returns None if the body is streaming, see `Body::try_clone`.
+	pub fn clone_for_send(&self) -> Option<Self> {
+		Some(Self {
+			header: self.header.clone(),
+			body: self.body.try_clone()?,
+			interim_responses: self.interim_responses.clone()
+		})
+	}
+
+	/// Adds an interim response which should be sent before this response.
+	/// 
+	/// Create it with `ResponseHeader::interim` or
+	/// `ResponseHeader::early_hints`.
+	pub fn push_interim(&mut self, header: ResponseHeader) {
+		self.interim_responses.push(header);
+	}
+
+	/// Returns the interim responses which should be sent in order before
+	/// this response.
+	pub fn interim_responses(&self) -> &[ResponseHeader] {
+		&self.interim_responses
+	}
+
+	/// Takes the interim responses, for example to send them before the
+	/// final response is ready.
+	pub fn take_interim_responses(&mut self) -> Vec<ResponseHeader> {
+		std::mem::take(&mut self.interim_responses)
+	}
+
+	/// Adds a `103 Early Hints` interim response containing the given `link`
+	/// values which should be sent before this response.
+	/// 
+	/// ## Panics
+	/// If a link is not a valid `HeaderValue`.
+	pub fn add_early_hints<I, L>(&mut self, links: I)
+	where
+		I: IntoIterator<Item=L>,
+		L: AsRef<str>
+	{
+		self.interim_responses.push(ResponseHeader::early_hints(links));
+	}
+
+	/// Creates a `200 OK` json response which streams the items as a json
+	/// array, see `Body::serialize_json_array`.
+	#[cfg(feature = "json")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+	pub fn stream_json_array<St, T, E>(stream: St) -> Self
+	where
+		St: futures_core::Stream<Item=Result<T, E>> + Send + 'static,
+		T: serde::Serialize,
+		E: std::fmt::Display
+	{
+		Self::builder()
+			.content_type(crate::header::Mime::JSON)
+			.body(Body::serialize_json_array(stream))
+			.build()
 	}
 
 	/// Creates a new `Response` with a builder.
@@ -25,11 +173,30 @@ impl Response {
 		ResponseBuilder::new()
 	}
 
+	/// Returns a builder containing a copy of the header values and the
+	/// body, which can be used to create a modified response without
+	/// changing this one.
+	/// 
+	/// Returns None if the body is streaming, see `Body::try_clone`.
+	pub fn to_builder(&self) -> Option<ResponseBuilder> {
+		ResponseBuilder::from_response(self)
+	}
+
 	/// Get the response header by reference.
 	pub fn header(&self) -> &ResponseHeader {
 		&self.header
 	}
 
+	/// Returns a borrowed view of the response which does not give access to
+	/// the body.
+	pub fn parts(&self) -> ResponseParts<'_> {
+		ResponseParts {
+			header: &self.header,
+			body_len: self.body.len(),
+			interim_responses: &self.interim_responses
+		}
+	}
+
 	/// Takes the body replacing it with an empty one.
 	/// 
 	/// ## Note
@@ -38,6 +205,120 @@ impl Response {
 	pub fn take_body(&mut self) -> Body {
 		self.body.take()
 	}
+
+	/// Replaces the body with the result of the closure, for example to wrap
+	/// it with another body.
+	/// 
+	/// ## Note
+	/// If the new body has a different length you should probably reset the
+	/// `content-length` header.
+	pub fn map_body<F>(mut self, f: F) -> Self
+	where F: FnOnce(Body) -> Body {
+		self.body = f(self.body);
+		self
+	}
+
+	/// Returns how the body should be delimited on the wire.
+	/// 
+	/// Informational responses, `204 No Content` and `304 Not Modified`
+	/// never have a body.
+	pub fn framing(&self) -> Framing {
+		let status = self.header.status_code;
+		if status.is_informational() ||
+			status == StatusCode::NO_CONTENT ||
+			status == StatusCode::NOT_MODIFIED
+		{
+			return Framing::None
+		}
+
+		match self.body.len() {
+			Some(len) => Framing::ContentLength(len as u64),
+			None => Framing::Chunked
+		}
+	}
+
+	/// Writes the interim responses, the header and the body into the
+	/// writer.
+	/// 
+	/// The `content-length` and `transfer-encoding` headers are set
+	/// according to `framing`, a body which is not allowed is dropped.
+	/// 
+	/// ## Note
+	/// A `Response` has no trailers so `finish` is always called with
+	/// `None`.
+	pub async fn write_into<W>(self, writer: &mut W) -> io::Result<()>
+	where W: WireWriter {
+		for interim in &self.interim_responses {
+			writer.write_head(interim, Framing::None).await?;
+		}
+
+		let framing = self.framing();
+		let mut header = self.header;
+		// only mutate if necessary since the values might be shared
+		if header.values.get("transfer-encoding").is_some() {
+			header.values.remove("transfer-encoding");
+		}
+		match framing {
+			Framing::ContentLength(len) => {
+				let len = len.to_string();
+				if header.value("content-length") != Some(len.as_str()) {
+					header.values.insert("content-length", len);
+				}
+			},
+			Framing::Chunked | Framing::None => {
+				if header.values.get("content-length").is_some() {
+					header.values.remove("content-length");
+				}
+			}
+		}
+
+		writer.write_head(&header, framing).await?;
+
+		if framing != Framing::None {
+			let stream = self.body.into_async_bytes_streamer();
+			let mut stream = pin!(stream);
+			while let Some(chunk) = stream.next().await {
+				writer.write_body_chunk(chunk?).await?;
+			}
+		}
+
+		writer.finish(None).await
+	}
+}
+
+/// A borrowed view of a `Response` without access to the body.
+/// 
+/// Derefs to `ResponseHeader`.
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseParts<'a> {
+	header: &'a ResponseHeader,
+	body_len: Option<usize>,
+	interim_responses: &'a [ResponseHeader]
+}
+
+impl<'a> ResponseParts<'a> {
+	/// Get the response header.
+	pub fn header(&self) -> &'a ResponseHeader {
+		self.header
+	}
+
+	/// Returns the length of the body if it is known.
+	pub fn body_len(&self) -> Option<usize> {
+		self.body_len
+	}
+
+	/// Returns the interim responses which are sent before this response.
+	pub fn interim_responses(&self) -> &'a [ResponseHeader] {
+		self.interim_responses
+	}
+}
+
+impl Deref for ResponseParts<'_> {
+	type Target = ResponseHeader;
+
+	fn deref(&self) -> &ResponseHeader {
+		self.header
+	}
 }
 
 impl From<Body> for Response {
@@ -54,4 +335,97 @@ impl From<StatusCode> for Response {
 			.status_code(status_code)
 			.build()
 	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_to_builder() {
+
+		let resp = Response::builder()
+			.header("a", "b")
+			.body("xyz")
+			.build();
+		let copy = resp.to_builder().unwrap()
+			.header("c", "d")
+			.build();
+		assert_eq!(copy.header.values.get_str("a"), Some("b"));
+		assert_eq!(copy.header.values.get_str("content-length"), Some("3"));
+		assert!(resp.header.values.get("c").is_none());
+		assert_eq!(copy.body.into_string().await.unwrap(), "xyz");
+
+		let streaming = Response::builder()
+			.body(Body::from_async_reader(&b"xyz"[..]))
+			.build();
+		assert!(streaming.to_builder().is_none());
+
+	}
+
+	#[test]
+	fn test_interim_responses() {
+
+		let mut resp = Response::builder()
+			.interim(ResponseHeader::interim(StatusCode::CONTINUE))
+			.body("a")
+			.build();
+		resp.push_interim(ResponseHeader::interim(StatusCode::PROCESSING));
+		resp.add_early_hints(["</a.css>; rel=preload; as=style"]);
+
+		let codes: Vec<_> = resp.parts().interim_responses().iter()
+			.map(|h| h.status_code.as_u16())
+			.collect();
+		assert_eq!(codes, [100, 102, 103]);
+		assert_eq!(
+			resp.interim_responses()[2].values.get_str("link"),
+			Some("</a.css>; rel=preload; as=style")
+		);
+
+		// copies keep the interim responses
+		let copy = resp.clone_for_send().unwrap();
+		assert_eq!(copy.interim_responses().len(), 3);
+		let copy = resp.to_builder().unwrap().build();
+		assert_eq!(copy.interim_responses().len(), 3);
+
+		assert_eq!(resp.take_interim_responses().len(), 3);
+		assert!(resp.interim_responses().is_empty());
+		assert!(Response::new(Default::default(), Body::new())
+			.interim_responses().is_empty());
+
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_invalid_interim() {
+		ResponseHeader::interim(StatusCode::SWITCHING_PROTOCOLS);
+	}
+
+	#[cfg(feature = "testing")]
+	#[tokio::test]
+	async fn test_write_interim_responses() {
+
+		use crate::testing::Loopback;
+
+		let Loopback { mut client, mut writer, .. } = Loopback::new();
+
+		let mut resp = Response::early_hints(["</a.js>; rel=preload"]);
+		assert_eq!(resp.header.status_code.as_u16(), 103);
+		resp = Response::builder()
+			.interim(resp.header)
+			.body("done")
+			.build();
+
+		resp.write_into(&mut writer).await.unwrap();
+		let (resp, _) = client.receive_response().await.unwrap();
+		assert_eq!(resp.interim_responses().len(), 1);
+		assert_eq!(
+			resp.interim_responses()[0].values.get_str("link"),
+			Some("</a.js>; rel=preload")
+		);
+		assert_eq!(resp.body.into_string().await.unwrap(), "done");
+
+	}
+
 }
\ No newline at end of file
@@ -0,0 +1,347 @@
+//! Helpers to test servers and clients without sockets.
+
+use crate::header::{RequestHeader, ResponseHeader, HeaderValues};
+use crate::wire::{WireReader, WireWriter, Framing};
+use crate::{Body, Request, Response};
+
+use std::io;
+use std::pin::pin;
+
+use bytes::Bytes;
+
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+
+
+/// How many frames can be buffered in each direction before the sender
+/// waits.
+const CAPACITY: usize = 16;
+
+#[derive(Debug)]
+enum RequestFrame {
+	Head(Box<RequestHeader>),
+	Chunk(Bytes),
+	End
+}
+
+#[derive(Debug)]
+enum ResponseFrame {
+	Head(Box<ResponseHeader>, Framing),
+	Chunk(Bytes),
+	End(Option<HeaderValues>)
+}
+
+/// An in-memory connection between a client and a server.
+///
+/// The `reader` receives the requests sent by the `client` and can be passed
+/// to `Request::read_from`, responses written into the `writer` are received
+/// by the `client`.
+///
+/// Instead of bytes the connection transfers frames: headers, body chunks
+/// and the end of a body with its trailers. So no http/1.1 parsing or
+/// chunked encoding is involved, a chunked body arrives as the chunks it
+/// was sent with. Timeouts set with `ReadLimits` apply like with a socket.
+#[derive(Debug)]
+pub struct Loopback {
+	pub client: LoopbackClient,
+	pub reader: LoopbackReader,
+	pub writer: LoopbackWriter
+}
+
+impl Loopback {
+	/// Creates a new `Loopback`.
+	pub fn new() -> Self {
+		let (req_tx, req_rx) = mpsc::channel(CAPACITY);
+		let (resp_tx, resp_rx) = mpsc::channel(CAPACITY);
+
+		Self {
+			client: LoopbackClient { tx: req_tx, rx: resp_rx },
+			reader: LoopbackReader { rx: req_rx },
+			writer: LoopbackWriter { tx: resp_tx }
+		}
+	}
+}
+
+impl Default for Loopback {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// The client side of a `Loopback`.
+#[derive(Debug)]
+pub struct LoopbackClient {
+	tx: mpsc::Sender<RequestFrame>,
+	rx: mpsc::Receiver<ResponseFrame>
+}
+
+impl LoopbackClient {
+	/// Sends the header and streams the body of the request.
+	///
+	/// The server needs to read the body concurrently if it does not fit
+	/// into the buffer.
+	pub async fn send_request(&mut self, req: Request) -> io::Result<()> {
+		let (header, body) = req.into_parts();
+		self.send_head(header).await?;
+
+		let mut stream = pin!(body.into_async_bytes_streamer());
+		while let Some(chunk) = stream.next().await {
+			self.send_chunk(chunk?).await?;
+		}
+
+		self.finish().await
+	}
+
+	/// Sends the header of a request, the body needs to be sent with
+	/// `send_chunk` and always ended with `finish`, even if it is empty.
+	pub async fn send_head(&mut self, header: RequestHeader) -> io::Result<()> {
+		self.send(RequestFrame::Head(Box::new(header))).await
+	}
+
+	/// Sends a chunk of the body.
+	pub async fn send_chunk(&mut self, chunk: Bytes) -> io::Result<()> {
+		self.send(RequestFrame::Chunk(chunk)).await
+	}
+
+	/// Marks the end of the request body.
+	pub async fn finish(&mut self) -> io::Result<()> {
+		self.send(RequestFrame::End).await
+	}
+
+	async fn send(&mut self, frame: RequestFrame) -> io::Result<()> {
+		self.tx.send(frame).await
+			.map_err(|_| closed())
+	}
+
+	/// Receives a complete response and its trailers.
+	///
+	/// Interim responses are added to `Response::interim_responses` and the
+	/// body is fully buffered.
+	pub async fn receive_response(
+		&mut self
+	) -> io::Result<(Response, Option<HeaderValues>)> {
+		let mut interim_responses = vec![];
+
+		let header = loop {
+			match self.receive().await? {
+				ResponseFrame::Head(header, Framing::None)
+					if header.status_code.is_informational() =>
+				{
+					interim_responses.push(*header);
+				},
+				ResponseFrame::Head(header, _) => break *header,
+				_ => return Err(unexpected_frame())
+			}
+		};
+
+		let mut chunks = vec![];
+		let trailers = loop {
+			match self.receive().await? {
+				ResponseFrame::Chunk(chunk) => chunks.push(chunk),
+				ResponseFrame::End(trailers) => break trailers,
+				ResponseFrame::Head(..) => return Err(unexpected_frame())
+			}
+		};
+
+		let mut resp = Response::new(header, Body::from(chunks));
+		for interim in interim_responses {
+			resp.push_interim(interim);
+		}
+
+		Ok((resp, trailers))
+	}
+
+	async fn receive(&mut self) -> io::Result<ResponseFrame> {
+		self.rx.recv().await
+			.ok_or_else(closed)
+	}
+}
+
+/// The server side of a `Loopback` which receives requests.
+///
+/// Trailers are not supported by `WireReader` and are never sent by the
+/// client.
+#[derive(Debug)]
+pub struct LoopbackReader {
+	rx: mpsc::Receiver<RequestFrame>
+}
+
+impl LoopbackReader {
+	async fn receive(&mut self) -> io::Result<RequestFrame> {
+		self.rx.recv().await
+			.ok_or_else(closed)
+	}
+}
+
+impl WireReader for LoopbackReader {
+	/// Skips the remaining body of the previous request if it was not read.
+	async fn read_head(&mut self) -> io::Result<RequestHeader> {
+		loop {
+			if let RequestFrame::Head(header) = self.receive().await? {
+				return Ok(*header)
+			}
+		}
+	}
+
+	async fn read_body_chunk(&mut self) -> io::Result<Option<Bytes>> {
+		match self.receive().await? {
+			RequestFrame::Chunk(chunk) => Ok(Some(chunk)),
+			RequestFrame::End => Ok(None),
+			RequestFrame::Head(_) => Err(unexpected_frame())
+		}
+	}
+
+	/// The end of the body is always marked by the client.
+	fn body_until_eof(&self) -> bool {
+		true
+	}
+}
+
+/// The server side of a `Loopback` which sends responses.
+#[derive(Debug)]
+pub struct LoopbackWriter {
+	tx: mpsc::Sender<ResponseFrame>
+}
+
+impl LoopbackWriter {
+	async fn send(&mut self, frame: ResponseFrame) -> io::Result<()> {
+		self.tx.send(frame).await
+			.map_err(|_| closed())
+	}
+}
+
+impl WireWriter for LoopbackWriter {
+	async fn write_head(
+		&mut self,
+		header: &ResponseHeader,
+		framing: Framing
+	) -> io::Result<()> {
+		self.send(ResponseFrame::Head(
+			Box::new(header.clone()),
+			framing
+		)).await
+	}
+
+	async fn write_body_chunk(&mut self, chunk: Bytes) -> io::Result<()> {
+		self.send(ResponseFrame::Chunk(chunk)).await
+	}
+
+	async fn finish(
+		&mut self,
+		trailers: Option<HeaderValues>
+	) -> io::Result<()> {
+		self.send(ResponseFrame::End(trailers)).await
+	}
+}
+
+fn closed() -> io::Error {
+	io::Error::new(io::ErrorKind::BrokenPipe, "loopback closed")
+}
+
+fn unexpected_frame() -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, "unexpected frame")
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::header::{test_request_header, Method, StatusCode};
+	use crate::wire::ReadLimits;
+
+	use std::time::Duration;
+
+
+	#[tokio::test]
+	async fn test_chunked_request() {
+
+		let Loopback { mut client, reader, .. } = Loopback::new();
+
+		let header = test_request_header(
+			Method::POST,
+			"/",
+			&[("transfer-encoding", "chunked")]
+		);
+		client.send_head(header).await.unwrap();
+		client.send_chunk("hello ".into()).await.unwrap();
+		client.send_chunk("world".into()).await.unwrap();
+		client.finish().await.unwrap();
+
+		let req = Request::read_from(reader, ReadLimits::default()).await
+			.unwrap();
+		assert!(req.body.len().is_none());
+		assert_eq!(req.body.into_string().await.unwrap(), "hello world");
+
+	}
+
+	#[tokio::test]
+	async fn test_chunked_response() {
+
+		let Loopback { mut client, mut writer, .. } = Loopback::new();
+
+		let chunks = ["hello ", "world"].map(|c| Ok(Bytes::from(c)));
+		Response::builder()
+			.body(Body::from_async_bytes_streamer(tokio_stream::iter(chunks)))
+			.build()
+			.write_into(&mut writer).await.unwrap();
+
+		let (resp, trailers) = client.receive_response().await.unwrap();
+		assert!(resp.header.value("content-length").is_none());
+		assert_eq!(resp.body.as_more_bytes().unwrap().segments_len(), 2);
+		assert_eq!(resp.body.into_string().await.unwrap(), "hello world");
+		assert!(trailers.is_none());
+
+		// trailers can be written directly
+		let mut trailers = HeaderValues::new();
+		trailers.insert("x-checksum", "abc");
+		writer.write_head(&ResponseHeader::default(), Framing::Chunked).await
+			.unwrap();
+		writer.write_body_chunk("data".into()).await.unwrap();
+		writer.finish(Some(trailers)).await.unwrap();
+
+		let (resp, trailers) = client.receive_response().await.unwrap();
+		assert_eq!(resp.header.status_code, StatusCode::OK);
+		assert_eq!(resp.body.into_string().await.unwrap(), "data");
+		assert_eq!(trailers.unwrap().get_str("x-checksum"), Some("abc"));
+
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_timeout() {
+
+		let Loopback { mut client, reader, .. } = Loopback::new();
+
+		let header = test_request_header(
+			Method::POST,
+			"/",
+			&[("content-length", "10")]
+		);
+		client.send_head(header).await.unwrap();
+		client.send_chunk("abc".into()).await.unwrap();
+
+		let limits = ReadLimits {
+			timeout: Some(Duration::from_secs(1)),
+			..Default::default()
+		};
+		let req = Request::read_from(reader, limits).await.unwrap();
+		let e = req.body.into_bytes().await.unwrap_err();
+		assert_eq!(e.kind(), io::ErrorKind::TimedOut);
+
+		// the reader was dropped together with the body
+		assert!(client.finish().await.is_err());
+
+	}
+
+	#[tokio::test]
+	async fn test_closed() {
+
+		let Loopback { client, reader, .. } = Loopback::new();
+		drop(client);
+
+		let e = Request::read_from(reader, ReadLimits::default()).await
+			.unwrap_err();
+		assert!(e.to_string().contains("loopback closed"), "{e}");
+
+	}
+}
\ No newline at end of file
@@ -0,0 +1,198 @@
+//! Verify the signature of incoming webhooks.
+
+use crate::header::HttpDate;
+use crate::Request;
+
+use std::{io, fmt};
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The way a webhook is signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WebhookScheme {
+	/// GitHub style `x-hub-signature-256: sha256=<hex>` where the hmac is
+	/// calculated over the body.
+	GitHub,
+	/// Stripe style `stripe-signature: t=<timestamp>,v1=<hex>` where the
+	/// hmac is calculated over `<timestamp>.<body>`.
+	///
+	/// The timestamp is only accepted if it is not further away from now
+	/// than the tolerance.
+	Stripe {
+		tolerance: Duration
+	}
+}
+
+impl WebhookScheme {
+	/// The header containing the signature.
+	pub fn header(&self) -> &'static str {
+		match self {
+			Self::GitHub => "x-hub-signature-256",
+			Self::Stripe { .. } => "stripe-signature"
+		}
+	}
+}
+
+/// Reads the body of the request, limited to `max_size` bytes, and verifies
+/// its hmac-sha256 signature with the shared secret.
+///
+/// The signatures are compared in constant time.
+///
+/// Returns the verified body.
+pub async fn verify_webhook(
+	req: &mut Request,
+	secret: &[u8],
+	scheme: WebhookScheme,
+	max_size: usize
+) -> Result<Bytes, WebhookError> {
+	let header = req.header().value(scheme.header())
+		.ok_or(WebhookError::MissingSignature)?;
+
+	let (timestamp, signatures) = parse_signatures(header, &scheme)?;
+
+	if let (WebhookScheme::Stripe { tolerance }, Some(ts)) =
+		(&scheme, timestamp)
+	{
+		let now = HttpDate::now().unix_secs();
+		if now.abs_diff(ts) > tolerance.as_secs() {
+			return Err(WebhookError::Expired)
+		}
+	}
+
+	let mut body = req.take_body();
+	body.set_size_limit(Some(max_size));
+	let bytes = body.into_bytes().await
+		.map_err(WebhookError::Reading)?;
+
+	let mut mac = HmacSha256::new_from_slice(secret)
+		.expect("hmac accepts keys of any size");
+	if let Some(ts) = timestamp {
+		mac.update(format!("{ts}.").as_bytes());
+	}
+	mac.update(&bytes);
+
+	let valid = signatures.iter()
+		.any(|sig| mac.clone().verify_slice(sig).is_ok());
+
+	if valid {
+		Ok(bytes)
+	} else {
+		Err(WebhookError::SignatureMismatch)
+	}
+}
+
+fn parse_signatures(
+	header: &str,
+	scheme: &WebhookScheme
+) -> Result<(Option<u64>, Vec<Vec<u8>>), WebhookError> {
+	match scheme {
+		WebhookScheme::GitHub => {
+			let sig = header.trim().strip_prefix("sha256=")
+				.and_then(from_hex)
+				.ok_or(WebhookError::InvalidSignature)?;
+
+			Ok((None, vec![sig]))
+		},
+		WebhookScheme::Stripe { .. } => {
+			let mut timestamp = None;
+			let mut signatures = vec![];
+
+			for (key, val) in header.split(',').filter_map(|p| {
+				p.trim().split_once('=')
+			}) {
+				match key {
+					"t" => timestamp = val.parse().ok(),
+					// there might be multiple signatures while the secret
+					// is rolled
+					"v1" => signatures.push(
+						from_hex(val).ok_or(WebhookError::InvalidSignature)?
+					),
+					_ => {}
+				}
+			}
+
+			if timestamp.is_none() || signatures.is_empty() {
+				return Err(WebhookError::InvalidSignature)
+			}
+
+			Ok((timestamp, signatures))
+		}
+	}
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+	if !s.len().is_multiple_of(2) || !s.is_ascii() {
+		return None
+	}
+
+	(0..s.len()).step_by(2)
+		.map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+		.collect()
+}
+
+/// Returned if a webhook could not be verified.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum WebhookError {
+	MissingSignature,
+	/// The signature header could not be parsed.
+	InvalidSignature,
+	/// The timestamp is outside of the tolerance.
+	Expired,
+	/// The signature does not match the body.
+	SignatureMismatch,
+	Reading(io::Error)
+}
+
+impl fmt::Display for WebhookError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::MissingSignature => f.write_str("missing signature"),
+			Self::InvalidSignature => f.write_str("invalid signature header"),
+			Self::Expired => f.write_str("signature timestamp expired"),
+			Self::SignatureMismatch => f.write_str("signature does not match"),
+			Self::Reading(e) => write!(f, "failed to read body {e}")
+		}
+	}
+}
+
+impl std::error::Error for WebhookError {}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_signatures() {
+
+		let (ts, sigs) = parse_signatures(
+			"sha256=00ff",
+			&WebhookScheme::GitHub
+		).unwrap();
+		assert_eq!(ts, None);
+		assert_eq!(sigs, [vec![0x00, 0xff]]);
+
+		let stripe = WebhookScheme::Stripe { tolerance: Duration::ZERO };
+		let (ts, sigs) = parse_signatures(
+			"t=1492774577,v1=0a,v0=ff,v1=0b",
+			&stripe
+		).unwrap();
+		assert_eq!(ts, Some(1492774577));
+		assert_eq!(sigs, [vec![0x0a], vec![0x0b]]);
+
+		assert!(parse_signatures("sha1=00ff", &WebhookScheme::GitHub).is_err());
+		assert!(parse_signatures("v1=0a", &stripe).is_err());
+		assert!(parse_signatures("t=1,v1=xyz", &stripe).is_err());
+
+	}
+
+}
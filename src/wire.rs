@@ -0,0 +1,316 @@
+//! Traits to plug a `Response` into any wire protocol.
+
+use crate::header::{RequestHeader, ResponseHeader, HeaderValues};
+use crate::error::Error;
+
+use std::{io, mem};
+use std::pin::Pin;
+use std::future::Future;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+
+use bytes::Bytes;
+
+
+/// How the body of a message is delimited on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Framing {
+	/// The message has no body, for example an interim response or
+	/// `304 Not Modified`.
+	None,
+	/// The body has exactly this length.
+	ContentLength(u64),
+	/// The length of the body is not known, with http/1.1 this means
+	/// `transfer-encoding: chunked`, http/2 and http/3 just use data frames.
+	Chunked
+}
+
+impl Framing {
+	/// Returns the framing of a request body as defined in RFC 9112
+	/// section 6.3.
+	///
+	/// Without `content-length` or `transfer-encoding` the request has no
+	/// body, unless `until_eof` is true.
+	///
+	/// ## Errors
+	/// Returns `Error::InvalidHeader` if `RequestHeader::check_framing`
+	/// fails.
+	pub fn from_request_header(
+		header: &RequestHeader,
+		until_eof: bool
+	) -> Result<Self, Error> {
+		header.check_framing().map_err(Error::invalid_header)?;
+
+		if header.values.get("transfer-encoding").is_some() {
+			return Ok(Self::Chunked)
+		}
+
+		match header.value("content-length") {
+			// check_framing made sure that every value is the same number
+			Some(len) => len.split(',').next().unwrap().trim().parse()
+				.map(Self::ContentLength)
+				.map_err(|_| Error::invalid_header("invalid content-length")),
+			None if until_eof => Ok(Self::Chunked),
+			None => Ok(Self::None)
+		}
+	}
+}
+
+/// A backend which writes responses to the wire, see
+/// `Response::write_into`.
+pub trait WireWriter {
+	/// Writes the status and the header fields.
+	///
+	/// The `content-length` and `transfer-encoding` values already match
+	/// the framing, a backend which does not use them (http/2 or http/3)
+	/// should skip them.
+	fn write_head(
+		&mut self,
+		header: &ResponseHeader,
+		framing: Framing
+	) -> impl Future<Output=io::Result<()>> + Send;
+
+	/// Writes a chunk of the body, is never called with the framing
+	/// `Framing::None`.
+	fn write_body_chunk(
+		&mut self,
+		chunk: Bytes
+	) -> impl Future<Output=io::Result<()>> + Send;
+
+	/// Finishes the response, trailers are only sent if the framing is
+	/// `Framing::Chunked`.
+	fn finish(
+		&mut self,
+		trailers: Option<HeaderValues>
+	) -> impl Future<Output=io::Result<()>> + Send;
+}
+
+/// A backend which reads requests from the wire, see `Request::read_from`.
+pub trait WireReader {
+	/// Reads and parses the request line and the header fields.
+	fn read_head(
+		&mut self
+	) -> impl Future<Output=io::Result<RequestHeader>> + Send;
+
+	/// Reads the next chunk of the body, returns `None` if the body is
+	/// finished.
+	///
+	/// The chunks should already be decoded, with http/1.1 this means the
+	/// chunked transfer coding is removed.
+	fn read_body_chunk(
+		&mut self
+	) -> impl Future<Output=io::Result<Option<Bytes>>> + Send;
+
+	/// Returns true if the protocol delimits the body itself, like http/2 or
+	/// http/3, a request without `content-length` is then read until
+	/// `read_body_chunk` returns `None`.
+	fn body_until_eof(&self) -> bool {
+		false
+	}
+}
+
+/// Limits which are applied to a request read by `Request::read_from`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadLimits {
+	/// The maximum size of the body.
+	pub size_limit: Option<usize>,
+	/// The timeout to read the body, starts when the body is read.
+	pub timeout: Option<Duration>
+}
+
+type ReadChunk<R> = Pin<Box<
+	dyn Future<Output=(R, io::Result<Option<Bytes>>)> + Send
+>>;
+
+/// A stream of the body chunks of a `WireReader` which checks that they
+/// match the `content-length`.
+pub(crate) struct WireBody<R> {
+	state: State<R>,
+	expected: Option<u64>,
+	read: u64
+}
+
+enum State<R> {
+	Idle(R),
+	Reading(ReadChunk<R>),
+	Done
+}
+
+impl<R> WireBody<R> {
+	pub(crate) fn new(reader: R, expected: Option<u64>) -> Self {
+		Self { state: State::Idle(reader), expected, read: 0 }
+	}
+}
+
+// the reader is never pinned, it is only moved into and out of the future
+impl<R> Unpin for WireBody<R> {}
+
+impl<R> Stream for WireBody<R>
+where R: WireReader + Send + 'static {
+	type Item = io::Result<Bytes>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context
+	) -> Poll<Option<io::Result<Bytes>>> {
+		let me = self.get_mut();
+
+		let mut fut = match mem::replace(&mut me.state, State::Done) {
+			State::Idle(mut reader) => Box::pin(async move {
+				let chunk = reader.read_body_chunk().await;
+				(reader, chunk)
+			}),
+			State::Reading(fut) => fut,
+			State::Done => return Poll::Ready(None)
+		};
+
+		let (reader, chunk) = match fut.as_mut().poll(cx) {
+			Poll::Ready(r) => r,
+			Poll::Pending => {
+				me.state = State::Reading(fut);
+				return Poll::Pending
+			}
+		};
+
+		let chunk = match chunk {
+			Ok(Some(chunk)) => chunk,
+			Ok(None) if me.expected.is_some_and(|len| me.read < len) => {
+				return Poll::Ready(Some(Err(io::Error::new(
+					io::ErrorKind::UnexpectedEof,
+					"body is shorter than the content-length"
+				))))
+			},
+			Ok(None) => return Poll::Ready(None),
+			Err(e) => return Poll::Ready(Some(Err(e)))
+		};
+
+		me.read += chunk.len() as u64;
+		if me.expected.is_some_and(|len| me.read > len) {
+			return Poll::Ready(Some(Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				"body is longer than the content-length"
+			))))
+		}
+
+		me.state = State::Idle(reader);
+
+		Poll::Ready(Some(Ok(chunk)))
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Request;
+	use crate::header::{test_request_header, Method};
+
+	use std::collections::VecDeque;
+
+	struct Reader {
+		header: Option<RequestHeader>,
+		chunks: VecDeque<&'static str>
+	}
+
+	impl WireReader for Reader {
+		async fn read_head(&mut self) -> io::Result<RequestHeader> {
+			Ok(self.header.take().unwrap())
+		}
+
+		async fn read_body_chunk(&mut self) -> io::Result<Option<Bytes>> {
+			Ok(self.chunks.pop_front().map(Bytes::from))
+		}
+	}
+
+	async fn read(
+		values: &[(&'static str, &str)],
+		chunks: &[&'static str]
+	) -> Result<Request, Error> {
+		let reader = Reader {
+			header: Some(test_request_header(Method::POST, "/", values)),
+			chunks: chunks.iter().copied().collect()
+		};
+		Request::read_from(reader, ReadLimits::default()).await
+	}
+
+	#[test]
+	fn test_request_framing() {
+
+		let framing = |values, until_eof| {
+			let header = test_request_header(Method::POST, "/", values);
+			Framing::from_request_header(&header, until_eof)
+		};
+
+		assert_eq!(framing(&[], false).unwrap(), Framing::None);
+		assert_eq!(framing(&[], true).unwrap(), Framing::Chunked);
+		assert_eq!(
+			framing(&[("content-length", "10")], true).unwrap(),
+			Framing::ContentLength(10)
+		);
+		assert_eq!(
+			framing(&[("transfer-encoding", "gzip, chunked")], false).unwrap(),
+			Framing::Chunked
+		);
+
+		assert!(framing(&[("content-length", "-1")], false).is_err());
+		assert!(framing(&[("transfer-encoding", "gzip")], false).is_err());
+		assert!(framing(&[
+			("content-length", "1"),
+			("transfer-encoding", "chunked")
+		], false).is_err());
+
+		// smuggling attempts
+		assert!(framing(&[("content-length", "+5")], false).is_err());
+		assert!(framing(&[
+			("content-length", "5"),
+			("content-length", "6")
+		], false).is_err());
+		assert!(framing(&[
+			("transfer-encoding", "chunked"),
+			("transfer-encoding", "gzip")
+		], false).is_err());
+		assert_eq!(
+			framing(&[
+				("content-length", "5"),
+				("content-length", "5")
+			], false).unwrap(),
+			Framing::ContentLength(5)
+		);
+		assert_eq!(
+			framing(&[
+				("transfer-encoding", "gzip"),
+				("transfer-encoding", "chunked")
+			], false).unwrap(),
+			Framing::Chunked
+		);
+
+	}
+
+	#[tokio::test]
+	async fn test_read_from() {
+
+		let req = read(&[("content-length", "6")], &["abc", "def"]).await
+			.unwrap();
+		assert_eq!(req.body.into_string().await.unwrap(), "abcdef");
+
+		let req = read(&[("transfer-encoding", "chunked")], &["a", "b"]).await
+			.unwrap();
+		assert_eq!(req.body.into_string().await.unwrap(), "ab");
+
+		let req = read(&[("content-length", "2")], &["abc"]).await.unwrap();
+		assert!(req.body.into_bytes().await.is_err());
+
+		let req = read(&[("content-length", "4")], &["abc"]).await.unwrap();
+		assert!(req.body.into_bytes().await.is_err());
+
+		let e = read(&[
+			("content-length", "3"),
+			("content-length", "4")
+		], &["abc"]).await.unwrap_err();
+		assert!(matches!(e, Error::InvalidHeader(_)));
+
+	}
+
+}
@@ -0,0 +1,274 @@
+//! Property based round trip tests for the header parsers.
+
+use fire_http_representation::header::{
+	HttpDate, Priority, TraceParent, TraceState, SetCookie, Query,
+	ContentEncoding, ContentType, Mime, IfRange, RequestHeader, HeaderValues,
+	Method
+};
+use fire_http_representation::header::cookie::SameSite;
+use fire_http_representation::header::negotiate::{
+	negotiate, SupportedRepresentations
+};
+use fire_http_representation::header::percent::{
+	encode_path_segment, decode_path_segment
+};
+
+use proptest::prelude::*;
+
+
+const EXTENSIONS: &[&str] = &[
+	"txt", "html", "js", "css", "json", "csv", "doc", "pdf", "php", "rtf",
+	"sh", "vsd", "xml", "rss", "atom", "jpg", "png", "gif", "svg", "ico",
+	"tiff", "webp", "eot", "ttf", "woff", "woff2", "avi", "ogv", "webm", "mp4",
+	"aac", "mp3", "oga", "wav", "weba", "rar", "tar", "zip", "7z", "jar",
+	"bin", "wasm"
+];
+
+fn mime() -> impl Strategy<Value=Mime> {
+	prop::sample::select(EXTENSIONS)
+		.prop_map(|ext| Mime::from_extension(ext).unwrap())
+}
+
+fn request_header(values: HeaderValues) -> RequestHeader {
+	RequestHeader {
+		address: ([127, 0, 0, 1], 0).into(),
+		method: Method::GET,
+		uri: "/".parse().unwrap(),
+		values,
+		connection: None
+	}
+}
+
+proptest! {
+	#[test]
+	fn http_date(secs in 0u64..253402300799) {
+		let date = HttpDate::from_unix_secs(secs);
+		prop_assert_eq!(date.to_string().parse::<HttpDate>().unwrap(), date);
+	}
+
+	#[test]
+	fn priority(urgency in 0u8..=7, incremental: bool) {
+		let prio = Priority::new().urgency(urgency).incremental(incremental);
+		prop_assert_eq!(prio.to_string().parse::<Priority>().unwrap(), prio);
+	}
+
+	#[test]
+	fn trace_parent(
+		trace_id: [u8; 16],
+		parent_id: [u8; 8],
+		flags: u8
+	) {
+		if let Some(tp) = TraceParent::new(trace_id, parent_id, flags) {
+			prop_assert_eq!(tp.to_string().parse::<TraceParent>().unwrap(), tp);
+		}
+	}
+
+	#[test]
+	fn trace_state(
+		entries in prop::collection::vec(
+			("[a-z][a-z0-9_*/-]{0,20}", "[!-+.-<>-~]{1,20}"),
+			0..10
+		)
+	) {
+		let mut ts = TraceState::new();
+		for (key, val) in entries {
+			ts.insert(key, val).unwrap();
+		}
+		prop_assert_eq!(ts.to_string().parse::<TraceState>().unwrap(), ts);
+	}
+
+	#[test]
+	fn set_cookie(
+		name in "[a-zA-Z0-9_]{1,20}",
+		value in "[a-zA-Z0-9]{0,20}",
+		expires in prop::option::of(0u64..253402300799),
+		max_age: Option<i64>,
+		domain in prop::option::of("[a-z]{1,10}\\.[a-z]{2,5}"),
+		path in prop::option::of("/[a-z/]{0,10}"),
+		secure: bool,
+		http_only: bool,
+		same_site in prop::option::of(prop_oneof![
+			Just(SameSite::Strict),
+			Just(SameSite::Lax),
+			Just(SameSite::None)
+		])
+	) {
+		let mut cookie = SetCookie::new(name, value);
+		cookie.expires = expires.map(HttpDate::from_unix_secs);
+		cookie.max_age = max_age;
+		cookie.domain = domain;
+		cookie.path = path;
+		cookie.secure = secure;
+		cookie.http_only = http_only;
+		cookie.same_site = same_site;
+
+		let parsed: SetCookie = cookie.to_string().parse().unwrap();
+		prop_assert_eq!(parsed, cookie);
+	}
+
+	#[test]
+	fn query(pairs in prop::collection::vec((".+", ".*"), 0..10)) {
+		let query: Query = pairs.into_iter().collect();
+		prop_assert_eq!(Query::parse(&query.to_query_string()), query);
+	}
+
+	#[test]
+	fn content_encoding(
+		list in prop::collection::vec("[a-z0-9-]{1,10}", 0..5)
+	) {
+		let list: Vec<ContentEncoding> = list.iter()
+			.map(|e| e.parse().unwrap())
+			.collect();
+		let formatted = ContentEncoding::format_list(&list);
+		prop_assert_eq!(ContentEncoding::parse_list(&formatted), list);
+	}
+
+	#[test]
+	fn content_type(mime in mime()) {
+		let ct = ContentType::from(mime);
+		prop_assert_eq!(ContentType::from(ct.to_string().as_str()), ct);
+		prop_assert_eq!(mime.to_string().parse::<Mime>(), Ok(mime));
+	}
+
+	#[test]
+	fn unknown_content_type(ty in "[a-z]{1,10}/x-[a-z0-9.+-]{1,20}") {
+		let ct = ContentType::from(ty.as_str());
+		prop_assert_eq!(&ct, &ContentType::Unknown(ty.clone()));
+		prop_assert_eq!(ContentType::from(ct.to_string()), ct);
+	}
+
+	#[test]
+	fn accept(
+		exts in prop::sample::subsequence(EXTENSIONS.to_vec(), 1..5)
+			.prop_shuffle(),
+		weights in prop::collection::vec(1u16..=1000, 5)
+	) {
+		let mimes: Vec<_> = exts.iter()
+			.map(|e| Mime::from_extension(e).unwrap())
+			.collect();
+		let supported = mimes.iter()
+			.fold(SupportedRepresentations::new(), |s, m| s.mime(*m));
+
+		let accept = mimes.iter().zip(&weights)
+			.map(|(m, w)| format!("{m};q={}", *w as f32 / 1000.0))
+			.collect::<Vec<_>>()
+			.join(", ");
+
+		// the highest weight wins, on a tie the earlier supported mime
+		let mut expected = (mimes[0], weights[0]);
+		for (mime, weight) in mimes.iter().zip(&weights) {
+			if *weight > expected.1 {
+				expected = (*mime, *weight);
+			}
+		}
+
+		let mut values = HeaderValues::new();
+		values.insert("accept", accept.clone());
+		let negotiated = negotiate(&request_header(values), &supported)
+			.unwrap();
+		prop_assert_eq!(negotiated.mime, Some(expected.0), "{}", accept);
+
+		let mut values = HeaderValues::new();
+		values.insert("accept", "*/*;q=0");
+		prop_assert!(negotiate(&request_header(values), &supported).is_err());
+	}
+
+	#[cfg(feature = "testing")]
+	#[test]
+	fn request_head(
+		method in prop::sample::select(vec![
+			Method::GET, Method::POST, Method::PUT, Method::DELETE
+		]),
+		path in "(/[a-zA-Z0-9._~-]{1,10}){0,4}",
+		query in prop::option::of("[a-z]{1,5}=[a-z0-9]{0,5}"),
+		values in prop::collection::vec(
+			("x-[a-z0-9-]{1,15}", "[!-~]([ -~]{0,30}[!-~])?"),
+			0..10
+		),
+		body in prop::collection::vec(any::<u8>(), 0..100)
+	) {
+		use fire_http_representation::Request;
+		use fire_http_representation::wire::{Framing, ReadLimits};
+		use fire_http_representation::testing::Loopback;
+
+		let uri = match &query {
+			Some(q) => format!("{path}/?{q}"),
+			None => format!("{path}/")
+		};
+
+		let mut header = request_header(HeaderValues::new());
+		header.method = method;
+		header.uri = uri.parse().unwrap();
+		for (key, val) in &values {
+			header.values.insert_cased(key, val.as_str());
+		}
+		header.values.insert("content-length", body.len().to_string());
+		let expected = header.clone();
+
+		prop_assert_eq!(
+			Framing::from_request_header(&header, false).unwrap(),
+			Framing::ContentLength(body.len() as u64)
+		);
+
+		let rt = tokio::runtime::Builder::new_current_thread()
+			.build()
+			.unwrap();
+		let (req, read_body) = rt.block_on(async {
+			let Loopback { mut client, reader, .. } = Loopback::new();
+			let sent = Request::new(header, body.clone().into());
+
+			let (sent, read) = tokio::join!(
+				client.send_request(sent),
+				async {
+					let mut req = Request::read_from(
+						reader,
+						ReadLimits::default()
+					).await.unwrap();
+					let body = req.take_body().into_bytes().await.unwrap();
+					(req, body)
+				}
+			);
+			sent.unwrap();
+			read
+		});
+
+		prop_assert_eq!(&req.header.method, &expected.method);
+		prop_assert_eq!(&req.header.uri, &expected.uri);
+		prop_assert_eq!(req.header.values.len(), expected.values.len());
+		for (key, val) in expected.values.iter() {
+			prop_assert_eq!(req.header.values.get(key), Some(val));
+		}
+		prop_assert_eq!(read_body, body);
+	}
+
+	#[test]
+	fn if_range_etag(tag in "[!#-~]{0,20}") {
+		let range = IfRange::ETag(format!("\"{tag}\""));
+		prop_assert_eq!(range.to_string().parse::<IfRange>().unwrap(), range);
+	}
+
+	#[test]
+	fn if_range_date(secs in 0u64..253402300799) {
+		let range = IfRange::Date(HttpDate::from_unix_secs(secs));
+		prop_assert_eq!(range.to_string().parse::<IfRange>().unwrap(), range);
+	}
+
+	#[test]
+	fn path_segment(s in ".*") {
+		let encoded = encode_path_segment(&s);
+		prop_assert_eq!(decode_path_segment(&encoded).unwrap(), s.as_str());
+	}
+
+	#[test]
+	fn parsers_do_not_panic(s in ".*") {
+		let _ = s.parse::<HttpDate>();
+		let _ = s.parse::<Priority>();
+		let _ = s.parse::<TraceParent>();
+		let _ = s.parse::<TraceState>();
+		let _ = s.parse::<SetCookie>();
+		let _ = Query::parse(&s);
+		let _ = ContentEncoding::parse_list(&s);
+		let _ = s.parse::<IfRange>();
+		let _ = ContentType::from(s.as_str());
+	}
+}